@@ -0,0 +1,165 @@
+#[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+mod sync {
+    use akatsuki_pp::{
+        mania::ManiaGradualDifficultyAttributes, osu::OsuGradualDifficultyAttributes, Accuracy,
+        Beatmap, CatchPP, CatchStars, ManiaPP, ManiaStars, OsuPP, OsuStars, TaikoPP, TaikoStars,
+    };
+    use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+
+    const OSU: &[u8] = include_bytes!("../maps/2785319.osu");
+    const TAIKO: &[u8] = include_bytes!("../maps/1028484.osu");
+    const CATCH: &[u8] = include_bytes!("../maps/2118524.osu");
+    const MANIA: &[u8] = include_bytes!("../maps/1974394.osu");
+
+    const MAPS: [(&str, &[u8]); 4] = [
+        ("osu", OSU),
+        ("taiko", TAIKO),
+        ("catch", CATCH),
+        ("mania", MANIA),
+    ];
+
+    fn parse(bytes: &[u8]) -> Beatmap {
+        Beatmap::from_bytes(bytes).unwrap()
+    }
+
+    pub fn parsing(c: &mut Criterion) {
+        let mut group = c.benchmark_group("parse");
+
+        for (name, bytes) in MAPS {
+            group.bench_function(name, |b| b.iter(|| parse(black_box(bytes))));
+        }
+
+        group.finish();
+    }
+
+    pub fn difficulty(c: &mut Criterion) {
+        let mut group = c.benchmark_group("difficulty");
+
+        let map = parse(OSU);
+        group.bench_function("osu", |b| b.iter(|| OsuStars::new(&map).calculate()));
+
+        let map = parse(TAIKO);
+        group.bench_function("taiko", |b| b.iter(|| TaikoStars::new(&map).calculate()));
+
+        let map = parse(CATCH);
+        group.bench_function("catch", |b| b.iter(|| CatchStars::new(&map).calculate()));
+
+        let map = parse(MANIA);
+        group.bench_function("mania", |b| b.iter(|| ManiaStars::new(&map).calculate()));
+
+        group.finish();
+    }
+
+    pub fn performance(c: &mut Criterion) {
+        let mut group = c.benchmark_group("performance");
+
+        let map = parse(OSU);
+        let attrs = OsuStars::new(&map).calculate();
+        group.bench_function("osu", |b| {
+            b.iter(|| {
+                OsuPP::new(&map)
+                    .attributes(attrs.clone())
+                    .accuracy(Accuracy::from_percent(98.5).unwrap())
+                    .n_misses(2)
+                    .calculate()
+            })
+        });
+
+        let map = parse(TAIKO);
+        let attrs = TaikoStars::new(&map).calculate();
+        group.bench_function("taiko", |b| {
+            b.iter(|| {
+                TaikoPP::new(&map)
+                    .attributes(attrs.clone())
+                    .accuracy(Accuracy::from_percent(98.5).unwrap())
+                    .n_misses(2)
+                    .calculate()
+            })
+        });
+
+        let map = parse(CATCH);
+        let attrs = CatchStars::new(&map).calculate();
+        group.bench_function("catch", |b| {
+            b.iter(|| {
+                CatchPP::new(&map)
+                    .attributes(attrs.clone())
+                    .accuracy(Accuracy::from_percent(98.5).unwrap())
+                    .misses(2)
+                    .calculate()
+            })
+        });
+
+        let map = parse(MANIA);
+        let attrs = ManiaStars::new(&map).calculate();
+        group.bench_function("mania", |b| {
+            b.iter(|| {
+                ManiaPP::new(&map)
+                    .attributes(attrs)
+                    .accuracy(Accuracy::from_percent(98.5).unwrap())
+                    .n_misses(2)
+                    .calculate()
+            })
+        });
+
+        group.finish();
+    }
+
+    pub fn gradual(c: &mut Criterion) {
+        let mut group = c.benchmark_group("gradual");
+
+        // The time per object should stay constant as the map grows
+        for (name, bytes) in [("osu", OSU), ("mania", MANIA)] {
+            let map = parse(bytes);
+
+            for times in [1, 2, 4, 8] {
+                let map = map.repeat(times).into_owned();
+                group.throughput(Throughput::Elements(map.hit_objects.len() as u64));
+
+                group.bench_with_input(BenchmarkId::new(name, times), &map, |b, map| match name {
+                    "osu" => b.iter(|| OsuGradualDifficultyAttributes::new(map, 0).last()),
+                    _ => b.iter(|| ManiaGradualDifficultyAttributes::new(map, 0).last()),
+                });
+            }
+        }
+
+        group.finish();
+    }
+
+    pub fn full(c: &mut Criterion) {
+        let mut group = c.benchmark_group("parse_and_pp");
+
+        group.bench_function("osu", |b| {
+            b.iter(|| OsuPP::new(&parse(black_box(OSU))).calculate())
+        });
+
+        group.bench_function("taiko", |b| {
+            b.iter(|| TaikoPP::new(&parse(black_box(TAIKO))).calculate())
+        });
+
+        group.bench_function("catch", |b| {
+            b.iter(|| CatchPP::new(&parse(black_box(CATCH))).calculate())
+        });
+
+        group.bench_function("mania", |b| {
+            b.iter(|| ManiaPP::new(&parse(black_box(MANIA))).calculate())
+        });
+
+        group.finish();
+    }
+}
+
+#[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+criterion::criterion_group!(
+    benches,
+    sync::parsing,
+    sync::difficulty,
+    sync::performance,
+    sync::gradual,
+    sync::full
+);
+
+#[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+criterion::criterion_main!(benches);
+
+#[cfg(any(feature = "async_tokio", feature = "async_std"))]
+fn main() {}
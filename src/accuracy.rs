@@ -0,0 +1,57 @@
+/// The accuracy of a play, stored as a fraction between `0.0` and `1.0`.
+///
+/// All performance calculators accept it for their `accuracy` method. There is
+/// no conversion from bare floats so the scale must always be stated explicitly
+/// through one of the validating constructors.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::Accuracy;
+///
+/// let acc = Accuracy::from_percent(98.5).unwrap();
+/// assert_eq!(Accuracy::from_fraction(0.985), Some(acc));
+///
+/// // out of range
+/// assert!(Accuracy::from_fraction(98.5).is_none());
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Accuracy(f64);
+
+impl Accuracy {
+    /// Create the accuracy from a fraction between `0.0` and `1.0`.
+    ///
+    /// Returns `None` if the value is out of range or not a number.
+    #[inline]
+    pub fn from_fraction(fraction: f64) -> Option<Self> {
+        if (0.0..=1.0).contains(&fraction) {
+            Some(Self(fraction))
+        } else {
+            None
+        }
+    }
+
+    /// Create the accuracy from a percentage between `0.0` and `100.0`.
+    ///
+    /// Returns `None` if the value is out of range or not a number.
+    #[inline]
+    pub fn from_percent(percent: f64) -> Option<Self> {
+        if (0.0..=100.0).contains(&percent) {
+            Some(Self(percent / 100.0))
+        } else {
+            None
+        }
+    }
+
+    /// The accuracy as fraction between `0.0` and `1.0`.
+    #[inline]
+    pub fn fraction(self) -> f64 {
+        self.0
+    }
+
+    /// The accuracy as percentage between `0.0` and `100.0`.
+    #[inline]
+    pub fn percent(self) -> f64 {
+        self.0 * 100.0
+    }
+}
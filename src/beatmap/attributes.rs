@@ -1,4 +1,4 @@
-use crate::{Beatmap, GameMode, Mods};
+use crate::{util::difficulty_range, Beatmap, GameMode, Mods};
 
 /// Summary struct for a [`Beatmap`]'s attributes.
 #[derive(Clone, Debug, PartialEq)]
@@ -9,7 +9,7 @@ pub struct BeatmapAttributes {
     pub od: f64,
     /// The circle size.
     pub cs: f64,
-    /// The health drain rate
+    /// The health drain rate, adjusted by EZ (x0.5) and HR (x1.4, capped at 10).
     pub hp: f64,
     /// The clock rate with respect to mods.
     pub clock_rate: f64,
@@ -24,6 +24,14 @@ pub struct BeatmapHitWindows {
     pub ar: f64,
     /// Hit window for overall difficulty i.e. time to hit a 300 ("Great") in milliseconds.
     pub od: f64,
+    /// Time to hit a 100 ("Ok") in milliseconds.
+    ///
+    /// `None` for osu!catch which has no such judgement.
+    pub n100: Option<f64>,
+    /// Time to hit a 50 ("Meh") in milliseconds.
+    ///
+    /// `None` for osu!taiko and osu!catch which have no such judgement.
+    pub n50: Option<f64>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -140,47 +148,67 @@ impl BeatmapAttributesBuilder {
         let preempt = difficulty_range(raw_ar as f64, 1800.0, 1200.0, 450.0) / clock_rate;
 
         // OD
-        let hit_window = match self.mode {
-            GameMode::Osu | GameMode::Catch => {
+        let (hit_window, n100, n50) = match self.mode {
+            GameMode::Osu => {
+                let raw_od = mod_mult(self.od) as f64;
+
+                let great = difficulty_range(raw_od, Self::OSU_MIN, Self::OSU_AVG, Self::OSU_MAX);
+                let ok = difficulty_range(raw_od, 140.0, 100.0, 60.0);
+                let meh = difficulty_range(raw_od, 200.0, 150.0, 100.0);
+
+                (
+                    great / clock_rate,
+                    Some(ok / clock_rate),
+                    Some(meh / clock_rate),
+                )
+            }
+            GameMode::Catch => {
                 let raw_od = mod_mult(self.od);
 
-                difficulty_range(raw_od as f64, Self::OSU_MIN, Self::OSU_AVG, Self::OSU_MAX)
-                    / clock_rate
+                let diff_range =
+                    difficulty_range(raw_od as f64, Self::OSU_MIN, Self::OSU_AVG, Self::OSU_MAX);
+
+                (diff_range / clock_rate, None, None)
             }
             GameMode::Taiko => {
-                let raw_od = mod_mult(self.od);
+                let raw_od = mod_mult(self.od) as f64;
 
-                let diff_range = difficulty_range(
-                    raw_od as f64,
-                    Self::TAIKO_MIN,
-                    Self::TAIKO_AVG,
-                    Self::TAIKO_MAX,
-                );
+                let great =
+                    difficulty_range(raw_od, Self::TAIKO_MIN, Self::TAIKO_AVG, Self::TAIKO_MAX);
+                let ok = difficulty_range(raw_od, 120.0, 80.0, 50.0);
 
-                diff_range / clock_rate
+                (great / clock_rate, Some(ok / clock_rate), None)
             }
             GameMode::Mania => {
-                let mut value = if !self.converted {
-                    34.0 + 3.0 * (10.0 - self.od).clamp(0.0, 10.0)
+                let od = (10.0 - self.od).clamp(0.0, 10.0);
+
+                let [great, ok, meh] = if !self.converted {
+                    [34.0 + 3.0 * od, 97.0 + 3.0 * od, 121.0 + 3.0 * od]
                 } else if self.od > 4.0 {
-                    34.0
+                    [34.0, 97.0, 121.0]
                 } else {
-                    47.0
+                    [47.0, 97.0, 121.0]
                 };
 
-                if mods.hr() {
-                    value /= 1.4;
-                } else if mods.ez() {
-                    value *= 1.4;
-                }
+                let window = |mut value: f32| {
+                    if mods.hr() {
+                        value /= 1.4;
+                    } else if mods.ez() {
+                        value *= 1.4;
+                    }
 
-                ((value as f64 * clock_rate).floor() / clock_rate).ceil()
+                    ((value as f64 * clock_rate).floor() / clock_rate).ceil()
+                };
+
+                (window(great), Some(window(ok)), Some(window(meh)))
             }
         };
 
         BeatmapHitWindows {
             ar: preempt,
             od: hit_window,
+            n100,
+            n50,
         }
     }
 
@@ -202,7 +230,7 @@ impl BeatmapAttributesBuilder {
         }
 
         let hit_windows = self.hit_windows();
-        let BeatmapHitWindows { ar, od } = hit_windows;
+        let BeatmapHitWindows { ar, od, .. } = hit_windows;
 
         // AR
         let ar = if ar > 1200.0 {
@@ -240,17 +268,7 @@ impl From<&Beatmap> for BeatmapAttributesBuilder {
             hp: map.hp,
             mods: None,
             clock_rate: None,
-            converted: false,
+            converted: map.is_convert,
         }
     }
 }
-
-fn difficulty_range(difficulty: f64, min: f64, mid: f64, max: f64) -> f64 {
-    if difficulty > 5.0 {
-        mid + (max - mid) * (difficulty - 5.0) / 5.0
-    } else if difficulty < 5.0 {
-        mid - (mid - min) * (5.0 - difficulty) / 5.0
-    } else {
-        mid
-    }
-}
@@ -0,0 +1,156 @@
+use std::{cmp::Ordering, iter::FromIterator};
+
+use crate::parse::{HitObject, HitObjectKind};
+
+use super::{Beatmap, DifficultyPoint, GameMode, TimingPoint};
+
+/// Build a [`Beatmap`] from hit objects instead of parsing a .osu file.
+///
+/// Unspecified difficulty settings default to 5 just like for parsing.
+#[derive(Clone, Debug)]
+pub struct BeatmapBuilder {
+    map: Beatmap,
+}
+
+impl BeatmapBuilder {
+    const DEFAULT_DIFFICULTY: f32 = 5.0;
+
+    /// Create a new [`BeatmapBuilder`] for the given mode.
+    #[inline]
+    pub fn new(mode: GameMode) -> Self {
+        let map = Beatmap {
+            mode,
+            version: 14,
+            ar: Self::DEFAULT_DIFFICULTY,
+            od: Self::DEFAULT_DIFFICULTY,
+            cs: Self::DEFAULT_DIFFICULTY,
+            hp: Self::DEFAULT_DIFFICULTY,
+            slider_mult: 1.0,
+            tick_rate: 1.0,
+            stack_leniency: 0.7,
+            ..Default::default()
+        };
+
+        Self { map }
+    }
+
+    /// Specify the approach rate.
+    #[inline]
+    pub fn ar(&mut self, ar: f32) -> &mut Self {
+        self.map.ar = ar;
+
+        self
+    }
+
+    /// Specify the overall difficulty.
+    #[inline]
+    pub fn od(&mut self, od: f32) -> &mut Self {
+        self.map.od = od;
+
+        self
+    }
+
+    /// Specify the circle size.
+    #[inline]
+    pub fn cs(&mut self, cs: f32) -> &mut Self {
+        self.map.cs = cs;
+
+        self
+    }
+
+    /// Specify the drain rate.
+    #[inline]
+    pub fn hp(&mut self, hp: f32) -> &mut Self {
+        self.map.hp = hp;
+
+        self
+    }
+
+    /// Specify the base slider velocity in pixels per beat.
+    #[inline]
+    pub fn slider_mult(&mut self, slider_mult: f64) -> &mut Self {
+        self.map.slider_mult = slider_mult;
+
+        self
+    }
+
+    /// Specify the amount of slider ticks per beat.
+    #[inline]
+    pub fn tick_rate(&mut self, tick_rate: f64) -> &mut Self {
+        self.map.tick_rate = tick_rate;
+
+        self
+    }
+
+    /// Specify the stack leniency.
+    #[inline]
+    pub fn stack_leniency(&mut self, stack_leniency: f32) -> &mut Self {
+        self.map.stack_leniency = stack_leniency;
+
+        self
+    }
+
+    /// Add a [`TimingPoint`].
+    #[inline]
+    pub fn timing_point(&mut self, point: TimingPoint) -> &mut Self {
+        self.map.timing_points.push(point);
+
+        self
+    }
+
+    /// Add a [`DifficultyPoint`].
+    #[inline]
+    pub fn difficulty_point(&mut self, point: DifficultyPoint) -> &mut Self {
+        self.map.difficulty_points.push_if_not_redundant(point);
+
+        self
+    }
+
+    /// Add a [`HitObject`].
+    ///
+    /// Malformed sliders are added as circles, see [`HitObjectKind::Slider`].
+    pub fn hit_object(&mut self, mut h: HitObject) -> &mut Self {
+        h.kind.sanitize();
+
+        match h.kind {
+            HitObjectKind::Circle => self.map.n_circles += 1,
+            HitObjectKind::Slider { .. } | HitObjectKind::Hold { .. } => self.map.n_sliders += 1,
+            HitObjectKind::Spinner { .. } => self.map.n_spinners += 1,
+        }
+
+        self.map.hit_objects.push(h);
+        self.map.sounds.push(0);
+
+        self
+    }
+
+    /// Add multiple [`HitObject`]s.
+    pub fn hit_objects<I>(&mut self, hit_objects: I) -> &mut Self
+    where
+        I: IntoIterator<Item = HitObject>,
+    {
+        for h in hit_objects {
+            self.hit_object(h);
+        }
+
+        self
+    }
+
+    /// Create the [`Beatmap`] with hit objects sorted by their start time.
+    pub fn build(&self) -> Beatmap {
+        let mut map = self.map.clone();
+
+        map.hit_objects
+            .sort_by(|h1, h2| h1.partial_cmp(h2).unwrap_or(Ordering::Equal));
+
+        map
+    }
+}
+
+impl FromIterator<HitObject> for Beatmap {
+    /// Create an osu!standard [`Beatmap`] with default difficulty settings.
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = HitObject>>(iter: I) -> Self {
+        BeatmapBuilder::new(GameMode::Osu).hit_objects(iter).build()
+    }
+}
@@ -25,8 +25,9 @@ mod pattern_type;
 const MAX_NOTES_FOR_DENSITY: usize = 7;
 
 impl Beatmap {
-    pub(in crate::beatmap) fn convert_to_mania(&self) -> Self {
+    pub(in crate::beatmap) fn convert_to_mania(&self, key_count: Option<u8>) -> Self {
         let mut map = self.clone_without_hit_objects(false);
+        map.is_convert = true;
 
         let mut n_circles = 0;
         let mut n_sliders = 0;
@@ -54,11 +55,13 @@ impl Beatmap {
         let percent_slider_or_spinner =
             (slider_or_spinner_count as f32 / self.hit_objects.len() as f32) as f64;
 
-        let target_columns = if percent_slider_or_spinner < 0.2 {
+        let target_columns = if let Some(key_count) = key_count {
+            key_count as f32
+        } else if percent_slider_or_spinner < 0.2 {
             7.0
         } else if percent_slider_or_spinner < 0.3 || rounded_cs >= 5.0 {
             (6 + (rounded_od > 5.0) as u8) as f32
-        } else if percent_slider_or_spinner as f64 > 0.6 {
+        } else if percent_slider_or_spinner > 0.6 {
             (4 + (rounded_od > 4.0) as u8) as f32
         } else {
             (rounded_od + 1.0).clamp(4.0, 7.0)
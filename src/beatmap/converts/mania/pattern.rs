@@ -40,6 +40,7 @@ impl Pattern {
             pos: Pos2::new(column_to_pos(column, generator.total_columns)),
             start_time: generator.hit_object.start_time,
             kind: HitObjectKind::Circle,
+            new_combo: false,
         };
 
         Self::new_single(hit_object, column)
@@ -50,6 +51,7 @@ impl Pattern {
             pos: Pos2::new(column_to_pos(column, generator.total_columns)),
             start_time: generator.hit_object.start_time,
             kind: HitObjectKind::Circle,
+            new_combo: false,
         };
 
         self.contained_columns.insert(column);
@@ -70,12 +72,14 @@ impl Pattern {
                 kind: HitObjectKind::Hold {
                     end_time: generator.end_time,
                 },
+                new_combo: false,
             }
         } else {
             HitObject {
                 pos,
                 start_time: generator.hit_object.start_time,
                 kind: HitObjectKind::Circle,
+                new_combo: false,
             }
         };
 
@@ -95,6 +99,7 @@ impl Pattern {
                 pos,
                 start_time: start_time as f64,
                 kind: HitObjectKind::Circle,
+                new_combo: false,
             }
         } else {
             HitObject {
@@ -103,6 +108,7 @@ impl Pattern {
                 kind: HitObjectKind::Hold {
                     end_time: end_time as f64,
                 },
+                new_combo: false,
             }
         };
 
@@ -123,6 +129,7 @@ impl Pattern {
                 pos,
                 start_time: start_time as f64,
                 kind: HitObjectKind::Circle,
+                new_combo: false,
             }
         } else {
             HitObject {
@@ -131,6 +138,7 @@ impl Pattern {
                 kind: HitObjectKind::Hold {
                     end_time: end_time as f64,
                 },
+                new_combo: false,
             }
         };
 
@@ -11,6 +11,7 @@ const OSU_BASE_SCORING_DIST: f32 = 100.0;
 impl Beatmap {
     pub(in crate::beatmap) fn convert_to_taiko(&self) -> Self {
         let mut map = self.clone_without_hit_objects(true);
+        map.is_convert = true;
         let mut curve_bufs = CurveBuffers::default();
 
         map.slider_mult *= LEGACY_TAIKO_VELOCITY_MULTIPLIER as f64;
@@ -36,6 +37,7 @@ impl Beatmap {
                         let mut j = obj.start_time;
 
                         let edge_sound_count = edge_sounds.len().max(1);
+                        let mut new_combo = obj.new_combo;
 
                         while j
                             <= obj.start_time + params.duration as f64 + params.tick_spacing / 8.0
@@ -44,9 +46,11 @@ impl Beatmap {
                                 pos: Default::default(),
                                 start_time: j,
                                 kind: HitObjectKind::Circle,
+                                new_combo,
                             };
 
                             map.hit_objects.push(h);
+                            new_combo = false;
                             map.sounds.push(*edge_sounds.get(i).unwrap_or(sound));
                             map.n_circles += 1;
 
@@ -74,6 +78,7 @@ impl Beatmap {
                         pos: obj.pos,
                         start_time: obj.start_time,
                         kind: HitObjectKind::Spinner { end_time },
+                        new_combo: obj.new_combo,
                     };
 
                     map.hit_objects.push(obj);
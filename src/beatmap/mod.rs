@@ -1,26 +1,45 @@
-use std::{borrow::Cow, cmp::Ordering};
+use std::{borrow::Cow, cmp::Ordering, ops::Range};
 
-use crate::{parse::HitObject, util::SortedVec};
+use crate::{
+    curve::{Curve, CurveBuffers},
+    parse::{HitObject, HitObjectKind},
+    util::{to_hex, SortedVec},
+    Mods,
+};
 
 pub use self::{
     attributes::{BeatmapAttributes, BeatmapAttributesBuilder, BeatmapHitWindows},
     breaks::Break,
+    builder::BeatmapBuilder,
     control_points::{DifficultyPoint, EffectPoint, TimingPoint},
     mode::GameMode,
 };
 
 mod attributes;
 mod breaks;
+mod builder;
 mod control_points;
 mod converts;
 mod mode;
 
+const BASE_SCORING_DISTANCE: f64 = 100.0;
+
 /// The main beatmap struct containing all data relevant
 /// for difficulty and performance calculation
+///
+/// Values derived from the difficulty settings such as the object radius, the preempt time,
+/// or hit windows are not stored but computed on each calculation so modifying e.g.
+/// `cs` or `ar` in place is immediately reflected by subsequent calculations.
+/// Only `n_circles`, `n_sliders`, and `n_spinners` are stored alongside the hit objects
+/// and must be kept in sync manually when modifying `hit_objects`,
+/// see [`count_objects_by_kind`](Beatmap::count_objects_by_kind).
 #[derive(Clone, Default, Debug)]
 pub struct Beatmap {
     /// The game mode.
     pub mode: GameMode,
+    /// Whether the map was converted from osu!standard into its current mode,
+    /// see [`converted`](Beatmap::converted).
+    pub is_convert: bool,
     /// The version of the .osu file.
     pub version: u8,
 
@@ -31,6 +50,11 @@ pub struct Beatmap {
     /// The amount of spinners.
     pub n_spinners: u32,
 
+    /// Milliseconds of silence before the audio starts playing.
+    pub audio_lead_in: f64,
+    /// Time in milliseconds when the audio preview should start.
+    pub preview_time: f64,
+
     /// The approach rate.
     pub ar: f32,
     /// The overall difficulty.
@@ -65,11 +89,72 @@ pub struct Beatmap {
     /// All break points of the beatmap.
     pub breaks: Vec<Break>,
 
+    /// The romanised title of the song.
+    pub title: String,
+
+    /// The title of the song in its original language.
+    pub title_unicode: String,
+
+    /// The romanised artist of the song.
+    pub artist: String,
+
+    /// The artist of the song in its original language.
+    pub artist_unicode: String,
+
     /// The creator of the beatmap
     pub creator: String,
 
+    /// The name of the difficulty, i.e. the `Version` field of the `[Metadata]` section.
+    pub difficulty_name: String,
+
+    /// The original media the song was produced for.
+    pub source: String,
+
+    /// Space-separated search terms.
+    pub tags: String,
+
     /// The beatmap ID of the map
     pub beatmap_id: u32,
+
+    /// The ID of the beatmapset containing the map
+    pub beatmap_set_id: u32,
+
+    /// The MD5 hash of the original `.osu` file content.
+    ///
+    /// `None` if the map was not parsed from a file or was derived from another
+    /// map whose content it does not match, e.g. through [`reverse`](Beatmap::reverse).
+    pub checksum: Option<[u8; 16]>,
+
+    /// The filename of the background image as specified in the `[Events]` section.
+    pub background_file: Option<String>,
+
+    /// Whether the `[Events]` section contains storyboard sprites or animations.
+    ///
+    /// Storyboards that are only contained in a separate `.osb` file are not detected.
+    pub has_storyboard: bool,
+}
+
+/// The amount of each [`HitObjectKind`] in a [`Beatmap`].
+///
+/// osu!mania hold notes are counted separately from sliders.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectCounts {
+    /// The amount of circles.
+    pub circles: u32,
+    /// The amount of sliders.
+    pub sliders: u32,
+    /// The amount of spinners.
+    pub spinners: u32,
+    /// The amount of osu!mania hold notes.
+    pub holds: u32,
+}
+
+impl ObjectCounts {
+    /// The total amount of hit objects.
+    #[inline]
+    pub fn total(&self) -> u32 {
+        self.circles + self.sliders + self.spinners + self.holds
+    }
 }
 
 impl Beatmap {
@@ -79,6 +164,24 @@ impl Beatmap {
         BeatmapAttributesBuilder::new(self)
     }
 
+    /// The difficulty settings adjusted by the given mods and clock rate,
+    /// including the approach rate in milliseconds and the hit windows.
+    ///
+    /// If no clock rate is given, it will be determined by the mods.
+    /// Shorthand for [`attributes`](Self::attributes) followed by
+    /// [`BeatmapAttributesBuilder::build`].
+    #[inline]
+    pub fn effective_attributes(&self, mods: u32, clock_rate: Option<f64>) -> BeatmapAttributes {
+        let mut builder = self.attributes();
+        builder.mods(mods);
+
+        if let Some(clock_rate) = clock_rate {
+            builder.clock_rate(clock_rate);
+        }
+
+        builder.build()
+    }
+
     /// The beats per minute of the map.
     #[inline]
     pub fn bpm(&self) -> f64 {
@@ -88,6 +191,62 @@ impl Beatmap {
         }
     }
 
+    /// The MD5 hash of the original `.osu` file content as lowercase hex string,
+    /// i.e. the same format as the osu!api's `file_md5`.
+    ///
+    /// The hash is computed on the raw bytes while parsing so modifying
+    /// the map afterwards does not affect it.
+    /// Returns `None` if the map was not parsed from a file.
+    #[inline]
+    pub fn md5(&self) -> Option<String> {
+        self.checksum.as_ref().map(to_hex)
+    }
+
+    /// The filename of the background image, if any.
+    #[inline]
+    pub fn background_filename(&self) -> Option<&str> {
+        self.background_file.as_deref()
+    }
+
+    /// The amount of circles, sliders, spinners, and hold notes of the map.
+    ///
+    /// The stored counts include hold notes in [`n_sliders`](Beatmap::n_sliders)
+    /// so they're attributed to hold notes for osu!mania maps.
+    #[inline]
+    pub fn object_counts(&self) -> ObjectCounts {
+        let (sliders, holds) = if self.mode == GameMode::Mania {
+            (0, self.n_sliders)
+        } else {
+            (self.n_sliders, 0)
+        };
+
+        ObjectCounts {
+            circles: self.n_circles,
+            sliders,
+            spinners: self.n_spinners,
+            holds,
+        }
+    }
+
+    /// Count the hit objects of each kind by iterating over them.
+    ///
+    /// Contrary to [`object_counts`](Beatmap::object_counts), this does not rely on the stored
+    /// counts so it stays accurate if the hit objects have been modified.
+    pub fn count_objects_by_kind(&self) -> ObjectCounts {
+        let mut counts = ObjectCounts::default();
+
+        for h in self.hit_objects.iter() {
+            match h.kind {
+                HitObjectKind::Circle => counts.circles += 1,
+                HitObjectKind::Slider { .. } => counts.sliders += 1,
+                HitObjectKind::Spinner { .. } => counts.spinners += 1,
+                HitObjectKind::Hold { .. } => counts.holds += 1,
+            }
+        }
+
+        counts
+    }
+
     /// Sum up the duration of all breaks (in milliseconds).
     #[inline]
     pub fn total_break_time(&self) -> f64 {
@@ -130,13 +289,374 @@ impl Beatmap {
             .map(|i| self.effect_points[i])
     }
 
+    /// Return both the active uninherited [`TimingPoint`] and inherited [`DifficultyPoint`]
+    /// for the given timestamp, i.e. the beat length and the slider velocity multiplier.
+    ///
+    /// If `time` is before the first difficulty point, the default difficulty point is used.
+    #[inline]
+    pub fn control_points_at(&self, time: f64) -> (TimingPoint, DifficultyPoint) {
+        let timing_point = self.timing_point_at(time);
+        let difficulty_point = self.difficulty_point_at(time).unwrap_or_default();
+
+        (timing_point, difficulty_point)
+    }
+
+    /// Return the hit objects whose start time is within `[start, end)`.
+    ///
+    /// Relies on the hit objects being sorted by their start time.
+    #[inline]
+    pub fn hit_objects_in_range(&self, start: f64, end: f64) -> &[HitObject] {
+        self.hit_objects
+            .get(self.hit_object_range(start, end))
+            .unwrap_or_default()
+    }
+
+    /// The amount of hit objects that start before `time` milliseconds passed while
+    /// playing with the given clock rate, e.g. 1.5 for DT.
+    ///
+    /// Suitable to translate the time of a fail into the amount of passed objects
+    /// for e.g. [`OsuStars::passed_objects`](crate::OsuStars::passed_objects).
+    /// If the time is already in terms of the map's timestamps like in replays, use a clock rate of 1.0.
+    #[inline]
+    pub fn objects_before(&self, time: f64, clock_rate: f64) -> usize {
+        let time = time * clock_rate;
+
+        self.hit_objects.partition_point(|h| h.start_time < time)
+    }
+
+    fn hit_object_range(&self, start: f64, end: f64) -> Range<usize> {
+        let from = self.hit_objects.partition_point(|h| h.start_time < start);
+        let to = self.hit_objects.partition_point(|h| h.start_time < end);
+
+        from..to.max(from)
+    }
+
+    /// Indices of the hit objects that are within both the time range and the object range.
+    pub(crate) fn trimmed_range(
+        &self,
+        start_time: Option<f64>,
+        end_time: Option<f64>,
+        object_range: Option<Range<usize>>,
+    ) -> Range<usize> {
+        let range = self.hit_object_range(
+            start_time.unwrap_or(f64::NEG_INFINITY),
+            end_time.unwrap_or(f64::INFINITY),
+        );
+
+        match object_range {
+            Some(object_range) => {
+                let from = range.start.max(object_range.start).min(range.end);
+                let to = range.end.min(object_range.end);
+
+                from..to.max(from)
+            }
+            None => range,
+        }
+    }
+
+    /// Only keep the hit objects whose start time is within `[start_time, end_time)`
+    /// and whose index is within `object_range`.
+    ///
+    /// Borrows the map if no bound is specified.
+    pub(crate) fn trim(
+        &self,
+        start_time: Option<f64>,
+        end_time: Option<f64>,
+        object_range: Option<Range<usize>>,
+    ) -> Cow<'_, Self> {
+        if start_time.is_none() && end_time.is_none() && object_range.is_none() {
+            return Cow::Borrowed(self);
+        }
+
+        let range = self.trimmed_range(start_time, end_time, object_range);
+
+        let mut map = self.clone_without_hit_objects(true);
+
+        if let Some(sounds) = self.sounds.get(range.clone()) {
+            map.sounds.extend_from_slice(sounds);
+        }
+
+        for h in &self.hit_objects[range] {
+            match h.kind {
+                HitObjectKind::Circle => map.n_circles += 1,
+                HitObjectKind::Slider { .. } | HitObjectKind::Hold { .. } => map.n_sliders += 1,
+                HitObjectKind::Spinner { .. } => map.n_spinners += 1,
+            }
+
+            map.hit_objects.push(h.clone());
+        }
+
+        Cow::Owned(map)
+    }
+
+    /// The maximum amount of repetitions for [`repeat`](Beatmap::repeat).
+    pub const MAX_REPEATS: usize = 1000;
+
+    /// Concatenate the map's hit objects `n` times.
+    ///
+    /// Each repetition starts one beat after the previous one ended, including the
+    /// duration of sliders, and keeps the control points it had originally. Breaks are
+    /// repeated too but none are added between the repetitions. Borrows the map if `n`
+    /// is at most 1 and `n` is capped at [`MAX_REPEATS`](Beatmap::MAX_REPEATS).
+    ///
+    /// This is what the `repeat` option of the difficulty calculators,
+    /// e.g. [`OsuStars::repeat`](crate::OsuStars::repeat), uses internally.
+    pub fn repeat(&self, n: usize) -> Cow<'_, Self> {
+        let n = n.min(Self::MAX_REPEATS);
+
+        let first = match self.hit_objects.first() {
+            Some(h) if n > 1 => h.start_time,
+            _ => return Cow::Borrowed(self),
+        };
+
+        let last = self.last_end_time(first);
+        let period = last - first + self.timing_point_at(last).beat_len;
+
+        // `n` is capped so it fits into a u32
+        let factor = n as u32;
+
+        let mut map = self.clone_without_hit_objects(true);
+        map.n_circles = self.n_circles.saturating_mul(factor);
+        map.n_sliders = self.n_sliders.saturating_mul(factor);
+        map.n_spinners = self.n_spinners.saturating_mul(factor);
+        map.hit_objects.reserve(self.hit_objects.len() * (n - 1));
+        map.sounds.reserve(self.sounds.len() * (n - 1));
+
+        for i in 0..n {
+            let offset = i as f64 * period;
+
+            map.hit_objects.extend(self.hit_objects.iter().map(|h| {
+                let mut h = h.clone();
+                h.start_time += offset;
+
+                if let HitObjectKind::Spinner { end_time } | HitObjectKind::Hold { end_time } =
+                    &mut h.kind
+                {
+                    *end_time += offset;
+                }
+
+                h
+            }));
+
+            map.sounds.extend_from_slice(&self.sounds);
+        }
+
+        // Each repetition starts with the points that were active at the first object
+        // and points after the last object would otherwise leak into the next repetition
+        macro_rules! repeat_control_points {
+            ( $( $field:ident ),* ) => {
+                $(
+                    map.$field = SortedVec::default();
+
+                    let active = self.$field.iter().rposition(|point| point.time <= first);
+                    let start = active.unwrap_or(0);
+                    let end = self.$field.iter().position(|point| point.time > last);
+                    let points = &self.$field[start..end.unwrap_or(self.$field.len())];
+
+                    for i in 0..n {
+                        let offset = i as f64 * period;
+
+                        for (j, point) in points.iter().enumerate() {
+                            let mut point = *point;
+
+                            if i > 0 && j == 0 && active.is_some() {
+                                point.time = first;
+                            }
+
+                            point.time += offset;
+                            map.$field.push(point);
+                        }
+                    }
+                )*
+            };
+        }
+
+        repeat_control_points!(timing_points, difficulty_points, effect_points);
+
+        map.breaks = (0..n)
+            .flat_map(|i| {
+                let offset = i as f64 * period;
+
+                self.breaks.iter().map(move |b| Break {
+                    start_time: b.start_time + offset,
+                    end_time: b.end_time + offset,
+                })
+            })
+            .collect();
+
+        Cow::Owned(map)
+    }
+
+    /// The latest end time of all hit objects, including the duration of sliders,
+    /// or `first` if all objects end before it.
+    fn last_end_time(&self, first: f64) -> f64 {
+        let mut bufs = CurveBuffers::default();
+
+        self.hit_objects
+            .iter()
+            .map(|h| self.object_end_time(h, &mut bufs))
+            .fold(first, f64::max)
+    }
+
+    /// The end time of the hit object which, unlike [`HitObject::end_time`],
+    /// includes the duration of sliders.
+    pub(crate) fn object_end_time(&self, h: &HitObject, bufs: &mut CurveBuffers) -> f64 {
+        match &h.kind {
+            HitObjectKind::Slider {
+                pixel_len,
+                repeats,
+                control_points,
+                ..
+            } => {
+                let timing_point = self.timing_point_at(h.start_time);
+                let difficulty_point = self.difficulty_point_at(h.start_time).unwrap_or_default();
+
+                let scoring_dist =
+                    BASE_SCORING_DISTANCE * self.slider_mult * difficulty_point.slider_vel;
+
+                let vel = scoring_dist / timing_point.beat_len;
+                let span_count = (*repeats + 1) as f64;
+                let curve = Curve::new(control_points, *pixel_len, bufs);
+
+                h.start_time + span_count * curve.dist() / vel
+            }
+            HitObjectKind::Circle | HitObjectKind::Spinner { .. } | HitObjectKind::Hold { .. } => {
+                h.end_time()
+            }
+        }
+    }
+
+    /// Create a copy of the map that plays back in reverse.
+    ///
+    /// All timestamps are mirrored so that the last object ends where the first object
+    /// started and vice versa. Timing, difficulty, and effect points as well as breaks
+    /// are mirrored too so that each object keeps the control points it had before.
+    /// Slider paths keep their direction and sliders start where they used to end,
+    /// including their duration. Sliders that span a change of the timing or difficulty
+    /// point start in the section where they used to end so their duration may differ.
+    ///
+    /// Mostly useful to test which parts of the difficulty calculation are direction-sensitive.
+    /// The reversed map has no [`checksum`](Beatmap::checksum) so it won't share cache
+    /// entries with the original map.
+    pub fn reverse(&self) -> Self {
+        let first = self.hit_objects.first().map_or(0.0, |h| h.start_time);
+        let last = self.last_end_time(first);
+        let mut bufs = CurveBuffers::default();
+
+        let mirror = |time: f64| first + last - time;
+
+        let mut map = self.clone_without_hit_objects(true);
+        map.n_circles = self.n_circles;
+        map.n_sliders = self.n_sliders;
+        map.n_spinners = self.n_spinners;
+
+        // The content differs from the original file
+        map.checksum = None;
+
+        let mut objects: Vec<_> = self
+            .hit_objects
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, h)| {
+                let mut kind = h.kind.clone();
+
+                match &mut kind {
+                    HitObjectKind::Spinner { end_time } | HitObjectKind::Hold { end_time } => {
+                        *end_time = mirror(h.start_time)
+                    }
+                    HitObjectKind::Circle | HitObjectKind::Slider { .. } => {}
+                }
+
+                let h = HitObject {
+                    pos: h.pos,
+                    start_time: mirror(self.object_end_time(h, &mut bufs)),
+                    kind,
+                    new_combo: h.new_combo,
+                };
+
+                (h, self.sounds.get(i).copied())
+            })
+            .collect();
+
+        // Objects with a duration may overlap so their mirrored
+        // start times are not necessarily in order anymore
+        objects.sort_by(|(a, _), (b, _)| {
+            a.start_time
+                .partial_cmp(&b.start_time)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        for (h, sound) in objects {
+            map.hit_objects.push(h);
+            map.sounds.extend(sound);
+        }
+
+        // Each point becomes active where its section used to end
+        macro_rules! reverse_control_points {
+            ( $( $field:ident ),* ) => {
+                $(
+                    map.$field = SortedVec::default();
+
+                    for (i, point) in self.$field.iter().enumerate() {
+                        let end = match self.$field.get(i + 1) {
+                            Some(next) => next.time,
+                            None => point.time.max(last),
+                        };
+
+                        let mut point = *point;
+                        point.time = mirror(end);
+                        map.$field.push(point);
+                    }
+                )*
+            };
+        }
+
+        reverse_control_points!(timing_points, difficulty_points, effect_points);
+
+        // Objects before the first difficulty point use the default one
+        // which now has to become active where the first point used to start
+        if let Some(point) = self.difficulty_points.first().filter(|p| p.time > first) {
+            map.difficulty_points.push(DifficultyPoint {
+                time: mirror(point.time),
+                ..DifficultyPoint::default()
+            });
+        }
+
+        map.breaks = self
+            .breaks
+            .iter()
+            .rev()
+            .map(|b| Break {
+                start_time: mirror(b.end_time),
+                end_time: mirror(b.start_time),
+            })
+            .collect();
+
+        map
+    }
+
+    /// Force the map to be interpreted as the given mode, e.g. if its `Mode` field is wrong.
+    ///
+    /// Contrary to [`convert_mode`](Beatmap::convert_mode), the hit objects stay
+    /// untouched so the map will be treated as if it was created for that mode.
+    /// Hence, an osu!standard map forced to osu!taiko is not considered a convert.
+    #[inline]
+    pub fn set_mode(&mut self, mode: GameMode) -> &mut Self {
+        self.mode = mode;
+
+        self
+    }
+
     /// Convert a [`Beatmap`] of some mode into a different mode.
     ///
+    /// To only change the mode without converting hit objects, use [`set_mode`](Beatmap::set_mode).
+    ///
     /// # Note
     /// - Since hitsounds are irrelevant for difficulty and performance calculations
-    /// in osu!mania, the resulting map of a conversion to mania will not contain hitsounds.
+    ///   in osu!mania, the resulting map of a conversion to mania will not contain hitsounds.
     /// - To avoid having to clone the map for osu!catch conversions, the field `Beatmap::mode`
-    /// will not be adjusted in a osu!catch-converted map.
+    ///   will not be adjusted in a osu!catch-converted map.
     #[inline]
     pub fn convert_mode(&self, mode: GameMode) -> Cow<'_, Self> {
         if mode == self.mode {
@@ -146,17 +666,70 @@ impl Beatmap {
         match mode {
             GameMode::Osu | GameMode::Catch => Cow::Borrowed(self),
             GameMode::Taiko => Cow::Owned(self.convert_to_taiko()),
-            GameMode::Mania => Cow::Owned(self.convert_to_mania()),
+            GameMode::Mania => Cow::Owned(self.convert_to_mania(None)),
+        }
+    }
+
+    /// Convert the map into the given mode once so that it can be reused for multiple calculations.
+    ///
+    /// Contrary to [`convert_mode`](Beatmap::convert_mode), the `mode` of the resulting map
+    /// always matches the given mode which requires a clone for osu!catch converts.
+    /// Converted maps have [`is_convert`](Beatmap::is_convert) set so calculators
+    /// treat them as converts without converting them again.
+    ///
+    /// Since key mods are only considered when converting, osu!mania converts with
+    /// key mods should be calculated on the original map instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use akatsuki_pp::{Beatmap, GameMode, TaikoPP};
+    ///
+    /// # /*
+    /// let map: Beatmap = ...
+    /// # */
+    /// # let map = Beatmap::default();
+    /// let converted = map.converted(GameMode::Taiko);
+    ///
+    /// let nomod = TaikoPP::new(&converted).calculate();
+    /// let hdhr = TaikoPP::new(&converted).mods(8 + 16).calculate();
+    /// ```
+    pub fn converted(&self, mode: GameMode) -> Cow<'_, Self> {
+        match mode {
+            GameMode::Catch if self.mode == GameMode::Osu => {
+                let mut map = self.clone();
+                map.mode = GameMode::Catch;
+                map.is_convert = true;
+
+                Cow::Owned(map)
+            }
+            _ => self.convert_mode(mode),
+        }
+    }
+
+    /// Convert the map to osu!mania while considering key mods.
+    ///
+    /// Just like in osu!, key mods only force the amount of columns for converts,
+    /// osu!mania maps themselves are unaffected.
+    pub(crate) fn convert_to_mania_with_mods(&self, mods: u32) -> Cow<'_, Self> {
+        match mods.key_count() {
+            Some(key_count) if self.mode != GameMode::Mania => {
+                Cow::Owned(self.convert_to_mania(Some(key_count)))
+            }
+            _ => self.convert_mode(GameMode::Mania),
         }
     }
 
     fn clone_without_hit_objects(&self, with_sounds: bool) -> Self {
         Self {
             mode: self.mode,
+            is_convert: self.is_convert,
             version: self.version,
             n_circles: 0,
             n_sliders: 0,
             n_spinners: 0,
+            audio_lead_in: self.audio_lead_in,
+            preview_time: self.preview_time,
             ar: self.ar,
             od: self.od,
             cs: self.cs,
@@ -170,8 +743,19 @@ impl Beatmap {
             effect_points: self.effect_points.clone(),
             stack_leniency: self.stack_leniency,
             breaks: self.breaks.clone(),
+            title: self.title.clone(),
+            title_unicode: self.title_unicode.clone(),
+            artist: self.artist.clone(),
+            artist_unicode: self.artist_unicode.clone(),
             creator: self.creator.clone(),
+            difficulty_name: self.difficulty_name.clone(),
+            source: self.source.clone(),
+            tags: self.tags.clone(),
             beatmap_id: self.beatmap_id,
+            beatmap_set_id: self.beatmap_set_id,
+            checksum: self.checksum,
+            background_file: self.background_file.clone(),
+            has_storyboard: self.has_storyboard,
         }
     }
 }
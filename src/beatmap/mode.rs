@@ -1,3 +1,7 @@
+use std::str::FromStr;
+
+use crate::ParseError;
+
 /// The mode of a beatmap.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum GameMode {
@@ -17,3 +21,28 @@ impl Default for GameMode {
         Self::Osu
     }
 }
+
+impl FromStr for GameMode {
+    type Err = ParseError;
+
+    /// Parse a mode from its name, a common alias, or its number, ignoring case.
+    ///
+    /// - osu!standard: `"osu"`, `"std"`, `"standard"`, or `"0"`
+    /// - osu!taiko: `"taiko"` or `"1"`
+    /// - osu!catch: `"catch"`, `"ctb"`, `"fruits"`, or `"2"`
+    /// - osu!mania: `"mania"` or `"3"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let aliases: [(&[&str], Self); 4] = [
+            (&["osu", "std", "standard", "0"], Self::Osu),
+            (&["taiko", "1"], Self::Taiko),
+            (&["catch", "ctb", "fruits", "2"], Self::Catch),
+            (&["mania", "3"], Self::Mania),
+        ];
+
+        aliases
+            .iter()
+            .find(|(names, _)| names.iter().any(|name| name.eq_ignore_ascii_case(s)))
+            .map(|(_, mode)| *mode)
+            .ok_or(ParseError::InvalidMode)
+    }
+}
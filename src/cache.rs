@@ -0,0 +1,210 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::{AnyStars, Beatmap, DifficultyAttributes, GameMode, Mods};
+
+/// Bounded least-recently-used cache for [`DifficultyAttributes`].
+///
+/// Entries are keyed by the map's MD5 hash, its mode, whether it's a convert, the mods,
+/// and the clock rate so that repeated requests for the same map-mod combination
+/// don't have to be recalculated.
+/// Maps that were not parsed from a file have no MD5 hash and will never be cached.
+///
+/// The MD5 hash is computed while parsing and is not updated when the map is modified
+/// afterwards. The difficulty settings and the amount of hit objects are part of the key too
+/// so modifying them in place does not return stale attributes. Any other modification,
+/// e.g. moving hit objects, is not detected so the map's
+/// [`checksum`](Beatmap::checksum) should be set to `None` in that case.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{Beatmap, DifficultyCache};
+///
+/// # /*
+/// let map: Beatmap = ...
+/// # */
+/// # let map = Beatmap::default();
+///
+/// let mut cache = DifficultyCache::new(100);
+///
+/// let attrs = cache.calculate(&map, 8 + 64, None); // HDDT
+///
+/// println!("Stars: {}", attrs.stars());
+/// ```
+#[derive(Clone, Debug)]
+pub struct DifficultyCache {
+    capacity: usize,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Keys in order of their usage, oldest first.
+    /// A key whose tick doesn't match its entry's `last_used` is outdated.
+    recency: VecDeque<(CacheKey, u64)>,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+struct CacheKey {
+    checksum: [u8; 16],
+    mode: GameMode,
+    is_convert: bool,
+    mods: u32,
+    clock_rate: u64,
+    /// Bits of AR, OD, CS, and HP.
+    settings: [u32; 4],
+    slider_mult: u64,
+    tick_rate: u64,
+    n_objects: usize,
+}
+
+#[derive(Clone, Debug)]
+struct CacheEntry {
+    attributes: DifficultyAttributes,
+    last_used: u64,
+}
+
+impl DifficultyCache {
+    /// Create a new cache that holds at most `capacity` entries.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            hits: 0,
+            misses: 0,
+            entries: HashMap::with_capacity(capacity.min(1024)),
+            recency: VecDeque::with_capacity(capacity.min(1024)),
+        }
+    }
+
+    /// Return the cached attributes for the given map, mods, and clock rate,
+    /// or calculate and cache them if they're not available yet.
+    ///
+    /// If no clock rate is specified, it will be deduced from the mods.
+    pub fn calculate(
+        &mut self,
+        map: &Beatmap,
+        mods: u32,
+        clock_rate: Option<f64>,
+    ) -> DifficultyAttributes {
+        let clock_rate = clock_rate.unwrap_or_else(|| mods.clock_rate());
+
+        let calculate = || {
+            AnyStars::new(map)
+                .mods(mods)
+                .clock_rate(clock_rate)
+                .calculate()
+        };
+
+        let key = match map.checksum {
+            Some(checksum) if self.capacity > 0 => CacheKey {
+                checksum,
+                mode: map.mode,
+                is_convert: map.is_convert,
+                mods,
+                clock_rate: clock_rate.to_bits(),
+                settings: [
+                    map.ar.to_bits(),
+                    map.od.to_bits(),
+                    map.cs.to_bits(),
+                    map.hp.to_bits(),
+                ],
+                slider_mult: map.slider_mult.to_bits(),
+                tick_rate: map.tick_rate.to_bits(),
+                n_objects: map.hit_objects.len(),
+            },
+            _ => return calculate(),
+        };
+
+        self.tick += 1;
+
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.tick;
+            self.hits += 1;
+            let attributes = entry.attributes.clone();
+            self.touch(key);
+
+            return attributes;
+        }
+
+        self.misses += 1;
+        let attributes = calculate();
+
+        if self.entries.len() >= self.capacity {
+            self.evict();
+        }
+
+        let entry = CacheEntry {
+            attributes: attributes.clone(),
+            last_used: self.tick,
+        };
+
+        self.entries.insert(key, entry);
+        self.touch(key);
+
+        attributes
+    }
+
+    /// The maximum amount of entries.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The current amount of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache contains no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// How many requests were served from the cache.
+    #[inline]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// How many requests had to be calculated and were then cached.
+    #[inline]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Remove all entries.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    fn touch(&mut self, key: CacheKey) {
+        self.recency.push_back((key, self.tick));
+
+        // Drop outdated keys so that frequent hits don't grow the queue indefinitely
+        if self.recency.len() > 2 * self.capacity {
+            let entries = &self.entries;
+
+            self.recency.retain(
+                |(key, tick)| matches!(entries.get(key), Some(entry) if entry.last_used == *tick),
+            );
+        }
+    }
+
+    fn evict(&mut self) {
+        while let Some((key, tick)) = self.recency.pop_front() {
+            let is_current =
+                matches!(self.entries.get(&key), Some(entry) if entry.last_used == tick);
+
+            if is_current {
+                self.entries.remove(&key);
+
+                return;
+            }
+        }
+    }
+}
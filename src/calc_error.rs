@@ -0,0 +1,88 @@
+use std::{
+    any::Any,
+    error::Error as StdError,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{
+    parse::{HitObject, HitObjectKind},
+    Beatmap,
+};
+
+/// Anything that could go wrong during a difficulty or performance calculation,
+/// see e.g. [`AnyPP::try_calculate`](crate::AnyPP::try_calculate).
+#[derive(Debug)]
+pub enum CalcError {
+    /// A difficulty setting of the map, e.g. `"OD"`, is not a finite number.
+    InvalidDifficulty(&'static str),
+    /// The hit object at the index has a non-finite time or position,
+    /// or it is a slider without control points.
+    InvalidHitObject {
+        /// The index of the hit object within the map, even for converts.
+        idx: usize,
+    },
+    /// The calculation panicked for any other reason.
+    Panic(String),
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDifficulty(setting) => write!(f, "{} is not a finite number", setting),
+            Self::InvalidHitObject { idx } => write!(f, "hit object {} is invalid", idx),
+            Self::Panic(msg) => write!(f, "calculation panicked: {}", msg),
+        }
+    }
+}
+
+impl StdError for CalcError {}
+
+/// Check the map for the known causes of panics or endless loops.
+pub(crate) fn validate(map: &Beatmap) -> Result<(), CalcError> {
+    let settings = [
+        ("AR", map.ar as f64),
+        ("OD", map.od as f64),
+        ("CS", map.cs as f64),
+        ("HP", map.hp as f64),
+        ("slider multiplier", map.slider_mult),
+        ("tick rate", map.tick_rate),
+    ];
+
+    if let Some((setting, _)) = settings.iter().find(|(_, value)| !value.is_finite()) {
+        return Err(CalcError::InvalidDifficulty(setting));
+    }
+
+    match map.hit_objects.iter().position(|h| !is_valid(h)) {
+        Some(idx) => Err(CalcError::InvalidHitObject { idx }),
+        None => Ok(()),
+    }
+}
+
+fn is_valid(h: &HitObject) -> bool {
+    let kind_is_valid = match &h.kind {
+        HitObjectKind::Circle => true,
+        HitObjectKind::Slider { control_points, .. } => !control_points.is_empty(),
+        HitObjectKind::Spinner { end_time } | HitObjectKind::Hold { end_time } => {
+            end_time.is_finite()
+        }
+    };
+
+    kind_is_valid && h.start_time.is_finite() && h.pos.x.is_finite() && h.pos.y.is_finite()
+}
+
+/// Run the calculation, turning panics into [`CalcError::Panic`].
+pub(crate) fn catch_panic<T>(calculate: impl FnOnce() -> T) -> Result<T, CalcError> {
+    panic::catch_unwind(AssertUnwindSafe(calculate))
+        .map_err(|payload| CalcError::Panic(panic_msg(payload)))
+}
+
+fn panic_msg(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(msg) => (*msg).to_owned(),
+            Err(_) => "unknown cause".to_owned(),
+        },
+    }
+}
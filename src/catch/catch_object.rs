@@ -2,7 +2,7 @@ use crate::parse::Pos2;
 
 use super::fruit_or_juice::FruitParams;
 
-const PLAYFIELD_WIDTH: f32 = 512.0;
+pub(crate) const PLAYFIELD_WIDTH: f32 = 512.0;
 const BASE_SPEED: f64 = 1.0;
 
 #[derive(Clone, Debug)]
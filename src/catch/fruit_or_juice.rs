@@ -6,7 +6,10 @@ use crate::{
     Beatmap,
 };
 
-use super::{catch_object::CatchObject, CatchDifficultyAttributes};
+use super::{
+    catch_object::{CatchObject, PLAYFIELD_WIDTH},
+    CatchDifficultyAttributes,
+};
 
 const LEGACY_LAST_TICK_OFFSET: f64 = 36.0;
 const BASE_SCORING_DISTANCE: f64 = 100.0;
@@ -20,6 +23,7 @@ pub(crate) struct FruitParams<'a> {
     pub(crate) map: &'a Beatmap,
     pub(crate) ticks: Vec<(Pos2, f64)>,
     pub(crate) with_hr: bool,
+    pub(crate) with_mirror: bool,
 }
 
 type JuiceStream = Map<IntoIter<(Pos2, f64)>, fn((Pos2, f64)) -> CatchObject>;
@@ -36,6 +40,10 @@ impl FruitOrJuice {
             HitObjectKind::Circle => {
                 let mut h = CatchObject::new((h.pos, h.start_time));
 
+                if params.with_mirror {
+                    h.pos = PLAYFIELD_WIDTH - h.pos;
+                }
+
                 if params.with_hr {
                     h = h.with_hr(params);
                 }
@@ -51,7 +59,13 @@ impl FruitOrJuice {
                 ..
             } => {
                 // HR business
-                params.last_pos = Some(h.pos.x + control_points[control_points.len() - 1].pos.x);
+                let last_pos = h.pos.x + control_points[control_points.len() - 1].pos.x;
+
+                params.last_pos = if params.with_mirror {
+                    Some(PLAYFIELD_WIDTH - last_pos)
+                } else {
+                    Some(last_pos)
+                };
                 params.last_time = h.start_time;
 
                 let timing_point = params.map.timing_point_at(h.start_time);
@@ -167,6 +181,12 @@ impl FruitOrJuice {
                 params.attributes.n_fruits += new_fruits;
                 params.attributes.n_droplets += slider_objects.len() - new_fruits;
 
+                if params.with_mirror {
+                    for (pos, _) in slider_objects.iter_mut() {
+                        pos.x = PLAYFIELD_WIDTH - pos.x;
+                    }
+                }
+
                 let iter = slider_objects
                     .into_iter()
                     .map(CatchObject::new as fn(_) -> _);
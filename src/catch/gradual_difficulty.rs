@@ -1,18 +1,18 @@
-use std::{iter, slice::Iter};
+use std::slice::Iter;
 
 use crate::{
-    catch::{difficulty_object::DifficultyObject, SECTION_LENGTH, STAR_SCALING_FACTOR},
+    catch::{difficulty_object::DifficultyObject, SECTION_LENGTH},
     curve::CurveBuffers,
     parse::{HitObject, Pos2},
     Beatmap, Mods,
 };
 
 use super::{
-    calculate_catch_width,
+    calculate_catch_width, calculate_catcher_width, calculate_fruit_radius,
     catch_object::CatchObject,
     fruit_or_juice::{FruitOrJuice, FruitParams},
     movement::Movement,
-    CatchDifficultyAttributes, ALLOWED_CATCH_RANGE,
+    CatchDifficultyAttributes, CatchDifficultyParams, ALLOWED_CATCH_RANGE,
 };
 
 /// Gradually calculate the difficulty attributes of an osu!catch map.
@@ -29,7 +29,7 @@ use super::{
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{Beatmap, catch::CatchGradualDifficultyAttributes};
+/// use akatsuki_pp::{Beatmap, catch::CatchGradualDifficultyAttributes};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -58,16 +58,25 @@ pub struct CatchGradualDifficultyAttributes<'map> {
     last_direction: i8,
     last_excess: f64,
     curr_section_end: f64,
-    strain_peak_buf: Vec<f64>,
+    star_scaling_factor: f64,
 }
 
 impl<'map> CatchGradualDifficultyAttributes<'map> {
     /// Create a new difficulty attributes iterator for osu!catch maps.
+    #[inline]
     pub fn new(map: &'map Beatmap, mods: u32) -> Self {
+        Self::with_params(map, mods, CatchDifficultyParams::default())
+    }
+
+    /// Same as [`new`](CatchGradualDifficultyAttributes::new) but with
+    /// adjusted constants, see [`CatchStars::params`](crate::CatchStars::params).
+    pub fn with_params(map: &'map Beatmap, mods: u32, params: CatchDifficultyParams) -> Self {
         let map_attributes = map.attributes().mods(mods).build();
 
         let attributes = CatchDifficultyAttributes {
             ar: map_attributes.ar,
+            catcher_width: calculate_catcher_width(map_attributes.cs),
+            fruit_radius: calculate_fruit_radius(map_attributes.cs),
             ..Default::default()
         };
 
@@ -91,7 +100,7 @@ impl<'map> CatchGradualDifficultyAttributes<'map> {
             last_direction,
             last_excess,
             curr_section_end: 0.0,
-            strain_peak_buf: Vec::new(),
+            star_scaling_factor: params.star_scaling_factor,
         }
     }
 
@@ -143,19 +152,8 @@ impl Iterator for CatchGradualDifficultyAttributes<'_> {
         self.movement.process(&h);
         self.prev = curr;
 
-        let len = self.movement.strain_peaks.len();
-        let missing = len + 1 - self.strain_peak_buf.len();
-        self.strain_peak_buf.extend(iter::repeat(0.0).take(missing));
-
-        self.strain_peak_buf[..len].copy_from_slice(&self.movement.strain_peaks);
-
-        if let Some(last) = self.strain_peak_buf.last_mut() {
-            *last = self.movement.curr_section_peak;
-        }
-
         let mut attributes = self.hit_objects.attributes();
-        attributes.stars =
-            Movement::difficulty_value(&mut self.strain_peak_buf).sqrt() * STAR_SCALING_FACTOR;
+        attributes.stars = self.movement.difficulty_value().sqrt() * self.star_scaling_factor;
 
         Some(attributes)
     }
@@ -178,6 +176,7 @@ impl<'map> CatchObjectIter<'map> {
             map,
             ticks: Vec::new(),
             with_hr: mods.hr(),
+            with_mirror: mods.mr(),
         };
 
         Self {
@@ -1,6 +1,9 @@
-use crate::{Beatmap, CatchPP};
+use crate::{Accuracy, Beatmap, CatchPP};
 
-use super::{CatchGradualDifficultyAttributes, CatchPerformanceAttributes};
+use super::{
+    CatchDifficultyAttributes, CatchDifficultyParams, CatchGradualDifficultyAttributes,
+    CatchPerformanceAttributes,
+};
 
 /// Aggregation for a score's current state i.e. what was the
 /// maximum combo so far and what are the current hitresults.
@@ -30,6 +33,63 @@ impl CatchScoreState {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Generate the hitresults for the given accuracy on a map with the given attributes.
+    ///
+    /// Misses are attributed to droplets first. Fruits and droplets are
+    /// otherwise all caught so the accuracy is matched through tiny droplets.
+    /// The max combo is left at 0.
+    pub fn from_accuracy(
+        attributes: &CatchDifficultyAttributes,
+        acc: impl Into<Accuracy>,
+        n_misses: usize,
+    ) -> Self {
+        let max_combo = attributes.max_combo();
+        let n_misses = n_misses.min(max_combo);
+        let n_droplets = attributes.n_droplets.saturating_sub(n_misses);
+        let n_fruits = max_combo - n_misses - n_droplets;
+
+        let max_tiny_droplets = attributes.n_tiny_droplets;
+        let total = max_combo + max_tiny_droplets;
+        let target_caught = (acc.into().fraction() * total as f64).round() as usize;
+
+        let n_tiny_droplets = target_caught
+            .saturating_sub(n_fruits + n_droplets)
+            .min(max_tiny_droplets);
+
+        Self {
+            max_combo: 0,
+            n_fruits,
+            n_droplets,
+            n_tiny_droplets,
+            n_tiny_droplet_misses: max_tiny_droplets - n_tiny_droplets,
+            n_misses,
+        }
+    }
+
+    /// Return the total amount of hits by adding everything up.
+    #[inline]
+    pub fn total_hits(&self) -> usize {
+        self.n_fruits
+            + self.n_droplets
+            + self.n_tiny_droplets
+            + self.n_tiny_droplet_misses
+            + self.n_misses
+    }
+
+    /// Calculate the accuracy between `0.0` and `1.0` for this state.
+    #[inline]
+    pub fn accuracy(&self) -> f64 {
+        let total_hits = self.total_hits();
+
+        if total_hits == 0 {
+            return 0.0;
+        }
+
+        let numerator = self.n_fruits + self.n_droplets + self.n_tiny_droplets;
+
+        numerator as f64 / total_hits as f64
+    }
 }
 
 /// Gradually calculate the performance attributes of an osu!catch map.
@@ -52,7 +112,7 @@ impl CatchScoreState {
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{Beatmap, catch::{CatchGradualPerformanceAttributes, CatchScoreState}};
+/// use akatsuki_pp::{Beatmap, catch::{CatchGradualPerformanceAttributes, CatchScoreState}};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -132,8 +192,15 @@ pub struct CatchGradualPerformanceAttributes<'map> {
 
 impl<'map> CatchGradualPerformanceAttributes<'map> {
     /// Create a new gradual performance calculator for osu!standard maps.
+    #[inline]
     pub fn new(map: &'map Beatmap, mods: u32) -> Self {
-        let difficulty = CatchGradualDifficultyAttributes::new(map, mods);
+        Self::with_params(map, mods, CatchDifficultyParams::default())
+    }
+
+    /// Same as [`new`](CatchGradualPerformanceAttributes::new) but with
+    /// adjusted constants, see [`CatchStars::params`](crate::CatchStars::params).
+    pub fn with_params(map: &'map Beatmap, mods: u32, params: CatchDifficultyParams) -> Self {
+        let difficulty = CatchGradualDifficultyAttributes::with_params(map, mods, params);
         let performance = CatchPP::new(map).mods(mods).passed_objects(0);
 
         Self {
@@ -14,12 +14,16 @@ pub use gradual_performance::*;
 use movement::Movement;
 pub use pp::*;
 
+use std::ops::Range;
+
 use crate::{catch::fruit_or_juice::FruitParams, curve::CurveBuffers, Beatmap, Mods, OsuStars};
 
 const SECTION_LENGTH: f64 = 750.0;
 const STAR_SCALING_FACTOR: f64 = 0.153;
 
 const ALLOWED_CATCH_RANGE: f32 = 0.8;
+// Radius of a fruit for CS 5, i.e. a sixteenth of the playfield width
+const FRUIT_RADIUS: f32 = 32.0;
 const CATCHER_SIZE: f32 = 106.75;
 
 /// Difficulty calculator on osu!catch maps.
@@ -27,7 +31,7 @@ const CATCHER_SIZE: f32 = 106.75;
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{CatchStars, Beatmap};
+/// use akatsuki_pp::{CatchStars, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -42,10 +46,15 @@ const CATCHER_SIZE: f32 = 106.75;
 /// ```
 #[derive(Clone, Debug)]
 pub struct CatchStars<'map> {
-    map: &'map Beatmap,
+    pub(crate) map: &'map Beatmap,
     mods: u32,
     passed_objects: Option<usize>,
     clock_rate: Option<f64>,
+    params: CatchDifficultyParams,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    object_range: Option<Range<usize>>,
+    repeats: usize,
 }
 
 impl<'map> CatchStars<'map> {
@@ -57,6 +66,11 @@ impl<'map> CatchStars<'map> {
             mods: 0,
             passed_objects: None,
             clock_rate: None,
+            params: CatchDifficultyParams::default(),
+            start_time: None,
+            end_time: None,
+            object_range: None,
+            repeats: 1,
         }
     }
 
@@ -92,12 +106,68 @@ impl<'map> CatchStars<'map> {
         self
     }
 
+    /// Override the constants used in the difficulty calculation.
+    ///
+    /// Only useful for experimenting with difficulty adjustments, the defaults match osu!.
+    /// For gradual calculations, use
+    /// [`CatchGradualDifficultyAttributes::with_params`](crate::catch::CatchGradualDifficultyAttributes::with_params).
+    #[inline]
+    pub fn params(mut self, params: CatchDifficultyParams) -> Self {
+        self.params = params;
+
+        self
+    }
+
+    /// Ignore all hit objects that start before the given time in milliseconds.
+    ///
+    /// Unlike [`passed_objects`](Self::passed_objects), the remaining objects are
+    /// treated as if they were the whole map.
+    #[inline]
+    pub fn start_time(mut self, start_time: f64) -> Self {
+        self.start_time = Some(start_time);
+
+        self
+    }
+
+    /// Ignore all hit objects that start at or after the given time in milliseconds.
+    #[inline]
+    pub fn end_time(mut self, end_time: f64) -> Self {
+        self.end_time = Some(end_time);
+
+        self
+    }
+
+    /// Only consider the hit objects whose index is within `[start, end)`.
+    ///
+    /// Just like for [`start_time`](Self::start_time), the remaining objects are
+    /// treated as if they were the whole map. If a time range is specified too,
+    /// only objects within both ranges are considered.
+    #[inline]
+    pub fn object_range(mut self, start: usize, end: usize) -> Self {
+        self.object_range = Some(start..end);
+
+        self
+    }
+
+    /// Play the map `repeats` times in a row, e.g. for endurance challenges.
+    ///
+    /// The hit objects are virtually repeated, each repetition starting one beat after
+    /// the previous one ended, so length-dependent values like the max combo and
+    /// the strain accumulation reflect the longer map. Values below 2 leave the map as is
+    /// and values above [`Beatmap::MAX_REPEATS`] are capped.
+    #[inline]
+    pub fn repeat(mut self, repeats: usize) -> Self {
+        self.repeats = repeats;
+
+        self
+    }
+
     /// Calculate all difficulty related values, including stars.
     #[inline]
     pub fn calculate(self) -> CatchDifficultyAttributes {
-        let (mut movement, mut attributes) = calculate_movement(self);
-        attributes.stars =
-            Movement::difficulty_value(&mut movement.strain_peaks).sqrt() * STAR_SCALING_FACTOR;
+        let star_scaling_factor = self.params.star_scaling_factor;
+        let (movement, mut attributes, _) = calculate_movement(self);
+        attributes.stars = movement.difficulty_value().sqrt() * star_scaling_factor;
 
         attributes
     }
@@ -107,10 +177,25 @@ impl<'map> CatchStars<'map> {
     /// Suitable to plot the difficulty of a map over time.
     #[inline]
     pub fn strains(self) -> CatchStrains {
-        let (movement, _) = calculate_movement(self);
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let (mut movement, _, curr_section_end) = calculate_movement(self);
+
+        let start_time = match curr_section_end {
+            Some(curr_section_end) => crate::util::first_section_start(
+                curr_section_end,
+                movement.strain_peaks.len(),
+                SECTION_LENGTH,
+                clock_rate,
+            ),
+            None => 0.0,
+        };
+
+        movement.save_final_peak();
 
         CatchStrains {
             section_len: SECTION_LENGTH,
+            start_time,
+            clock_rate,
             movement: movement.strain_peaks,
         }
     }
@@ -122,33 +207,88 @@ impl<'map> CatchStars<'map> {
 pub struct CatchStrains {
     /// Time in ms inbetween two strains.
     pub section_len: f64,
+    /// Start of the first strain section in milliseconds in terms of the map's timestamps.
+    ///
+    /// The section of the `n`-th strain peak starts at
+    /// `start_time + n * section_len_with_clock_rate(clock_rate)`, which is also the time
+    /// that [`peak`](Self::peak) returns alongside the peak.
+    pub start_time: f64,
+    /// The clock rate that was used to calculate the strains.
+    pub clock_rate: f64,
     /// Strain peaks of the movement skill.
     pub movement: Vec<f64>,
 }
 
 impl CatchStrains {
+    /// Time in ms inbetween two strains in terms of the map's timestamps,
+    /// e.g. 1125ms instead of 750ms for DT.
+    #[inline]
+    pub fn section_len_with_clock_rate(&self, clock_rate: f64) -> f64 {
+        crate::util::section_len_with_clock_rate(self.section_len, clock_rate)
+    }
+
     /// Returns the number of strain peaks per skill.
     #[inline]
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.movement.len()
     }
+
+    /// The highest movement strain peak as `(time, value)`, or `None` if there are no peaks.
+    #[inline]
+    pub fn peak(&self) -> Option<(f64, f64)> {
+        crate::util::strain_peak(
+            &self.movement,
+            self.start_time,
+            self.section_len_with_clock_rate(self.clock_rate),
+        )
+    }
+}
+
+/// Constants of the osu!catch difficulty calculation that can be adjusted through
+/// [`CatchStars::params`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CatchDifficultyParams {
+    /// Multiplier from the movement skill's difficulty value to the star rating.
+    pub star_scaling_factor: f64,
+}
+
+impl Default for CatchDifficultyParams {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            star_scaling_factor: STAR_SCALING_FACTOR,
+        }
+    }
 }
 
-fn calculate_movement(params: CatchStars<'_>) -> (Movement, CatchDifficultyAttributes) {
+/// Also returns the end of the current strain section, if any section was started.
+fn calculate_movement(
+    params: CatchStars<'_>,
+) -> (Movement, CatchDifficultyAttributes, Option<f64>) {
     let CatchStars {
         map,
         mods,
         passed_objects,
         clock_rate,
+        params: _,
+        start_time,
+        end_time,
+        object_range,
+        repeats,
     } = params;
 
+    let map = map.trim(start_time, end_time, object_range);
+    let map = map.repeat(repeats);
+    let map = map.as_ref();
     let take = passed_objects.unwrap_or(usize::MAX);
     let clock_rate = clock_rate.unwrap_or_else(|| mods.clock_rate());
     let map_attributes = map.attributes().mods(mods).clock_rate(clock_rate).build();
 
     let attributes = CatchDifficultyAttributes {
         ar: map_attributes.ar,
+        catcher_width: calculate_catcher_width(map_attributes.cs),
+        fruit_radius: calculate_fruit_radius(map_attributes.cs),
         ..Default::default()
     };
 
@@ -160,6 +300,7 @@ fn calculate_movement(params: CatchStars<'_>) -> (Movement, CatchDifficultyAttri
         map,
         ticks: Vec::new(), // using the same buffer for all sliders
         with_hr: mods.hr(),
+        with_mirror: mods.mr(),
     };
 
     // BUG: Incorrect object order on 2B maps that have fruits within sliders
@@ -181,7 +322,7 @@ fn calculate_movement(params: CatchStars<'_>) -> (Movement, CatchDifficultyAttri
 
     let (mut prev, curr) = match (hit_objects.next(), hit_objects.next()) {
         (Some(prev), Some(curr)) => (prev, curr),
-        (Some(_), None) | (None, None) => return (movement, params.attributes),
+        (Some(_), None) | (None, None) => return (movement, params.attributes, None),
         (None, Some(_)) => unreachable!(),
     };
 
@@ -223,20 +364,38 @@ fn calculate_movement(params: CatchStars<'_>) -> (Movement, CatchDifficultyAttri
         prev = curr;
     }
 
-    movement.save_current_peak();
-
-    (movement, params.attributes)
+    (movement, params.attributes, Some(curr_section_end))
 }
 
 #[inline]
 pub(crate) fn calculate_catch_width(cs: f32) -> f32 {
-    let scale = 1.0 - 0.7 * (cs - 5.0) / 5.0;
+    CATCHER_SIZE * cs_scale(cs).abs() * ALLOWED_CATCH_RANGE
+}
+
+#[inline]
+pub(crate) fn calculate_catcher_width(cs: f64) -> f64 {
+    (calculate_catch_width(cs as f32) / ALLOWED_CATCH_RANGE) as f64
+}
 
-    CATCHER_SIZE * scale.abs() * ALLOWED_CATCH_RANGE
+#[inline]
+pub(crate) fn calculate_fruit_radius(cs: f64) -> f64 {
+    (FRUIT_RADIUS * cs_scale(cs as f32).abs()) as f64
+}
+
+fn cs_scale(cs: f32) -> f32 {
+    1.0 - 0.7 * (cs - 5.0) / 5.0
 }
 
 /// The result of a difficulty calculation on an osu!catch map.
+///
+/// The struct is `#[non_exhaustive]` so it can't be created through a struct
+/// literal outside of this crate. Use [`Default`] instead and set the fields manually.
+///
+/// Note that the [`Default`] attributes are all zero, just like the attributes
+/// of a map without hit objects. Use [`is_empty`](Self::is_empty) to tell them
+/// apart from the attributes of an actual map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
 pub struct CatchDifficultyAttributes {
     /// The final star rating
     pub stars: f64,
@@ -248,6 +407,10 @@ pub struct CatchDifficultyAttributes {
     pub n_droplets: usize,
     /// The amount of tiny droplets.
     pub n_tiny_droplets: usize,
+    /// The width of the catcher in osu!pixels, adjusted by the circle size and mods.
+    pub catcher_width: f64,
+    /// The radius of fruits in osu!pixels, adjusted by the circle size and mods.
+    pub fruit_radius: f64,
 }
 
 impl CatchDifficultyAttributes {
@@ -256,10 +419,21 @@ impl CatchDifficultyAttributes {
     pub fn max_combo(&self) -> usize {
         self.n_fruits + self.n_droplets
     }
+
+    /// Whether no hit object was considered, e.g. because the attributes
+    /// are [`Default`] or the map has no (passed) objects.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.max_combo() + self.n_tiny_droplets == 0
+    }
 }
 
 /// The result of a performance calculation on an osu!catch map.
+///
+/// The struct is `#[non_exhaustive]` so it can't be created through a struct
+/// literal outside of this crate. Use [`new`](Self::new) or [`Default`] instead.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
 pub struct CatchPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: CatchDifficultyAttributes,
@@ -268,6 +442,12 @@ pub struct CatchPerformanceAttributes {
 }
 
 impl CatchPerformanceAttributes {
+    /// Create new performance attributes with the given pp value.
+    #[inline]
+    pub fn new(difficulty: CatchDifficultyAttributes, pp: f64) -> Self {
+        Self { difficulty, pp }
+    }
+
     /// Return the star value.
     #[inline]
     pub fn stars(&self) -> f64 {
@@ -302,6 +482,14 @@ impl<'map> From<OsuStars<'map>> for CatchStars<'map> {
             mods,
             passed_objects,
             clock_rate,
+            start_time,
+            end_time,
+            object_range,
+            repeats,
+            ignore_spinners: _,
+            precision: _,
+            params: _,
+            rate_adjusted_hit_window: _,
         } = osu;
 
         Self {
@@ -309,6 +497,11 @@ impl<'map> From<OsuStars<'map>> for CatchStars<'map> {
             mods,
             passed_objects,
             clock_rate,
+            params: CatchDifficultyParams::default(),
+            start_time,
+            end_time,
+            object_range,
+            repeats,
         }
     }
 }
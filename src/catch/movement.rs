@@ -1,6 +1,6 @@
-use super::DifficultyObject;
+use crate::util::SortedPeaks;
 
-use std::cmp::Ordering;
+use super::DifficultyObject;
 
 const ABSOLUTE_PLAYER_POSITIONING_ERROR: f32 = 16.0;
 const NORMALIZED_HITOBJECT_RADIUS: f32 = 41.0;
@@ -22,6 +22,7 @@ pub(crate) struct Movement {
     pub(crate) curr_section_peak: f64,
 
     pub(crate) strain_peaks: Vec<f64>,
+    sorted_peaks: SortedPeaks,
     prev_time: Option<f64>,
 }
 
@@ -42,6 +43,7 @@ impl Movement {
             curr_section_peak: 1.0,
 
             strain_peaks: Vec::with_capacity(128),
+            sorted_peaks: SortedPeaks::new(DECAY_WEIGHT),
             prev_time: None,
         }
     }
@@ -49,6 +51,15 @@ impl Movement {
     #[inline]
     pub(crate) fn save_current_peak(&mut self) {
         self.strain_peaks.push(self.curr_section_peak);
+        self.sorted_peaks.push(self.curr_section_peak);
+    }
+
+    /// Save the peak of the current section if any object was processed.
+    #[inline]
+    pub(crate) fn save_final_peak(&mut self) {
+        if self.prev_time.is_some() {
+            self.save_current_peak();
+        }
     }
 
     #[inline]
@@ -63,18 +74,12 @@ impl Movement {
         self.prev_time.replace(current.start_time);
     }
 
-    pub(crate) fn difficulty_value(strain_peaks: &mut [f64]) -> f64 {
-        let mut difficulty = 0.0;
-        let mut weight = 1.0;
-
-        strain_peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
-
-        for &strain in strain_peaks.iter() {
-            difficulty += strain * weight;
-            weight *= DECAY_WEIGHT;
+    pub(crate) fn difficulty_value(&self) -> f64 {
+        // The current section has no peak if no object was processed
+        match self.prev_time {
+            Some(_) => self.sorted_peaks.weighted_sum(&[self.curr_section_peak], 0),
+            None => 0.0,
         }
-
-        difficulty
     }
 
     fn strain_value_of(&mut self, current: &DifficultyObject<'_>) -> f64 {
@@ -1,12 +1,12 @@
 use super::{CatchDifficultyAttributes, CatchPerformanceAttributes, CatchScoreState, CatchStars};
-use crate::{Beatmap, DifficultyAttributes, Mods, OsuPP, PerformanceAttributes};
+use crate::{Accuracy, Beatmap, DifficultyAttributes, Mods, OsuPP, PerformanceAttributes};
 
 /// Performance calculator on osu!catch maps.
 ///
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{CatchPP, Beatmap};
+/// use akatsuki_pp::{Accuracy, CatchPP, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -16,7 +16,7 @@ use crate::{Beatmap, DifficultyAttributes, Mods, OsuPP, PerformanceAttributes};
 /// let pp_result = CatchPP::new(&map)
 ///     .mods(8 + 64) // HDDT
 ///     .combo(1234)
-///     .accuracy(98.5)
+///     .accuracy(Accuracy::from_percent(98.5).unwrap())
 ///     .misses(1)
 ///     .calculate();
 ///
@@ -25,7 +25,7 @@ use crate::{Beatmap, DifficultyAttributes, Mods, OsuPP, PerformanceAttributes};
 /// let next_result = CatchPP::new(&map)
 ///     .attributes(pp_result) // reusing previous results for performance
 ///     .mods(8 + 64) // has to be the same to reuse attributes
-///     .accuracy(99.5)
+///     .accuracy(Accuracy::from_percent(99.5).unwrap())
 ///     .calculate();
 ///
 /// println!("PP: {} | Stars: {}", next_result.pp(), next_result.stars());
@@ -33,9 +33,9 @@ use crate::{Beatmap, DifficultyAttributes, Mods, OsuPP, PerformanceAttributes};
 #[derive(Clone, Debug)]
 #[allow(clippy::upper_case_acronyms)]
 pub struct CatchPP<'map> {
-    map: &'map Beatmap,
-    attributes: Option<CatchDifficultyAttributes>,
-    mods: u32,
+    pub(crate) map: &'map Beatmap,
+    pub(crate) attributes: Option<CatchDifficultyAttributes>,
+    pub(crate) mods: u32,
     combo: Option<usize>,
 
     pub(crate) n_fruits: Option<usize>,
@@ -89,6 +89,21 @@ impl<'map> CatchPP<'map> {
         self
     }
 
+    /// Strip all rate-changing and difficulty-altering mods,
+    /// see [`Mods::without_difficulty_mods`].
+    ///
+    /// The score's accuracy, combo, and hit results stay the same so the
+    /// result can be compared to the actual play, e.g. "if played nomod".
+    /// Previously provided attributes and clock rate are discarded.
+    #[inline]
+    pub fn nomod(mut self) -> Self {
+        self.mods = self.mods.without_difficulty_mods();
+        self.attributes = None;
+        self.clock_rate = None;
+
+        self
+    }
+
     /// Specify the max combo of the play.
     #[inline]
     pub fn combo(mut self, combo: usize) -> Self {
@@ -182,10 +197,10 @@ impl<'map> CatchPP<'map> {
     }
 
     // TODO: adjust this on the next rework
-    /// Generate the hit results with respect to the given accuracy between `0.0` and `100.0`.
+    /// Generate the hit results with respect to the given accuracy, see [`Accuracy`].
     ///
     /// Be sure to set `misses` beforehand! Also, if available, set `attributes` beforehand.
-    pub fn accuracy(mut self, mut acc: f64) -> Self {
+    pub fn accuracy(mut self, acc: impl Into<Accuracy>) -> Self {
         if self.attributes.is_none() {
             let mut calculator = CatchStars::new(self.map).mods(self.mods);
 
@@ -217,7 +232,7 @@ impl<'map> CatchPP<'map> {
         });
 
         let max_tiny_droplets = attributes.n_tiny_droplets;
-        acc /= 100.0;
+        let acc = acc.into().fraction();
 
         let n_tiny_droplets = self.n_tiny_droplets.unwrap_or_else(|| {
             ((acc * (max_combo + max_tiny_droplets) as f64).round() as usize)
@@ -310,6 +325,22 @@ impl<'map> CatchPP<'map> {
         }
     }
 
+    /// Calculate the performance of a full combo with 100% accuracy, i.e. an SS,
+    /// for the same map, mods, clock rate, and amount of passed objects.
+    ///
+    /// Specified accuracy, combo, and hitresults are ignored.
+    pub fn max_pp(&self) -> CatchPerformanceAttributes {
+        let mut calculator = self.clone();
+        calculator.combo = None;
+        calculator.n_fruits = None;
+        calculator.n_droplets = None;
+        calculator.n_tiny_droplets = None;
+        calculator.n_tiny_droplet_misses = None;
+        calculator.n_misses = None;
+
+        calculator.calculate()
+    }
+
     /// Calculate all performance related values, including pp and stars.
     pub fn calculate(mut self) -> CatchPerformanceAttributes {
         let attributes = self.attributes.take().unwrap_or_else(|| {
@@ -431,9 +462,7 @@ impl CatchPPInner {
         if total_hits == 0 {
             1.0
         } else {
-            (self.successful_hits() as f64 / total_hits as f64)
-                .max(0.0)
-                .min(1.0)
+            (self.successful_hits() as f64 / total_hits as f64).clamp(0.0, 1.0)
         }
     }
 }
@@ -469,7 +498,7 @@ impl<'map> From<OsuPP<'map>> for CatchPP<'map> {
             clock_rate,
         };
 
-        match acc {
+        match acc.and_then(Accuracy::from_fraction) {
             Some(acc) => res.accuracy(acc),
             None => res,
         }
@@ -545,7 +574,7 @@ mod test {
         let calculator = CatchPP::new(&map)
             .attributes(attributes)
             .passed_objects(total_objects)
-            .accuracy(target_acc);
+            .accuracy(Accuracy::from_percent(target_acc).unwrap());
 
         let numerator = calculator.n_fruits.unwrap_or(0)
             + calculator.n_droplets.unwrap_or(0)
@@ -578,7 +607,7 @@ mod test {
             .passed_objects(total_objects)
             .droplets(n_droplets)
             .tiny_droplets(n_tiny_droplets)
-            .accuracy(target_acc);
+            .accuracy(Accuracy::from_percent(target_acc).unwrap());
 
         assert_eq!(
             n_droplets,
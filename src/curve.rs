@@ -33,14 +33,12 @@ impl BezierBuffers {
 
         let additional = len - self.left.len();
 
-        self.left
-            .extend(iter::repeat(Pos2::zero()).take(additional));
-        self.right
-            .extend(iter::repeat(Pos2::zero()).take(additional));
+        self.left.extend(iter::repeat_n(Pos2::zero(), additional));
+        self.right.extend(iter::repeat_n(Pos2::zero(), additional));
         self.midpoints
-            .extend(iter::repeat(Pos2::zero()).take(additional));
+            .extend(iter::repeat_n(Pos2::zero(), additional));
         self.left_child
-            .extend(iter::repeat(Pos2::zero()).take(additional));
+            .extend(iter::repeat_n(Pos2::zero(), additional));
     }
 }
 
@@ -63,6 +61,8 @@ impl<'bufs> Curve<'bufs> {
         expected_len: Option<f64>,
         bufs: &'bufs mut CurveBuffers,
     ) -> Self {
+        let expected_len = expected_len.filter(|len| len.is_finite() && *len > 0.0);
+
         Self::calculate_path(points, bufs);
         Self::calculate_length(points, bufs, expected_len);
 
@@ -308,8 +308,16 @@ impl<'bufs> Curve<'bufs> {
             2
         } else {
             let divisor = 2.0 * (1.0 - CIRCULAR_ARC_TOLERANCE / pr.radius).acos();
-
-            ((pr.theta_range / divisor as f64).ceil() as usize).max(2)
+            let amount_points = (pr.theta_range / divisor as f64).ceil();
+
+            // Nearly collinear points have a huge radius so the divisor approaches zero.
+            // osu!lazer casts the resulting amount to `int` which overflows to a negative
+            // value for too large amounts and thus falls back to 2 points.
+            if amount_points <= i32::MAX as f64 {
+                (amount_points as usize).max(2)
+            } else {
+                2
+            }
         };
 
         path.reserve_exact(amount_points);
@@ -504,6 +512,10 @@ impl<'bufs> Curve<'bufs> {
 
         let radius = d_a.length();
 
+        if !radius.is_finite() {
+            return None;
+        }
+
         let theta_start = (d_a.y as f64).atan2(d_a.x as f64);
         let mut theta_end = (d_c.y as f64).atan2(d_c.x as f64);
 
@@ -0,0 +1,133 @@
+use crate::{
+    catch::CatchDifficultyAttributes, mania::ManiaDifficultyAttributes,
+    osu::OsuDifficultyAttributes, taiko::TaikoDifficultyAttributes, DifficultyAttributes,
+};
+
+/// The field-wise comparison of two [`DifficultyAttributes`] of the same mode.
+///
+/// Created through [`DifficultyAttributes::diff`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttributesDiff {
+    /// The compared values of all fields in declaration order.
+    pub fields: Vec<AttributeDelta>,
+}
+
+impl AttributesDiff {
+    /// The compared values of the field with the given name.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&AttributeDelta> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Iterate over all fields whose values differ by more than `epsilon`.
+    #[inline]
+    pub fn changed(&self, epsilon: f64) -> impl Iterator<Item = &AttributeDelta> {
+        self.fields
+            .iter()
+            .filter(move |field| field.delta().abs() > epsilon)
+    }
+
+    /// Whether no field differs by more than `epsilon`.
+    #[inline]
+    pub fn is_equal(&self, epsilon: f64) -> bool {
+        self.changed(epsilon).next().is_none()
+    }
+}
+
+/// The values of a single attribute field before and after.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AttributeDelta {
+    /// The name of the field, e.g. `"stars"`.
+    pub name: &'static str,
+    /// The value of the attributes on which [`DifficultyAttributes::diff`] was called.
+    pub old: f64,
+    /// The value of the attributes that were passed to [`DifficultyAttributes::diff`].
+    pub new: f64,
+}
+
+impl AttributeDelta {
+    /// The difference `new - old`.
+    #[inline]
+    pub fn delta(&self) -> f64 {
+        self.new - self.old
+    }
+}
+
+pub(crate) fn diff(
+    old: &DifficultyAttributes,
+    new: &DifficultyAttributes,
+) -> Option<AttributesDiff> {
+    let (old, new) = match (old, new) {
+        (DifficultyAttributes::Osu(old), DifficultyAttributes::Osu(new)) => (osu(old), osu(new)),
+        (DifficultyAttributes::Taiko(old), DifficultyAttributes::Taiko(new)) => {
+            (taiko(old), taiko(new))
+        }
+        (DifficultyAttributes::Catch(old), DifficultyAttributes::Catch(new)) => {
+            (catch(old), catch(new))
+        }
+        (DifficultyAttributes::Mania(old), DifficultyAttributes::Mania(new)) => {
+            (mania(old), mania(new))
+        }
+        _ => return None,
+    };
+
+    let fields = old
+        .iter()
+        .zip(new.iter())
+        .map(|(&(name, old), &(_, new))| AttributeDelta { name, old, new })
+        .collect();
+
+    Some(AttributesDiff { fields })
+}
+
+fn osu(attrs: &OsuDifficultyAttributes) -> Vec<(&'static str, f64)> {
+    vec![
+        ("aim", attrs.aim),
+        ("speed", attrs.speed),
+        ("flashlight", attrs.flashlight),
+        ("slider_factor", attrs.slider_factor),
+        ("speed_note_count", attrs.speed_note_count),
+        ("ar", attrs.ar),
+        ("od", attrs.od),
+        ("hp", attrs.hp),
+        ("preempt", attrs.preempt),
+        ("fade_in", attrs.fade_in),
+        ("n_circles", attrs.n_circles as f64),
+        ("n_sliders", attrs.n_sliders as f64),
+        ("n_spinners", attrs.n_spinners as f64),
+        ("stars", attrs.stars),
+        ("max_combo", attrs.max_combo as f64),
+    ]
+}
+
+fn taiko(attrs: &TaikoDifficultyAttributes) -> Vec<(&'static str, f64)> {
+    vec![
+        ("stamina", attrs.stamina),
+        ("rhythm", attrs.rhythm),
+        ("colour", attrs.colour),
+        ("peak", attrs.peak),
+        ("hit_window", attrs.hit_window),
+        ("stars", attrs.stars),
+        ("max_combo", attrs.max_combo as f64),
+    ]
+}
+
+fn catch(attrs: &CatchDifficultyAttributes) -> Vec<(&'static str, f64)> {
+    vec![
+        ("stars", attrs.stars),
+        ("ar", attrs.ar),
+        ("n_fruits", attrs.n_fruits as f64),
+        ("n_droplets", attrs.n_droplets as f64),
+        ("n_tiny_droplets", attrs.n_tiny_droplets as f64),
+        ("catcher_width", attrs.catcher_width),
+        ("fruit_radius", attrs.fruit_radius),
+    ]
+}
+
+fn mania(attrs: &ManiaDifficultyAttributes) -> Vec<(&'static str, f64)> {
+    vec![
+        ("stars", attrs.stars),
+        ("hit_window", attrs.hit_window),
+        ("max_combo", attrs.max_combo as f64),
+    ]
+}
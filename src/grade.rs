@@ -0,0 +1,139 @@
+use crate::{GameMode, Mods, ScoreState};
+
+/// The letter grade of a score.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Grade {
+    /// Grade D
+    D,
+    /// Grade C
+    C,
+    /// Grade B
+    B,
+    /// Grade A
+    A,
+    /// Grade S
+    S,
+    /// Silver S i.e. grade S with HD or FL
+    SH,
+    /// Grade SS
+    X,
+    /// Silver SS i.e. grade SS with HD or FL
+    XH,
+}
+
+impl Grade {
+    fn silver(self, mods: u32) -> Self {
+        if !(mods.hd() || mods.fl()) {
+            return self;
+        }
+
+        match self {
+            Self::S => Self::SH,
+            Self::X => Self::XH,
+            _ => self,
+        }
+    }
+}
+
+/// Calculate the grade of a score through its hitresults.
+///
+/// For osu!catch, `n300` are the caught fruits, `n100` the caught droplets,
+/// and `n50` the caught tiny droplets. `n_misses` should include missed tiny droplets.
+///
+/// For osu!mania, `n300` should include the amount of 320s while 200s are not considered.
+/// Use [`ScoreState::grade`] to consider all hitresults.
+#[inline]
+pub fn grade(
+    mode: GameMode,
+    mods: u32,
+    n300: usize,
+    n100: usize,
+    n50: usize,
+    n_misses: usize,
+) -> Grade {
+    let state = ScoreState {
+        n300,
+        n100,
+        n50,
+        n_misses,
+        ..Default::default()
+    };
+
+    state.grade(mode, mods)
+}
+
+impl ScoreState {
+    /// Calculate the grade of the score.
+    ///
+    /// The hitresults are interpreted as described for the fields of [`ScoreState`].
+    pub fn grade(&self, mode: GameMode, mods: u32) -> Grade {
+        let grade = match mode {
+            GameMode::Osu | GameMode::Taiko => self.ratio_grade(),
+            GameMode::Catch => {
+                let caught = self.n300 + self.n100 + self.n50;
+                let total = caught + self.n_katu + self.n_misses;
+
+                accuracy_grade(caught as f64, total as f64, [0.98, 0.94, 0.9, 0.85])
+            }
+            GameMode::Mania => {
+                let total =
+                    self.n_geki + self.n300 + self.n_katu + self.n100 + self.n50 + self.n_misses;
+
+                let numerator =
+                    6 * (self.n_geki + self.n300) + 4 * self.n_katu + 2 * self.n100 + self.n50;
+
+                accuracy_grade(numerator as f64, (6 * total) as f64, [0.95, 0.9, 0.8, 0.7])
+            }
+        };
+
+        grade.silver(mods)
+    }
+
+    fn ratio_grade(&self) -> Grade {
+        let total = self.n300 + self.n100 + self.n50 + self.n_misses;
+
+        if total == 0 {
+            return Grade::D;
+        }
+
+        let ratio300 = self.n300 as f64 / total as f64;
+        let ratio50 = self.n50 as f64 / total as f64;
+        let full_combo = self.n_misses == 0;
+
+        if self.n300 == total {
+            Grade::X
+        } else if ratio300 > 0.9 && ratio50 <= 0.01 && full_combo {
+            Grade::S
+        } else if (ratio300 > 0.8 && full_combo) || ratio300 > 0.9 {
+            Grade::A
+        } else if (ratio300 > 0.7 && full_combo) || ratio300 > 0.8 {
+            Grade::B
+        } else if ratio300 > 0.6 {
+            Grade::C
+        } else {
+            Grade::D
+        }
+    }
+}
+
+fn accuracy_grade(numerator: f64, denominator: f64, [s, a, b, c]: [f64; 4]) -> Grade {
+    if denominator <= 0.0 {
+        return Grade::D;
+    }
+
+    let acc = numerator / denominator;
+
+    if (acc - 1.0).abs() <= f64::EPSILON {
+        Grade::X
+    } else if acc > s {
+        Grade::S
+    } else if acc > a {
+        Grade::A
+    } else if acc > b {
+        Grade::B
+    } else if acc > c {
+        Grade::C
+    } else {
+        Grade::D
+    }
+}
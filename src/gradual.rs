@@ -18,7 +18,7 @@ use crate::{
 /// # Example
 ///
 /// ```no_run
-/// use rosu_pp::{Beatmap, GradualDifficultyAttributes};
+/// use akatsuki_pp::{Beatmap, GradualDifficultyAttributes};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -129,6 +129,8 @@ impl From<ScoreState> for OsuScoreState {
             n100: state.n100,
             n50: state.n50,
             n_misses: state.n_misses,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         }
     }
 }
@@ -199,7 +201,7 @@ impl From<ScoreState> for ManiaScoreState {
 /// # Example
 ///
 /// ```no_run
-/// use rosu_pp::{Beatmap, GradualPerformanceAttributes, ScoreState};
+/// use akatsuki_pp::{Beatmap, GradualPerformanceAttributes, ScoreState};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -291,6 +293,93 @@ impl<'map> GradualPerformanceAttributes<'map> {
         }
     }
 
+    /// Calculate the performance of a replay given the judgement of each hit object.
+    ///
+    /// A judgement is expected for every hit object in order, see [`HitResult`] for
+    /// the results of each mode. For osu!taiko only circles are judged and for
+    /// osu!catch every fruit, droplet, and tiny droplet. Combo is tracked based on the
+    /// map's max combo after each object i.e. sliders increase the combo by their amount
+    /// of ticks and a miss resets it.
+    ///
+    /// In osu!standard and osu!mania the first object is processed together with the
+    /// second one so the curve contains no value for the first judgement. Similarly, the
+    /// first two objects in osu!taiko are processed together with the third one. In
+    /// osu!catch tiny droplets are not processed on their own so their judgements only
+    /// add a value to the curve alongside the next fruit or droplet.
+    /// Judgements beyond the map's amount of hit objects are ignored.
+    pub fn from_judgements(
+        map: &'map Beatmap,
+        mods: u32,
+        judgements: &[Judgement],
+    ) -> JudgementPerformance {
+        let mut difficulty = GradualDifficultyAttributes::new(map, mods);
+        let mut gradual = Self::new(map, mods);
+        let mut state = ScoreState::new();
+        let mut combo = 0;
+        let mut prev_max_combo = 0;
+        let mut curve = Vec::with_capacity(judgements.len());
+        let mut attributes = None;
+        let mut pending_miss = false;
+
+        // The gradual calculators process the first few objects together
+        let mut skip = match map.mode {
+            GameMode::Osu | GameMode::Mania => 1,
+            GameMode::Taiko => map
+                .hit_objects
+                .iter()
+                .take(2)
+                .filter(|h| h.is_circle())
+                .count(),
+            GameMode::Catch => 0,
+        };
+
+        for judgement in judgements {
+            if !judgement.result.apply(map.mode, &mut state) {
+                continue;
+            }
+
+            let miss = matches!(
+                judgement.result,
+                HitResult::Miss | HitResult::TinyDropletMiss
+            ) || pending_miss;
+
+            if skip > 0 {
+                skip -= 1;
+                pending_miss = miss;
+
+                continue;
+            }
+
+            pending_miss = false;
+
+            let max_combo = match difficulty.next() {
+                Some(difficulty) => difficulty.max_combo(),
+                None => break,
+            };
+
+            let combo_gain = max_combo.saturating_sub(prev_max_combo);
+            prev_max_combo = max_combo;
+
+            if miss {
+                combo = 0;
+            } else {
+                combo += combo_gain;
+            }
+
+            state.max_combo = state.max_combo.max(combo);
+
+            let performance = match gradual.process_next_object(state.clone()) {
+                Some(performance) => performance,
+                None => break,
+            };
+
+            curve.push((judgement.time, performance.pp()));
+            attributes = Some(performance);
+        }
+
+        JudgementPerformance { attributes, curve }
+    }
+
     /// Process the next hit object and calculate the
     /// performance attributes for the resulting score.
     pub fn process_next_object(&mut self, state: ScoreState) -> Option<PerformanceAttributes> {
@@ -324,3 +413,85 @@ impl<'map> GradualPerformanceAttributes<'map> {
         }
     }
 }
+
+/// The judgement of a single hit object, e.g. taken from a replay.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Judgement {
+    /// The time of the judgement in milliseconds.
+    pub time: f64,
+    /// The hit result.
+    pub result: HitResult,
+}
+
+/// The hit result of a [`Judgement`].
+///
+/// For osu!catch, `N300` stands for fruits, `N100` for droplets, `N50` for tiny droplets,
+/// and `Miss` for missed fruits and droplets.
+///
+/// `N320` and `N200` only exist in osu!mania and count as `N300` and `N100` in other modes.
+/// `TinyDropletMiss` only exists in osu!catch and counts as `Miss` in other modes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HitResult {
+    /// A 320 in osu!mania.
+    N320,
+    /// A 300 (fruit for osu!catch).
+    N300,
+    /// A 200 in osu!mania.
+    N200,
+    /// A 100 (droplet for osu!catch).
+    N100,
+    /// A 50 (tiny droplet for osu!catch).
+    N50,
+    /// A missed tiny droplet in osu!catch.
+    TinyDropletMiss,
+    /// A miss.
+    Miss,
+}
+
+impl HitResult {
+    /// Add the hit result to the state and return whether it
+    /// belongs to an object that the gradual calculators process.
+    fn apply(self, mode: GameMode, state: &mut ScoreState) -> bool {
+        match (self, mode) {
+            (Self::N320, GameMode::Mania) => state.n_geki += 1,
+            (Self::N200, GameMode::Mania) => state.n_katu += 1,
+            (Self::N50, GameMode::Catch) => {
+                state.n50 += 1;
+
+                return false;
+            }
+            (Self::TinyDropletMiss, GameMode::Catch) => {
+                state.n_katu += 1;
+
+                return false;
+            }
+            (Self::N300, _) | (Self::N320, _) => state.n300 += 1,
+            (Self::N100, _) | (Self::N200, _) => state.n100 += 1,
+            (Self::N50, _) => state.n50 += 1,
+            (Self::Miss, _) | (Self::TinyDropletMiss, _) => state.n_misses += 1,
+        }
+
+        true
+    }
+}
+
+/// The result of [`GradualPerformanceAttributes::from_judgements`].
+#[derive(Clone, Debug)]
+pub struct JudgementPerformance {
+    /// The performance attributes after the last judgement.
+    ///
+    /// `None` if there were no judgements.
+    pub attributes: Option<PerformanceAttributes>,
+    /// The time and pp value after each judgement.
+    pub curve: Vec<(f64, f64)>,
+}
+
+impl JudgementPerformance {
+    /// The final pp value, `0.0` if there were no judgements.
+    #[inline]
+    pub fn pp(&self) -> f64 {
+        self.attributes
+            .as_ref()
+            .map_or(0.0, PerformanceAttributes::pp)
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    convert::TryFrom,
+    fmt::{Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+};
+
+use serde::{
+    de::{Error as DeError, SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    mods::{MODS, PF, SD},
+    Mods,
+};
+
+/// A single mod as returned by the osu!lazer API.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct LazerMod {
+    /// The mod's acronym, e.g. `"DT"`.
+    pub acronym: String,
+    /// The mod's custom settings.
+    #[serde(default)]
+    pub settings: LazerModSettings,
+}
+
+/// Custom settings of a [`LazerMod`].
+///
+/// Settings that are irrelevant for difficulty and performance calculation are ignored.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct LazerModSettings {
+    /// The custom speed change of DT, NC, HT, or DC.
+    pub speed_change: Option<f64>,
+}
+
+/// Mods converted from osu!lazer's structured representation.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::LazerMods;
+///
+/// let json = r#"[{"acronym":"HD"},{"acronym":"DT","settings":{"speed_change":1.3}}]"#;
+/// let mods = LazerMods::from_json(json).unwrap();
+///
+/// assert_eq!(mods.mods, 8 + 64);
+/// assert!((mods.clock_rate() - 1.3).abs() < f64::EPSILON);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LazerMods {
+    /// The mods as legacy bit values.
+    pub mods: u32,
+    /// The custom speed change of DT, NC, HT, or DC, if any.
+    pub speed_change: Option<f64>,
+}
+
+// The speed change is compared bitwise so that `LazerMods` can be used as key
+impl PartialEq for LazerMods {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.mods == other.mods
+            && self.speed_change.map(f64::to_bits) == other.speed_change.map(f64::to_bits)
+    }
+}
+
+impl Eq for LazerMods {}
+
+impl Hash for LazerMods {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.mods.hash(state);
+        self.speed_change.map(f64::to_bits).hash(state);
+    }
+}
+
+impl LazerMods {
+    /// Parse mods from the osu!lazer API's JSON representation
+    /// i.e. a list of objects containing an acronym and optional settings.
+    ///
+    /// Unknown acronyms are ignored.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let mods: Vec<LazerMod> = serde_json::from_str(json)?;
+
+        Ok(Self::from_mods(&mods))
+    }
+
+    /// Convert a list of [`LazerMod`]s.
+    ///
+    /// Unknown acronyms are ignored.
+    pub fn from_mods(mods: &[LazerMod]) -> Self {
+        let mut this = Self::default();
+
+        for lazer_mod in mods {
+            this.mods |= acronym_bits(&lazer_mod.acronym);
+
+            if let Some(speed_change) = lazer_mod.settings.speed_change {
+                this.speed_change = Some(speed_change);
+            }
+        }
+
+        this
+    }
+
+    /// The clock rate of the mods, considering a custom speed change.
+    ///
+    /// The result can be passed to the `clock_rate` method of calculators.
+    #[inline]
+    pub fn clock_rate(&self) -> f64 {
+        match self.speed_change {
+            Some(speed_change) => self.mods.speed_change(speed_change),
+            None => self.mods.clock_rate(),
+        }
+    }
+}
+
+/// Legacy mod bits that can be (de)serialized as either an integer or acronyms.
+///
+/// Serializes as integer by default. To serialize as a list of acronyms instead,
+/// annotate the field with `#[serde(serialize_with = "GameMods::serialize_acronyms")]`.
+///
+/// Deserializes from an integer, a string of an integer or of concatenated acronyms
+/// like `"HDDT"`, or a list of acronyms or osu!lazer mods like `[{"acronym":"DT"}]`.
+/// Unknown acronyms and the settings of osu!lazer mods are ignored.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::GameMods;
+///
+/// let mods: GameMods = serde_json::from_str(r#"["HD", "DT"]"#).unwrap();
+/// assert_eq!(mods, GameMods(8 + 64));
+///
+/// let mods: GameMods = serde_json::from_str("72").unwrap();
+/// assert_eq!(mods.acronyms(), ["HD", "DT"]);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GameMods(pub u32);
+
+impl GameMods {
+    /// The acronyms of all contained mods, e.g. `["HD", "NC"]`.
+    ///
+    /// Mods that are implied by others, i.e. DT for NC and SD for PF, are omitted.
+    pub fn acronyms(self) -> Vec<&'static str> {
+        let mut implied = 0;
+
+        if self.0 & u32::NC > 0 {
+            implied |= u32::DT;
+        }
+
+        if self.0 & PF > 0 {
+            implied |= SD;
+        }
+
+        MODS.iter()
+            .filter(|(bit, ..)| self.0 & bit > 0 && implied & bit == 0)
+            .map(|(_, acronym, _)| *acronym)
+            .collect()
+    }
+
+    /// Serialize the mods as a list of acronyms, see [`acronyms`](GameMods::acronyms).
+    ///
+    /// Meant to be used through `#[serde(serialize_with = "GameMods::serialize_acronyms")]`.
+    pub fn serialize_acronyms<S: Serializer>(mods: &Self, s: S) -> Result<S::Ok, S::Error> {
+        let acronyms = mods.acronyms();
+        let mut seq = s.serialize_seq(Some(acronyms.len()))?;
+
+        for acronym in acronyms {
+            seq.serialize_element(acronym)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl From<u32> for GameMods {
+    #[inline]
+    fn from(mods: u32) -> Self {
+        Self(mods)
+    }
+}
+
+impl From<GameMods> for u32 {
+    #[inline]
+    fn from(mods: GameMods) -> Self {
+        mods.0
+    }
+}
+
+impl Serialize for GameMods {
+    #[inline]
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u32(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for GameMods {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        d.deserialize_any(GameModsVisitor)
+    }
+}
+
+struct GameModsVisitor;
+
+impl<'de> Visitor<'de> for GameModsVisitor {
+    type Value = GameMods;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("integer mods, a string of acronyms, or a list of acronyms")
+    }
+
+    fn visit_u64<E: DeError>(self, v: u64) -> Result<Self::Value, E> {
+        u32::try_from(v)
+            .map(GameMods)
+            .map_err(|_| DeError::custom(format!("invalid mods {}", v)))
+    }
+
+    fn visit_i64<E: DeError>(self, v: i64) -> Result<Self::Value, E> {
+        u32::try_from(v)
+            .map(GameMods)
+            .map_err(|_| DeError::custom(format!("invalid mods {}", v)))
+    }
+
+    fn visit_str<E: DeError>(self, v: &str) -> Result<Self::Value, E> {
+        if let Ok(mods) = v.parse() {
+            return Ok(GameMods(mods));
+        }
+
+        let mut mods = 0;
+        let mut rest = v.trim();
+
+        // Acronyms are two characters long except for SV2
+        while !rest.is_empty() {
+            let len = if matches!(rest.get(..3), Some(prefix) if prefix.eq_ignore_ascii_case("SV2"))
+            {
+                3
+            } else {
+                rest.char_indices().nth(2).map_or(rest.len(), |(i, _)| i)
+            };
+
+            mods |= acronym_bits(&rest[..len]);
+            rest = &rest[len..];
+        }
+
+        Ok(GameMods(mods))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut mods = 0;
+
+        while let Some(elem) = seq.next_element::<AcronymOrLazerMod>()? {
+            let acronym = match &elem {
+                AcronymOrLazerMod::Acronym(acronym) => acronym,
+                AcronymOrLazerMod::LazerMod(lazer_mod) => &lazer_mod.acronym,
+            };
+
+            mods |= acronym_bits(acronym);
+        }
+
+        Ok(GameMods(mods))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AcronymOrLazerMod {
+    Acronym(String),
+    LazerMod(LazerMod),
+}
+
+fn acronym_bits(acronym: &str) -> u32 {
+    let acronym = acronym.to_ascii_uppercase();
+
+    match acronym.as_str() {
+        "DC" => return u32::HT,
+        "NC" => return u32::NC | u32::DT,
+        "PF" => return PF | SD,
+        _ => {}
+    }
+
+    MODS.iter()
+        .find(|(_, mod_acronym, _)| *mod_acronym == acronym)
+        .map_or(0, |(bit, ..)| *bit)
+}
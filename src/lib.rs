@@ -5,7 +5,7 @@
 //! ## Usage
 //!
 //! ```no_run
-//! use rosu_pp::{Beatmap, BeatmapExt};
+//! use akatsuki_pp::{Accuracy, Beatmap, BeatmapExt};
 //!
 //! # /*
 //! // Parse the map yourself
@@ -20,7 +20,7 @@
 //! let result = map.pp()
 //!     .mods(24) // HDHR
 //!     .combo(1234)
-//!     .accuracy(99.2)
+//!     .accuracy(Accuracy::from_percent(99.2).unwrap())
 //!     .n_misses(2)
 //!     .calculate();
 //!
@@ -34,7 +34,7 @@
 //!     .combo(543)
 //!     .n_misses(5)
 //!     .n50(3)
-//!     .accuracy(96.5)
+//!     .accuracy(Accuracy::from_percent(96.5).unwrap())
 //!     .calculate();
 //!
 //! println!("Next PP: {}", next_result.pp());
@@ -53,7 +53,7 @@
 //! If either the `async_tokio` or `async_std` feature is enabled, beatmap parsing will be async.
 //!
 //! ```no_run
-//! use rosu_pp::{Beatmap, BeatmapExt};
+//! use akatsuki_pp::{Accuracy, Beatmap, BeatmapExt};
 //!
 //! # /*
 //! // Parse the map asynchronously
@@ -68,7 +68,7 @@
 //!     .mods(24) // HDHR
 //!     .combo(1234)
 //!     .n_misses(2)
-//!     .accuracy(99.2)
+//!     .accuracy(Accuracy::from_percent(99.2).unwrap())
 //!     .calculate();
 //!
 //! println!("PP: {}", result.pp());
@@ -82,7 +82,7 @@
 //! Instead, you should use [`GradualDifficultyAttributes`] and [`GradualPerformanceAttributes`]:
 //!
 //! ```no_run
-//! use rosu_pp::{
+//! use akatsuki_pp::{
 //!     Beatmap, BeatmapExt, GradualPerformanceAttributes, ScoreState,
 //!     taiko::TaikoScoreState,
 //! };
@@ -159,6 +159,12 @@
 //! println!("PP after the first 11 objects: {}", curr_performance.pp());
 //! ```
 //!
+//! ## Thread safety
+//!
+//! [`Beatmap`] is `Send + Sync` so a parsed map can be shared across threads, e.g. through
+//! an `Arc` or scoped threads. All difficulty and performance calculators only borrow the map
+//! and are `Send` so each thread can create and run its own calculators on the shared map.
+//!
 //! ## Features
 //!
 //! | Flag | Description |
@@ -166,6 +172,10 @@
 //! | `default` | Beatmap parsing will be non-async |
 //! | `async_tokio` | Beatmap parsing will be async through [tokio](https://github.com/tokio-rs/tokio) |
 //! | `async_std` | Beatmap parsing will be async through [async-std](https://github.com/async-rs/async-std) |
+//! | `cache` | Enables `DifficultyCache`, a bounded cache for difficulty attributes |
+//! | `lazer` | Enables `LazerMods` to convert osu!lazer's JSON mods |
+//! | `metrics` | Enables `Beatmap::parse_with_stats` to measure parsing |
+//! | `attribution` | Enables `OsuStars::attribution` to list the strain of each hit object |
 //!
 
 #![cfg_attr(docsrs, feature(doc_cfg), deny(broken_intra_doc_links))]
@@ -198,10 +208,22 @@ pub mod parse;
 
 /// Beatmap and contained types
 pub mod beatmap;
-pub use beatmap::{Beatmap, GameMode};
+pub use beatmap::{Beatmap, BeatmapBuilder, GameMode, ObjectCounts};
 
 mod gradual;
-pub use gradual::{GradualDifficultyAttributes, GradualPerformanceAttributes, ScoreState};
+pub use gradual::{
+    GradualDifficultyAttributes, GradualPerformanceAttributes, HitResult, Judgement,
+    JudgementPerformance, ScoreState,
+};
+
+mod accuracy;
+pub use accuracy::Accuracy;
+
+mod grade;
+pub use grade::{grade, Grade};
+
+mod diff;
+pub use diff::{AttributeDelta, AttributesDiff};
 
 mod pp;
 pub use pp::{AnyPP, AttributeProvider, HitResultPriority};
@@ -209,9 +231,36 @@ pub use pp::{AnyPP, AttributeProvider, HitResultPriority};
 mod stars;
 pub use stars::AnyStars;
 
+mod precision;
+pub use precision::Precision;
+
+mod source;
+pub use source::{calculate_by_id, BeatmapSource, SourceError};
+
+#[cfg(any(feature = "async_tokio", feature = "async_std"))]
+pub use source::FetchFuture;
+
+mod calc_error;
+pub use calc_error::CalcError;
+
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "cache")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+pub use cache::DifficultyCache;
+
+#[cfg(feature = "lazer")]
+mod lazer;
+#[cfg(feature = "lazer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lazer")))]
+pub use lazer::{GameMods, LazerMod, LazerModSettings, LazerMods};
+
 mod curve;
 mod mods;
-mod util;
+mod sealed;
+
+/// Utility types and math helpers
+pub mod util;
 
 pub use catch::{CatchPP, CatchStars};
 pub use mania::{ManiaPP, ManiaStars};
@@ -220,10 +269,12 @@ pub use taiko::{TaikoPP, TaikoStars};
 
 pub use mods::Mods;
 pub use parse::{ParseError, ParseResult};
-pub use util::SortedVec;
+pub use util::{SortedVec, StrainSummary};
 
 /// Provides some additional methods on [`Beatmap`].
-pub trait BeatmapExt {
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait BeatmapExt: sealed::Sealed {
     /// Calculate the stars and other attributes of a beatmap which are required for pp calculation.
     fn stars(&self) -> AnyStars<'_>;
 
@@ -254,6 +305,24 @@ pub trait BeatmapExt {
     /// Suitable to efficiently get a score's performance after multiple different locations,
     /// i.e. live update a score's pp.
     fn gradual_performance(&self, mods: u32) -> GradualPerformanceAttributes<'_>;
+
+    /// Calculate the difficulty attributes for every mode that the map can be played in.
+    ///
+    /// osu!standard maps are converted to all other modes while maps of
+    /// other modes only provide the attributes of their own mode.
+    ///
+    /// Suitable to display the star ratings of a map's converts at once.
+    fn calculate_all(&self, mods: u32) -> AllModesAttributes;
+
+    /// Calculate the difficulty attributes in the given mode for commonly played mods,
+    /// i.e. NM, EZ, HT, HD, HR, DT, FL, HDHR, and HDDT in that order.
+    ///
+    /// osu!standard maps are converted to the mode while maps of other modes ignore it.
+    /// Attributes are only calculated once for mods that don't affect the difficulty
+    /// differently, e.g. HD in modes other than osu!standard.
+    ///
+    /// Suitable to display a table of star ratings on a map page.
+    fn common_mods_table(&self, mode: GameMode) -> Vec<(u32, DifficultyAttributes)>;
 }
 
 impl BeatmapExt for Beatmap {
@@ -307,6 +376,80 @@ impl BeatmapExt for Beatmap {
     fn gradual_performance(&self, mods: u32) -> GradualPerformanceAttributes<'_> {
         GradualPerformanceAttributes::new(self, mods)
     }
+
+    fn calculate_all(&self, mods: u32) -> AllModesAttributes {
+        let mut attrs = AllModesAttributes::default();
+
+        match self.mode {
+            GameMode::Osu => {
+                attrs.osu = Some(OsuStars::new(self).mods(mods).calculate());
+                attrs.taiko = Some(TaikoStars::new(self).mods(mods).calculate());
+                attrs.catch = Some(CatchStars::new(self).mods(mods).calculate());
+                attrs.mania = Some(ManiaStars::new(self).mods(mods).calculate());
+            }
+            GameMode::Taiko => attrs.taiko = Some(TaikoStars::new(self).mods(mods).calculate()),
+            GameMode::Catch => attrs.catch = Some(CatchStars::new(self).mods(mods).calculate()),
+            GameMode::Mania => attrs.mania = Some(ManiaStars::new(self).mods(mods).calculate()),
+        }
+
+        attrs
+    }
+
+    fn common_mods_table(&self, mode: GameMode) -> Vec<(u32, DifficultyAttributes)> {
+        const COMMON_MODS: [u32; 9] = [
+            0,
+            u32::EZ,
+            u32::HT,
+            u32::HD,
+            u32::HR,
+            u32::DT,
+            u32::FL,
+            u32::HD | u32::HR,
+            u32::HD | u32::DT,
+        ];
+
+        let mode = match self.mode {
+            GameMode::Osu => mode,
+            other => other,
+        };
+
+        let mut calculated: Vec<(pp::DifficultyKey, DifficultyAttributes)> = Vec::new();
+        let mut table = Vec::with_capacity(COMMON_MODS.len());
+
+        for &mods in COMMON_MODS.iter() {
+            let key = pp::difficulty_key(mode, mods);
+
+            let attrs = match calculated.iter().find(|(k, _)| *k == key) {
+                Some((_, attrs)) => attrs.clone(),
+                None => {
+                    let attrs = AnyStars::new(self).mode(mode).mods(mods).calculate();
+                    calculated.push((key, attrs.clone()));
+
+                    attrs
+                }
+            };
+
+            table.push((mods, attrs));
+        }
+
+        table
+    }
+}
+
+/// The difficulty attributes of a map for every mode it can be played in,
+/// see [`BeatmapExt::calculate_all`].
+///
+/// Each field is `None` if the map can't be converted to that mode.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AllModesAttributes {
+    /// The osu!standard attributes.
+    pub osu: Option<osu::OsuDifficultyAttributes>,
+    /// The osu!taiko attributes.
+    pub taiko: Option<taiko::TaikoDifficultyAttributes>,
+    /// The osu!catch attributes.
+    pub catch: Option<catch::CatchDifficultyAttributes>,
+    /// The osu!mania attributes.
+    pub mania: Option<mania::ManiaDifficultyAttributes>,
 }
 
 /// The result of calculating the strains on a map.
@@ -335,6 +478,16 @@ impl Strains {
         }
     }
 
+    /// Time in ms inbetween two strains in terms of the map's timestamps.
+    ///
+    /// Strains are measured in clock rate adjusted time so the sections of a map
+    /// played with a clock rate of 1.5, e.g. through DT, span 1.5 times as much
+    /// of the map's own time as [`section_len`](Self::section_len).
+    #[inline]
+    pub fn section_len_with_clock_rate(&self, clock_rate: f64) -> f64 {
+        util::section_len_with_clock_rate(self.section_len(), clock_rate)
+    }
+
     /// Returns the number of strain peaks per skill.
     #[inline]
     #[allow(clippy::len_without_is_empty)]
@@ -383,6 +536,77 @@ impl DifficultyAttributes {
             Self::Mania(attrs) => attrs.max_combo,
         }
     }
+
+    /// Whether no hit object was considered, e.g. because the attributes are
+    /// [`Default`] or the map has no (passed) objects.
+    ///
+    /// Attributes like that are all zero and should not be mistaken for the
+    /// attributes of an actual map with zero difficulty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Osu(attrs) => attrs.is_empty(),
+            Self::Taiko(attrs) => attrs.is_empty(),
+            Self::Catch(attrs) => attrs.is_empty(),
+            Self::Mania(attrs) => attrs.is_empty(),
+        }
+    }
+
+    /// Roughly estimate the attributes of two maps played back to back.
+    ///
+    /// The result is based on the attributes with the higher star rating while
+    /// the max combo and all object counts are summed up.
+    /// Returns `None` if the attributes are of different modes.
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        let (harder, easier) = if self.stars() >= other.stars() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let merged = match (harder, easier) {
+            (Self::Osu(harder), Self::Osu(easier)) => Self::Osu(osu::OsuDifficultyAttributes {
+                speed_note_count: harder.speed_note_count + easier.speed_note_count,
+                n_circles: harder.n_circles + easier.n_circles,
+                n_sliders: harder.n_sliders + easier.n_sliders,
+                n_spinners: harder.n_spinners + easier.n_spinners,
+                max_combo: harder.max_combo + easier.max_combo,
+                ..harder.clone()
+            }),
+            (Self::Taiko(harder), Self::Taiko(easier)) => {
+                Self::Taiko(taiko::TaikoDifficultyAttributes {
+                    max_combo: harder.max_combo + easier.max_combo,
+                    ..harder.clone()
+                })
+            }
+            (Self::Catch(harder), Self::Catch(easier)) => {
+                Self::Catch(catch::CatchDifficultyAttributes {
+                    n_fruits: harder.n_fruits + easier.n_fruits,
+                    n_droplets: harder.n_droplets + easier.n_droplets,
+                    n_tiny_droplets: harder.n_tiny_droplets + easier.n_tiny_droplets,
+                    ..harder.clone()
+                })
+            }
+            (Self::Mania(harder), Self::Mania(easier)) => {
+                Self::Mania(mania::ManiaDifficultyAttributes {
+                    max_combo: harder.max_combo + easier.max_combo,
+                    ..*harder
+                })
+            }
+            _ => return None,
+        };
+
+        Some(merged)
+    }
+
+    /// Compare the attributes field by field, e.g. to validate changes of the calculation.
+    ///
+    /// `self` is considered the old and `other` the new value of each [`AttributeDelta`].
+    /// Returns `None` if the attributes are of different modes.
+    #[inline]
+    pub fn diff(&self, other: &Self) -> Option<AttributesDiff> {
+        diff::diff(self, other)
+    }
 }
 
 impl From<osu::OsuDifficultyAttributes> for DifficultyAttributes {
@@ -3,15 +3,15 @@ use std::borrow::Cow;
 use crate::{
     beatmap::BeatmapHitWindows,
     parse::{HitObject, HitObjectKind},
-    util::FloatExt,
-    Beatmap, GameMode, Mods,
+    Beatmap, Mods,
 };
 
 use super::{
     difficulty_object::ManiaDifficultyObject,
-    mania_object::ObjectParameters,
+    mania_object::{hold_combo, ObjectParameters},
+    shuffled_columns,
     skills::{Skill, Strain},
-    ManiaDifficultyAttributes, ManiaObject, STAR_SCALING_FACTOR,
+    total_columns, ManiaDifficultyAttributes, ManiaDifficultyParams, ManiaObject, ManiaStars,
 };
 
 /// Gradually calculate the difficulty attributes of an osu!mania map.
@@ -23,10 +23,14 @@ use super::{
 /// If you want to calculate performance attributes, use
 /// [`ManiaGradualPerformanceAttributes`](crate::mania::ManiaGradualPerformanceAttributes) instead.
 ///
+/// To respect settings of a [`ManiaStars`] such as its key count or `lazer` flag,
+/// convert it through `From`. Its `passed_objects` are ignored, use
+/// [`resume_from`](Self::resume_from) to skip objects instead.
+///
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{Beatmap, mania::ManiaGradualDifficultyAttributes};
+/// use akatsuki_pp::{Beatmap, ManiaStars, mania::ManiaGradualDifficultyAttributes};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -36,6 +40,10 @@ use super::{
 /// let mods = 64; // DT
 /// let mut iter = ManiaGradualDifficultyAttributes::new(&map, mods);
 ///
+/// // Or with settings of a `ManiaStars`
+/// let stars = ManiaStars::new(&map).mods(mods).key_count(7).lazer(true);
+/// let iter_7k = ManiaGradualDifficultyAttributes::from(stars);
+///
 /// let attrs1 = iter.next(); // the difficulty of the map after the first hit object
 /// let attrs2 = iter.next(); //                           after the second hit object
 ///
@@ -53,24 +61,138 @@ pub struct ManiaGradualDifficultyAttributes<'map> {
     diff_objects: Vec<ManiaDifficultyObject>,
     curr_combo: usize,
     clock_rate: f64,
+    lazer: bool,
+    star_scaling_factor: f64,
 }
 
 impl<'map> ManiaGradualDifficultyAttributes<'map> {
     /// Create a new difficulty attributes iterator for osu!mania maps.
+    #[inline]
     pub fn new(map: &'map Beatmap, mods: u32) -> Self {
-        let map = map.convert_mode(GameMode::Mania);
-        let total_columns = map.cs.round_even().max(1.0);
-        let clock_rate = mods.clock_rate();
+        Self::with_params(map, mods, ManiaDifficultyParams::default())
+    }
+
+    /// Same as [`new`](ManiaGradualDifficultyAttributes::new) but with
+    /// adjusted constants, see [`ManiaStars::params`](crate::ManiaStars::params).
+    #[inline]
+    pub fn with_params(map: &'map Beatmap, mods: u32, params: ManiaDifficultyParams) -> Self {
+        Self::from(ManiaStars::new(map).mods(mods).params(params))
+    }
+
+    /// Create a new difficulty attributes iterator for osu!mania maps
+    /// that already processed the first `processed` items, e.g. to resume a session.
+    ///
+    /// The next call of [`Iterator::next`] returns the same attributes as the
+    /// next call after skipping `processed` items of an iterator created through
+    /// [`new`](Self::new).
+    ///
+    /// Note that the strain of the skipped objects still needs to be processed
+    /// so this does not come for free. However, it is still considerably faster than
+    /// iterating because the star rating is not evaluated for each skipped object.
+    #[inline]
+    pub fn resume(map: &'map Beatmap, mods: u32, processed: usize) -> Self {
+        Self::resume_from(ManiaStars::new(map).mods(mods), processed)
+    }
+
+    /// Same as [`resume`](Self::resume) but respecting the settings of a [`ManiaStars`]
+    /// like its `From` conversion does.
+    pub fn resume_from(stars: ManiaStars<'map>, processed: usize) -> Self {
+        let mut gradual = Self::from(stars);
+        gradual.advance(processed);
+
+        gradual
+    }
+
+    fn advance(&mut self, n: usize) {
+        for _ in 0..n {
+            let curr = match self.diff_objects.get(self.idx) {
+                Some(curr) => curr,
+                None => return,
+            };
+
+            self.idx += 1;
+
+            if let Some(h) = self.map.hit_objects.get(self.idx) {
+                Self::increment_combo(h, curr, &mut self.curr_combo, self.clock_rate, self.lazer);
+            }
+
+            self.strain.process(curr, &self.diff_objects);
+        }
+    }
+
+    fn increment_combo(
+        h: &HitObject,
+        diff_obj: &ManiaDifficultyObject,
+        curr_combo: &mut usize,
+        clock_rate: f64,
+        lazer: bool,
+    ) {
+        match &h.kind {
+            HitObjectKind::Circle => *curr_combo += 1,
+            _ => {
+                let start_time = diff_obj.start_time * clock_rate;
+                let end_time = diff_obj.end_time * clock_rate;
+                let duration = end_time - start_time;
+
+                *curr_combo += 1 + hold_combo(duration, lazer);
+            }
+        }
+    }
+}
+
+impl<'map> From<ManiaStars<'map>> for ManiaGradualDifficultyAttributes<'map> {
+    fn from(stars: ManiaStars<'map>) -> Self {
+        let ManiaStars {
+            map,
+            mods,
+            passed_objects: _,
+            clock_rate,
+            is_convert,
+            key_count,
+            params: difficulty_params,
+            start_time,
+            end_time,
+            object_range,
+            repeats,
+            rate_adjusted_hit_window,
+            lazer,
+            random_seed,
+        } = stars;
+
+        let map = map.convert_to_mania_with_mods(mods);
+
+        let map = if start_time.is_some() || end_time.is_some() || object_range.is_some() {
+            Cow::Owned(map.trim(start_time, end_time, object_range).into_owned())
+        } else {
+            map
+        };
+
+        let map = if repeats > 1 {
+            Cow::Owned(map.repeat(repeats).into_owned())
+        } else {
+            map
+        };
+        let star_scaling_factor = difficulty_params.star_scaling_factor;
+        let total_columns = key_count.map_or_else(|| total_columns(map.as_ref()), f32::from);
+        let clock_rate = clock_rate.unwrap_or_else(|| mods.clock_rate());
         let strain = Strain::new(total_columns as usize);
 
+        let hit_window_clock_rate = if rate_adjusted_hit_window {
+            clock_rate
+        } else {
+            1.0
+        };
+
         let BeatmapHitWindows { od: hit_window, .. } = map
             .attributes()
             .mods(mods)
-            .converted(matches!(map, Cow::Owned(_)))
-            .clock_rate(clock_rate)
+            .converted(is_convert || map.is_convert)
+            .clock_rate(hit_window_clock_rate)
             .hit_windows();
 
-        let mut params = ObjectParameters::new(map.as_ref());
+        let mut params = ObjectParameters::new(map.as_ref(), mods, lazer);
+        params.column_order =
+            random_seed.map(|seed| shuffled_columns(total_columns as usize, seed));
         let mut hit_objects = map.hit_objects.iter();
 
         let first = match hit_objects.next() {
@@ -84,6 +206,8 @@ impl<'map> ManiaGradualDifficultyAttributes<'map> {
                     diff_objects: Vec::new(),
                     curr_combo: 0,
                     clock_rate,
+                    lazer,
+                    star_scaling_factor,
                 }
             }
         };
@@ -109,24 +233,8 @@ impl<'map> ManiaGradualDifficultyAttributes<'map> {
             diff_objects,
             curr_combo,
             clock_rate,
-        }
-    }
-
-    fn increment_combo(
-        h: &HitObject,
-        diff_obj: &ManiaDifficultyObject,
-        curr_combo: &mut usize,
-        clock_rate: f64,
-    ) {
-        match &h.kind {
-            HitObjectKind::Circle => *curr_combo += 1,
-            _ => {
-                let start_time = diff_obj.start_time * clock_rate;
-                let end_time = diff_obj.end_time * clock_rate;
-                let duration = end_time - start_time;
-
-                *curr_combo += 1 + (duration / 100.0) as usize;
-            }
+            lazer,
+            star_scaling_factor,
         }
     }
 }
@@ -139,13 +247,13 @@ impl Iterator for ManiaGradualDifficultyAttributes<'_> {
         self.idx += 1;
 
         if let Some(h) = self.map.hit_objects.get(self.idx) {
-            Self::increment_combo(h, curr, &mut self.curr_combo, self.clock_rate);
+            Self::increment_combo(h, curr, &mut self.curr_combo, self.clock_rate, self.lazer);
         }
 
         self.strain.process(curr, &self.diff_objects);
 
         Some(ManiaDifficultyAttributes {
-            stars: self.strain.clone().difficulty_value() * STAR_SCALING_FACTOR,
+            stars: self.strain.difficulty_value() * self.star_scaling_factor,
             hit_window: self.hit_window,
             max_combo: self.curr_combo,
         })
@@ -160,17 +268,7 @@ impl Iterator for ManiaGradualDifficultyAttributes<'_> {
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
         let skip = n.min(self.len()).saturating_sub(1);
-
-        for _ in 0..skip {
-            let curr = self.diff_objects.get(self.idx)?;
-            self.idx += 1;
-
-            if let Some(h) = self.map.hit_objects.get(self.idx) {
-                Self::increment_combo(h, curr, &mut self.curr_combo, self.clock_rate);
-            }
-
-            self.strain.process(curr, &self.diff_objects);
-        }
+        self.advance(skip);
 
         self.next()
     }
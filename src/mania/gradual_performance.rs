@@ -1,6 +1,6 @@
-use crate::{Beatmap, ManiaPP};
+use crate::{Accuracy, Beatmap, ManiaPP, ManiaStars};
 
-use super::{ManiaGradualDifficultyAttributes, ManiaPerformanceAttributes};
+use super::{ManiaDifficultyParams, ManiaGradualDifficultyAttributes, ManiaPerformanceAttributes};
 
 /// Aggregation for a score's current state
 /// i.e. what are the current hitresults.
@@ -49,6 +49,47 @@ impl ManiaScoreState {
 
         numerator as f64 / denominator as f64
     }
+
+    /// Generate the hitresults for the given accuracy with as many 320s as possible.
+    ///
+    /// The accuracy is matched as closely as `n_objects` allows.
+    /// 300s are never generated since they're worth as much as 320s.
+    pub fn from_accuracy(n_objects: usize, acc: impl Into<Accuracy>, n_misses: usize) -> Self {
+        let n_misses = n_misses.min(n_objects);
+        let n_remaining = n_objects - n_misses;
+        let target_total = (acc.into().fraction() * (6 * n_objects) as f64).round() as usize;
+
+        // Every non-miss is worth at least one point, each 320 adds five more
+        let delta = target_total.saturating_sub(n_remaining);
+        let mut n320 = (delta / 5).min(n_remaining);
+
+        // The leftover points are covered by a 200 (+3) and 100s (+1)
+        let extra = delta - 5 * n320;
+        let n_rest = n_remaining - n320;
+        let mut n200 = (extra / 3).min(n_rest);
+        let mut n100 = (extra - 3 * n200).min(n_rest - n200);
+
+        // If too few objects are left for the remaining points,
+        // overshooting with another 320 might be closer
+        let missing = extra - 3 * n200 - n100;
+
+        if missing > 0 && n320 < n_remaining && 5 - extra < missing {
+            n320 += 1;
+            n200 = 0;
+            n100 = 0;
+        }
+
+        let n_rest = n_remaining - n320;
+
+        Self {
+            n320,
+            n300: 0,
+            n200,
+            n100,
+            n50: n_rest - n200 - n100,
+            n_misses,
+        }
+    }
 }
 
 /// Gradually calculate the performance attributes of an osu!mania map.
@@ -68,7 +109,7 @@ impl ManiaScoreState {
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{Beatmap, mania::{ManiaGradualPerformanceAttributes, ManiaScoreState}};
+/// use akatsuki_pp::{Beatmap, mania::{ManiaGradualPerformanceAttributes, ManiaScoreState}};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -129,10 +170,36 @@ pub struct ManiaGradualPerformanceAttributes<'map> {
     performance: ManiaPP<'map>,
 }
 
+impl<'map> From<ManiaPP<'map>> for ManiaGradualPerformanceAttributes<'map> {
+    /// Use the mods, clock rate, and `lazer` flag of the [`ManiaPP`]
+    /// while the given hitresults are ignored.
+    fn from(performance: ManiaPP<'map>) -> Self {
+        let mut stars = ManiaStars::new(performance.map)
+            .mods(performance.mods)
+            .lazer(performance.lazer);
+
+        if let Some(clock_rate) = performance.clock_rate {
+            stars = stars.clock_rate(clock_rate);
+        }
+
+        Self {
+            difficulty: ManiaGradualDifficultyAttributes::from(stars),
+            performance: performance.passed_objects(0),
+        }
+    }
+}
+
 impl<'map> ManiaGradualPerformanceAttributes<'map> {
     /// Create a new gradual performance calculator for osu!mania maps.
+    #[inline]
     pub fn new(map: &'map Beatmap, mods: u32) -> Self {
-        let difficulty = ManiaGradualDifficultyAttributes::new(map, mods);
+        Self::with_params(map, mods, ManiaDifficultyParams::default())
+    }
+
+    /// Same as [`new`](ManiaGradualPerformanceAttributes::new) but with
+    /// adjusted constants, see [`ManiaStars::params`](crate::ManiaStars::params).
+    pub fn with_params(map: &'map Beatmap, mods: u32, params: ManiaDifficultyParams) -> Self {
+        let difficulty = ManiaGradualDifficultyAttributes::with_params(map, mods, params);
         let performance = ManiaPP::new(map).mods(mods).passed_objects(0);
 
         Self {
@@ -1,7 +1,7 @@
 use crate::{
     curve::{Curve, CurveBuffers},
     parse::{HitObject, HitObjectKind},
-    Beatmap,
+    Beatmap, Mods,
 };
 
 const BASE_SCORING_DISTANCE: f64 = 100.0;
@@ -10,14 +10,20 @@ pub(crate) struct ObjectParameters<'a> {
     pub(crate) map: &'a Beatmap,
     pub(crate) max_combo: usize,
     pub(crate) curve_bufs: CurveBuffers,
+    pub(crate) lazer: bool,
+    pub(crate) mirror: bool,
+    pub(crate) column_order: Option<Vec<usize>>,
 }
 
 impl<'a> ObjectParameters<'a> {
-    pub(crate) fn new(map: &'a Beatmap) -> Self {
+    pub(crate) fn new(map: &'a Beatmap, mods: u32, lazer: bool) -> Self {
         Self {
             map,
             max_combo: 0,
             curve_bufs: CurveBuffers::default(),
+            lazer,
+            mirror: mods.mr(),
+            column_order: None,
         }
     }
 }
@@ -44,9 +50,21 @@ impl ManiaObject {
             map,
             max_combo,
             curve_bufs,
+            lazer,
+            mirror,
+            column_order,
         } = params;
 
-        let column = Self::column(h.pos.x, total_columns);
+        let mut column = Self::column(h.pos.x, total_columns);
+
+        if let Some(column_order) = column_order {
+            column = column_order[column];
+        }
+
+        if *mirror {
+            column = total_columns as usize - 1 - column;
+        }
+
         *max_combo += 1;
 
         match &h.kind {
@@ -76,7 +94,7 @@ impl ManiaObject {
                 let duration = span_count * dist / vel;
                 let end_time = h.start_time + duration;
 
-                *max_combo += (duration / 100.0) as usize;
+                *max_combo += hold_combo(duration, *lazer);
 
                 Self {
                     start_time: h.start_time,
@@ -85,7 +103,7 @@ impl ManiaObject {
                 }
             }
             HitObjectKind::Spinner { end_time } | HitObjectKind::Hold { end_time } => {
-                *max_combo += ((*end_time - h.start_time) / 100.0) as usize;
+                *max_combo += hold_combo(*end_time - h.start_time, *lazer);
 
                 Self {
                     start_time: h.start_time,
@@ -96,3 +114,15 @@ impl ManiaObject {
         }
     }
 }
+
+/// Additional combo of a hold note on top of its head.
+///
+/// Stable awards combo every 100ms while holding,
+/// lazer only awards combo for the tail.
+pub(crate) fn hold_combo(duration: f64, lazer: bool) -> usize {
+    if lazer {
+        1
+    } else {
+        (duration / 100.0) as usize
+    }
+}
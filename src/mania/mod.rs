@@ -5,9 +5,13 @@ mod mania_object;
 mod pp;
 mod skills;
 
-use std::borrow::Cow;
+use std::ops::Range;
 
-use crate::{beatmap::BeatmapHitWindows, util::FloatExt, Beatmap, GameMode, Mods, OsuStars};
+use crate::{
+    beatmap::BeatmapHitWindows,
+    util::{FloatExt, StrainSummary},
+    Beatmap, Mods, OsuStars,
+};
 
 pub use self::{gradual_difficulty::*, gradual_performance::*, pp::*};
 
@@ -21,13 +25,15 @@ use self::{
 
 const SECTION_LEN: f64 = 400.0;
 const STAR_SCALING_FACTOR: f64 = 0.018;
+// Highest key count of actual osu!mania maps
+const MAX_COLUMNS: f32 = 18.0;
 
 /// Difficulty calculator on osu!mania maps.
 ///
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{ManiaStars, Beatmap};
+/// use akatsuki_pp::{ManiaStars, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -42,31 +48,48 @@ const STAR_SCALING_FACTOR: f64 = 0.018;
 /// ```
 #[derive(Clone, Debug)]
 pub struct ManiaStars<'map> {
-    map: Cow<'map, Beatmap>,
+    pub(crate) map: &'map Beatmap,
     mods: u32,
     passed_objects: Option<usize>,
     clock_rate: Option<f64>,
     is_convert: bool,
+    key_count: Option<u8>,
+    params: ManiaDifficultyParams,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    object_range: Option<Range<usize>>,
+    repeats: usize,
+    rate_adjusted_hit_window: bool,
+    lazer: bool,
+    random_seed: Option<u64>,
 }
 
 impl<'map> ManiaStars<'map> {
     /// Create a new difficulty calculator for osu!mania maps.
     #[inline]
     pub fn new(map: &'map Beatmap) -> Self {
-        let map = map.convert_mode(GameMode::Mania);
-        let is_convert = matches!(map, Cow::Owned(_));
-
         Self {
             map,
             mods: 0,
             passed_objects: None,
             clock_rate: None,
-            is_convert,
+            is_convert: map.is_convert,
+            key_count: None,
+            params: ManiaDifficultyParams::default(),
+            start_time: None,
+            end_time: None,
+            object_range: None,
+            repeats: 1,
+            lazer: false,
+            random_seed: None,
+            rate_adjusted_hit_window: true,
         }
     }
 
     /// Specify mods through their bit values.
     ///
+    /// Key mods force the amount of columns of converted maps.
+    ///
     /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
     #[inline]
     pub fn mods(mut self, mods: u32) -> Self {
@@ -105,25 +128,131 @@ impl<'map> ManiaStars<'map> {
         self
     }
 
+    /// Force the amount of columns instead of deriving it from the map's circle size.
+    ///
+    /// The key count is clamped to `1..=18`.
+    #[inline]
+    pub fn key_count(mut self, key_count: u8) -> Self {
+        self.key_count = Some(key_count.clamp(1, MAX_COLUMNS as u8));
+
+        self
+    }
+
+    /// Override the constants used in the difficulty calculation.
+    ///
+    /// Only useful for experimenting with difficulty adjustments, the defaults match osu!.
+    /// For gradual calculations, use
+    /// [`ManiaGradualDifficultyAttributes::with_params`](crate::mania::ManiaGradualDifficultyAttributes::with_params).
+    #[inline]
+    pub fn params(mut self, params: ManiaDifficultyParams) -> Self {
+        self.params = params;
+
+        self
+    }
+
+    /// Ignore all hit objects that start before the given time in milliseconds.
+    ///
+    /// Unlike [`passed_objects`](Self::passed_objects), the remaining objects are
+    /// treated as if they were the whole map.
+    #[inline]
+    pub fn start_time(mut self, start_time: f64) -> Self {
+        self.start_time = Some(start_time);
+
+        self
+    }
+
+    /// Ignore all hit objects that start at or after the given time in milliseconds.
+    #[inline]
+    pub fn end_time(mut self, end_time: f64) -> Self {
+        self.end_time = Some(end_time);
+
+        self
+    }
+
+    /// Only consider the hit objects whose index is within `[start, end)`.
+    ///
+    /// Just like for [`start_time`](Self::start_time), the remaining objects are
+    /// treated as if they were the whole map. If a time range is specified too,
+    /// only objects within both ranges are considered.
+    #[inline]
+    pub fn object_range(mut self, start: usize, end: usize) -> Self {
+        self.object_range = Some(start..end);
+
+        self
+    }
+
+    /// Play the map `repeats` times in a row, e.g. for endurance challenges.
+    ///
+    /// The hit objects are virtually repeated, each repetition starting one beat after
+    /// the previous one ended, so length-dependent values like the max combo and
+    /// the strain accumulation reflect the longer map. Values below 2 leave the map as is
+    /// and values above [`Beatmap::MAX_REPEATS`] are capped.
+    #[inline]
+    pub fn repeat(mut self, repeats: usize) -> Self {
+        self.repeats = repeats;
+
+        self
+    }
+
+    /// Whether the max combo should be counted like in osu!lazer
+    /// i.e. hold notes award combo only for their head and tail.
+    ///
+    /// Defaults to `false` i.e. stable's combo which also
+    /// awards combo every 100ms while holding.
+    #[inline]
+    pub fn lazer(mut self, lazer: bool) -> Self {
+        self.lazer = lazer;
+
+        self
+    }
+
+    /// Shuffle the columns like the Random mod, using the given seed.
+    ///
+    /// The same seed always results in the same column order
+    /// but the order does not match the one of osu! itself.
+    #[inline]
+    pub fn random_seed(mut self, seed: u64) -> Self {
+        self.random_seed = Some(seed);
+
+        self
+    }
+
+    /// Whether the hit window should be adjusted by the clock rate.
+    ///
+    /// Defaults to `true`. If set to `false`, the clock rate still applies to the timing
+    /// of hit objects but the hit window is calculated as if no rate was applied.
+    /// Useful to isolate the effect of the clock rate on the difficulty.
+    #[inline]
+    pub fn rate_adjusted_hit_window(mut self, rate_adjusted_hit_window: bool) -> Self {
+        self.rate_adjusted_hit_window = rate_adjusted_hit_window;
+
+        self
+    }
+
     /// Calculate all difficulty related values, including stars.
     #[inline]
     pub fn calculate(self) -> ManiaDifficultyAttributes {
-        let is_convert = self.is_convert || matches!(self.map, Cow::Owned(_));
+        let map = self.map.convert_to_mania_with_mods(self.mods);
+        let is_convert = self.is_convert || map.is_convert;
 
-        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let clock_rate = if self.rate_adjusted_hit_window {
+            self.clock_rate.unwrap_or_else(|| self.mods.clock_rate())
+        } else {
+            1.0
+        };
 
-        let BeatmapHitWindows { od: hit_window, .. } = self
-            .map
+        let BeatmapHitWindows { od: hit_window, .. } = map
             .attributes()
             .mods(self.mods)
             .converted(is_convert)
             .clock_rate(clock_rate)
             .hit_windows();
 
-        let ManiaResult { strain, max_combo } = calculate_result(self);
+        let star_scaling_factor = self.params.star_scaling_factor;
+        let ManiaResult { strain, max_combo } = calculate_result(&map, self);
 
         ManiaDifficultyAttributes {
-            stars: strain.difficulty_value() * STAR_SCALING_FACTOR,
+            stars: strain.difficulty_value() * star_scaling_factor,
             hit_window,
             max_combo,
         }
@@ -134,13 +263,40 @@ impl<'map> ManiaStars<'map> {
     /// Suitable to plot the difficulty of a map over time.
     #[inline]
     pub fn strains(self) -> ManiaStrains {
-        let ManiaResult { strain, .. } = calculate_result(self);
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let map = self.map.convert_to_mania_with_mods(self.mods);
+        let ManiaResult { strain, .. } = calculate_result(&map, self);
+
+        let start_time = if strain.strain_peaks.is_empty() {
+            0.0
+        } else {
+            crate::util::first_section_start(
+                skills::StrainSkill::curr_section_end(&strain),
+                strain.strain_peaks.len(),
+                SECTION_LEN,
+                clock_rate,
+            )
+        };
 
         ManiaStrains {
             section_len: SECTION_LEN,
+            start_time,
+            clock_rate,
             strains: strain.strain_peaks,
         }
     }
+
+    /// Calculate the individual strain of each column.
+    ///
+    /// The values are the columns' strain at the time of their last note
+    /// after processing all hit objects, e.g. to compare the load on both hands.
+    #[inline]
+    pub fn column_strains(self) -> Vec<f64> {
+        let map = self.map.convert_to_mania_with_mods(self.mods);
+        let ManiaResult { strain, .. } = calculate_result(&map, self);
+
+        strain.individual_strains().to_vec()
+    }
 }
 
 /// The result of calculating the strains on a osu!taiko map.
@@ -149,34 +305,154 @@ impl<'map> ManiaStars<'map> {
 pub struct ManiaStrains {
     /// Time in ms inbetween two strains.
     pub section_len: f64,
+    /// Start of the first strain section in milliseconds in terms of the map's timestamps.
+    ///
+    /// The section of the `n`-th strain peak starts at
+    /// `start_time + n * section_len_with_clock_rate(clock_rate)`, which is also the time
+    /// that [`peak`](Self::peak) returns alongside the peak.
+    pub start_time: f64,
+    /// The clock rate that was used to calculate the strains.
+    pub clock_rate: f64,
     /// Strain peaks of the strain skill.
     pub strains: Vec<f64>,
 }
 
 impl ManiaStrains {
+    /// Time in ms inbetween two strains in terms of the map's timestamps,
+    /// e.g. 480ms instead of 400ms for a clock rate of 1.2.
+    #[inline]
+    pub fn section_len_with_clock_rate(&self, clock_rate: f64) -> f64 {
+        crate::util::section_len_with_clock_rate(self.section_len, clock_rate)
+    }
+
     /// Returns the number of strain peaks per skill.
     #[inline]
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.strains.len()
     }
+
+    /// Summary statistics of the strain peaks.
+    #[inline]
+    pub fn summary(&self) -> StrainSummary {
+        StrainSummary::new(&self.strains)
+    }
+
+    /// The highest strain peak as `(time, value)`, or `None` if there are no peaks.
+    #[inline]
+    pub fn peak(&self) -> Option<(f64, f64)> {
+        crate::util::strain_peak(
+            &self.strains,
+            self.start_time,
+            self.section_len_with_clock_rate(self.clock_rate),
+        )
+    }
+
+    /// The mean of all strain peaks.
+    ///
+    /// Just like for the difficulty calculation, sections with 0 strain
+    /// such as breaks are not considered.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.summary().mean
+    }
+
+    /// The population variance of all non-zero strain peaks.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        self.summary().variance
+    }
+
+    /// The standard deviation of all non-zero strain peaks.
+    #[inline]
+    pub fn std_dev(&self) -> f64 {
+        self.summary().std_dev()
+    }
+
+    /// The standard deviation of the strain peaks relative to their mean.
+    ///
+    /// Close to 0 for maps with consistent difficulty and larger
+    /// the more the difficulty is focused on spikes.
+    #[inline]
+    pub fn spikiness(&self) -> f64 {
+        self.summary().spikiness()
+    }
+}
+
+/// Constants of the osu!mania difficulty calculation that can be adjusted through
+/// [`ManiaStars::params`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ManiaDifficultyParams {
+    /// Multiplier from the strain's difficulty value to the star rating.
+    pub star_scaling_factor: f64,
+}
+
+impl Default for ManiaDifficultyParams {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            star_scaling_factor: STAR_SCALING_FACTOR,
+        }
+    }
 }
 
-fn calculate_result(params: ManiaStars<'_>) -> ManiaResult {
+/// The amount of columns of a mania map based on its circle size.
+///
+/// Clamped to `1..=18` so that malformed circle sizes can't lead to huge per-column state.
+// Not using `clamp` so that a NaN circle size still results in one column
+#[allow(clippy::manual_clamp)]
+pub(crate) fn total_columns(map: &Beatmap) -> f32 {
+    map.cs.round_even().max(1.0).min(MAX_COLUMNS)
+}
+
+/// Fisher-Yates shuffle of the column indices, driven by a splitmix64 generator.
+fn shuffled_columns(total_columns: usize, mut seed: u64) -> Vec<usize> {
+    let mut next = || {
+        seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        z ^ (z >> 31)
+    };
+
+    let mut columns: Vec<usize> = (0..total_columns).collect();
+
+    for i in (1..total_columns).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        columns.swap(i, j);
+    }
+
+    columns
+}
+
+fn calculate_result(map: &Beatmap, params: ManiaStars<'_>) -> ManiaResult {
     let ManiaStars {
-        map,
+        map: _,
         mods,
         passed_objects,
         clock_rate,
         is_convert: _,
+        key_count,
+        params: _,
+        start_time,
+        end_time,
+        object_range,
+        repeats,
+        lazer,
+        random_seed,
+        rate_adjusted_hit_window: _,
     } = params;
 
+    let map = map.trim(start_time, end_time, object_range);
+    let map = map.repeat(repeats);
     let take = passed_objects.unwrap_or(map.hit_objects.len());
-    let total_columns = map.cs.round_even().max(1.0);
+    let total_columns = key_count.map_or_else(|| total_columns(map.as_ref()), f32::from);
 
     let clock_rate = clock_rate.unwrap_or_else(|| mods.clock_rate());
     let mut strain = Strain::new(total_columns as usize);
-    let mut params = ObjectParameters::new(map.as_ref());
+    let mut params = ObjectParameters::new(map.as_ref(), mods, lazer);
+    params.column_order = random_seed.map(|seed| shuffled_columns(total_columns as usize, seed));
     let mut hit_objects = map.hit_objects.iter().take(take);
 
     let first = match hit_objects.next() {
@@ -216,7 +492,15 @@ struct ManiaResult {
 }
 
 /// The result of a difficulty calculation on an osu!mania map.
+///
+/// The struct is `#[non_exhaustive]` so it can't be created through a struct
+/// literal outside of this crate. Use [`Default`] instead and set the fields manually.
+///
+/// Note that the [`Default`] attributes are all zero, just like the attributes
+/// of a map without hit objects. Use [`is_empty`](Self::is_empty) to tell them
+/// apart from the attributes of an actual map.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
 pub struct ManiaDifficultyAttributes {
     /// The final star rating.
     pub stars: f64,
@@ -232,10 +516,21 @@ impl ManiaDifficultyAttributes {
     pub fn max_combo(&self) -> usize {
         self.max_combo
     }
+
+    /// Whether no hit object was considered, e.g. because the attributes
+    /// are [`Default`] or the map has no (passed) notes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.max_combo == 0
+    }
 }
 
 /// The result of a performance calculation on an osu!mania map.
+///
+/// The struct is `#[non_exhaustive]` so it can't be created through a struct
+/// literal outside of this crate. Use [`new`](Self::new) or [`Default`] instead.
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
 pub struct ManiaPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation.
     pub difficulty: ManiaDifficultyAttributes,
@@ -246,6 +541,18 @@ pub struct ManiaPerformanceAttributes {
 }
 
 impl ManiaPerformanceAttributes {
+    /// Create new performance attributes with the given pp value.
+    ///
+    /// All pp portions are initialized with zero.
+    #[inline]
+    pub fn new(difficulty: ManiaDifficultyAttributes, pp: f64) -> Self {
+        Self {
+            difficulty,
+            pp,
+            ..Default::default()
+        }
+    }
+
     /// Return the star value.
     #[inline]
     pub fn stars(&self) -> f64 {
@@ -280,14 +587,57 @@ impl<'map> From<OsuStars<'map>> for ManiaStars<'map> {
             mods,
             passed_objects,
             clock_rate,
+            start_time,
+            end_time,
+            object_range,
+            repeats,
+            ignore_spinners: _,
+            precision: _,
+            params: _,
+            rate_adjusted_hit_window,
         } = osu;
 
         Self {
-            map: map.convert_mode(GameMode::Mania),
+            map,
             mods,
             passed_objects,
             clock_rate,
             is_convert: true,
+            key_count: None,
+            params: ManiaDifficultyParams::default(),
+            start_time,
+            end_time,
+            object_range,
+            repeats,
+            lazer: false,
+            random_seed: None,
+            rate_adjusted_hit_window,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameMode;
+
+    #[test]
+    fn total_columns_clamped() {
+        let mut map = Beatmap {
+            mode: GameMode::Mania,
+            cs: 50.0,
+            ..Default::default()
+        };
+
+        assert_eq!(total_columns(&map), MAX_COLUMNS);
+
+        map.cs = -3.0;
+        assert_eq!(total_columns(&map), 1.0);
+
+        map.cs = f32::NAN;
+        assert_eq!(total_columns(&map), 1.0);
+
+        map.cs = 7.0;
+        assert_eq!(total_columns(&map), 7.0);
+    }
+}
@@ -1,8 +1,6 @@
-use std::borrow::Cow;
-
 use super::{ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaScoreState, ManiaStars};
 use crate::{
-    Beatmap, DifficultyAttributes, GameMode, HitResultPriority, Mods, OsuPP, PerformanceAttributes,
+    Accuracy, Beatmap, DifficultyAttributes, HitResultPriority, Mods, OsuPP, PerformanceAttributes,
 };
 
 /// Performance calculator on osu!mania maps.
@@ -10,7 +8,7 @@ use crate::{
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{ManiaPP, Beatmap};
+/// use akatsuki_pp::{Accuracy, ManiaPP, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -20,7 +18,7 @@ use crate::{
 /// let pp_result = ManiaPP::new(&map)
 ///     .mods(64) // DT
 ///     .n_misses(1)
-///     .accuracy(98.5)
+///     .accuracy(Accuracy::from_percent(98.5).unwrap())
 ///     .calculate();
 ///
 /// println!("PP: {} | Stars: {}", pp_result.pp(), pp_result.stars());
@@ -41,11 +39,11 @@ use crate::{
 #[derive(Clone, Debug)]
 #[allow(clippy::upper_case_acronyms)]
 pub struct ManiaPP<'map> {
-    map: Cow<'map, Beatmap>,
-    attributes: Option<ManiaDifficultyAttributes>,
-    mods: u32,
+    pub(crate) map: &'map Beatmap,
+    pub(crate) attributes: Option<ManiaDifficultyAttributes>,
+    pub(crate) mods: u32,
     passed_objects: Option<usize>,
-    clock_rate: Option<f64>,
+    pub(crate) clock_rate: Option<f64>,
 
     pub(crate) n320: Option<usize>,
     pub(crate) n300: Option<usize>,
@@ -56,6 +54,8 @@ pub struct ManiaPP<'map> {
 
     acc: Option<f64>,
     hitresult_priority: Option<HitResultPriority>,
+    pub(crate) lazer: bool,
+    score_v2: Option<bool>,
 }
 
 impl<'map> ManiaPP<'map> {
@@ -63,7 +63,7 @@ impl<'map> ManiaPP<'map> {
     #[inline]
     pub fn new(map: &'map Beatmap) -> Self {
         Self {
-            map: map.convert_mode(GameMode::Mania),
+            map,
             attributes: None,
             mods: 0,
             passed_objects: None,
@@ -76,6 +76,8 @@ impl<'map> ManiaPP<'map> {
             n_misses: None,
             acc: None,
             hitresult_priority: None,
+            lazer: false,
+            score_v2: None,
         }
     }
 
@@ -93,6 +95,8 @@ impl<'map> ManiaPP<'map> {
 
     /// Specify mods through their bit values.
     ///
+    /// Key mods force the amount of columns of converted maps.
+    ///
     /// See [https://github.com/ppy/osu-api/wiki#mods](https://github.com/ppy/osu-api/wiki#mods)
     #[inline]
     pub fn mods(mut self, mods: u32) -> Self {
@@ -101,6 +105,21 @@ impl<'map> ManiaPP<'map> {
         self
     }
 
+    /// Strip all rate-changing and difficulty-altering mods,
+    /// see [`Mods::without_difficulty_mods`].
+    ///
+    /// The score's accuracy, combo, and hit results stay the same so the
+    /// result can be compared to the actual play, e.g. "if played nomod".
+    /// Previously provided attributes and clock rate are discarded.
+    #[inline]
+    pub fn nomod(mut self) -> Self {
+        self.mods = self.mods.without_difficulty_mods();
+        self.attributes = None;
+        self.clock_rate = None;
+
+        self
+    }
+
     /// Amount of passed objects for partial plays, e.g. a fail.
     ///
     /// If you want to calculate the performance after every few objects, instead of
@@ -123,11 +142,31 @@ impl<'map> ManiaPP<'map> {
         self
     }
 
-    /// Specify the accuracy of a play between `0.0` and `100.0`.
+    /// Specify the accuracy of a play, see [`Accuracy`].
     /// This will be used to generate matching hitresults.
     #[inline]
-    pub fn accuracy(mut self, acc: f64) -> Self {
-        self.acc = Some(acc / 100.0);
+    pub fn accuracy(mut self, acc: impl Into<Accuracy>) -> Self {
+        self.acc = Some(acc.into().fraction());
+
+        self
+    }
+
+    /// Whether the max combo should be counted like in osu!lazer,
+    /// see [`ManiaStars::lazer`].
+    #[inline]
+    pub fn lazer(mut self, lazer: bool) -> Self {
+        self.lazer = lazer;
+
+        self
+    }
+
+    /// Whether the accuracy given through [`accuracy`](Self::accuracy) is weighted like
+    /// ScoreV2 i.e. a 320 is worth more than a 300.
+    ///
+    /// If not specified, it's deduced from the ScoreV2 mod ([`Mods::V2`]).
+    #[inline]
+    pub fn score_v2(mut self, score_v2: bool) -> Self {
+        self.score_v2 = Some(score_v2);
 
         self
     }
@@ -212,12 +251,32 @@ impl<'map> ManiaPP<'map> {
         self
     }
 
+    /// Calculate the performance of a full combo with 100% accuracy, i.e. an SS,
+    /// for the same map, mods, clock rate, and amount of passed objects.
+    ///
+    /// Specified accuracy, combo, and hitresults are ignored.
+    pub fn max_pp(&self) -> ManiaPerformanceAttributes {
+        let mut calculator = self.clone();
+        calculator.acc = None;
+        calculator.n320 = None;
+        calculator.n300 = None;
+        calculator.n200 = None;
+        calculator.n100 = None;
+        calculator.n50 = None;
+        calculator.n_misses = None;
+
+        calculator.calculate()
+    }
+
     /// Calculate all performance related values, including pp and stars.
     pub fn calculate(self) -> ManiaPerformanceAttributes {
+        let map = self.map.convert_to_mania_with_mods(self.mods);
+
         let attrs = self.attributes.unwrap_or_else(|| {
-            let mut calculator = ManiaStars::new(self.map.as_ref())
+            let mut calculator = ManiaStars::new(map.as_ref())
                 .mods(self.mods)
-                .is_convert(matches!(self.map, Cow::Owned(_)));
+                .is_convert(map.is_convert)
+                .lazer(self.lazer);
 
             if let Some(passed_objects) = self.passed_objects {
                 calculator = calculator.passed_objects(passed_objects);
@@ -233,14 +292,43 @@ impl<'map> ManiaPP<'map> {
         let inner = ManiaPpInner {
             attrs,
             mods: self.mods,
-            state: self.generate_hitresults(),
+            state: self.generate_hitresults(map.hit_objects.len()),
         };
 
         inner.calculate()
     }
 
-    fn generate_hitresults(&self) -> ManiaScoreState {
-        let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
+    fn generate_hitresults(&self, n_map_objects: usize) -> ManiaScoreState {
+        let score_v2 = self.score_v2.unwrap_or_else(|| self.mods.v2());
+        let n_objects = self.passed_objects.unwrap_or(n_map_objects);
+
+        match self.acc {
+            Some(acc) if score_v2 => {
+                let acc = self.v1_accuracy(acc, n_objects);
+
+                self.generate_hitresults_with_acc(Some(acc), n_objects)
+            }
+            acc => self.generate_hitresults_with_acc(acc, n_objects),
+        }
+    }
+
+    /// Convert a ScoreV2 accuracy into the ScoreV1 accuracy of the same hitresults.
+    ///
+    /// ScoreV2 weighs 320s with 305 and the total with 305 instead of 300 so the
+    /// difference depends on the amount of 320s which is estimated through ScoreV1.
+    fn v1_accuracy(&self, acc: f64, n_objects: usize) -> f64 {
+        let state = self.generate_hitresults_with_acc(Some(acc), n_objects);
+        let total_hits = state.total_hits() as f64;
+
+        if total_hits <= 0.0 {
+            return acc;
+        }
+
+        ((acc * 305.0 * total_hits - 5.0 * state.n320 as f64) / (300.0 * total_hits))
+            .clamp(0.0, 1.0)
+    }
+
+    fn generate_hitresults_with_acc(&self, acc: Option<f64>, n_objects: usize) -> ManiaScoreState {
         let priority = self.hitresult_priority.unwrap_or_default();
 
         let mut n320 = self.n320.unwrap_or(0);
@@ -250,7 +338,7 @@ impl<'map> ManiaPP<'map> {
         let mut n50 = self.n50.unwrap_or(0);
         let n_misses = self.n_misses.unwrap_or(0);
 
-        if let Some(acc) = self.acc {
+        if let Some(acc) = acc {
             let target_total = (acc * (n_objects * 6) as f64).round() as usize;
 
             match (self.n320, self.n300, self.n200, self.n100, self.n50) {
@@ -274,7 +362,8 @@ impl<'map> ManiaPP<'map> {
                 }
                 (Some(_), _, _, None, None) | (_, Some(_), _, None, None) => {
                     let n3x0 = n320 + n300;
-                    let delta = (target_total - n_objects.saturating_sub(n_misses))
+                    let delta = target_total
+                        .saturating_sub(n_objects.saturating_sub(n_misses))
                         .saturating_sub(n3x0 * 5 + n200 * 3);
 
                     n100 = delta % 5;
@@ -294,7 +383,8 @@ impl<'map> ManiaPP<'map> {
                 }
                 (Some(_), _, None, Some(_), None) | (_, Some(_), None, Some(_), None) => {
                     let n3x0 = n320 + n300;
-                    let delta = (target_total - n_objects.saturating_sub(n_misses))
+                    let delta = target_total
+                        .saturating_sub(n_objects.saturating_sub(n_misses))
                         .saturating_sub(n3x0 * 5 + n100);
 
                     n200 = delta / 3;
@@ -323,8 +413,9 @@ impl<'map> ManiaPP<'map> {
                     }
                 }
                 (None, None, None, Some(_), Some(_)) => {
-                    let delta =
-                        (target_total - n_objects.saturating_sub(n_misses)).saturating_sub(n100);
+                    let delta = target_total
+                        .saturating_sub(n_objects.saturating_sub(n_misses))
+                        .saturating_sub(n100);
 
                     match priority {
                         HitResultPriority::BestCase => n320 = delta / 5,
@@ -354,7 +445,8 @@ impl<'map> ManiaPP<'map> {
                     }
                 }
                 (None, None, Some(_), None, None) => {
-                    let delta = (target_total - n_objects.saturating_sub(n_misses))
+                    let delta = target_total
+                        .saturating_sub(n_objects.saturating_sub(n_misses))
                         .saturating_sub(n200 * 3);
 
                     match priority {
@@ -387,7 +479,8 @@ impl<'map> ManiaPP<'map> {
                     }
                 }
                 (None, None, _, Some(_), None) => {
-                    let delta = (target_total - n_objects.saturating_sub(n_misses))
+                    let delta = target_total
+                        .saturating_sub(n_objects.saturating_sub(n_misses))
                         .saturating_sub(n200 * 3 + n100);
 
                     match priority {
@@ -398,8 +491,9 @@ impl<'map> ManiaPP<'map> {
                     n50 = n_objects.saturating_sub(n320 + n300 + n200 + n100 + n_misses);
                 }
                 (None, None, _, None, Some(_)) => {
-                    let delta =
-                        target_total - n_objects.saturating_sub(n_misses).saturating_sub(n200 * 3);
+                    let delta = target_total
+                        .saturating_sub(n_objects.saturating_sub(n_misses))
+                        .saturating_sub(n200 * 3);
 
                     match priority {
                         HitResultPriority::BestCase => n320 = delta / 5,
@@ -430,7 +524,7 @@ impl<'map> ManiaPP<'map> {
                     }
                 }
                 (None, None, None, None, None) => {
-                    let delta = target_total - n_objects.saturating_sub(n_misses);
+                    let delta = target_total.saturating_sub(n_objects.saturating_sub(n_misses));
 
                     match priority {
                         HitResultPriority::BestCase => n320 = delta / 5,
@@ -576,13 +670,20 @@ impl<'map> From<OsuPP<'map>> for ManiaPP<'map> {
             n100,
             n50,
             n_misses,
+            n_slider_breaks: _,
+            slider_end_hits: _,
+            slider_tick_hits: _,
             passed_objects,
             clock_rate,
             hitresult_priority,
+            unstable_rate: _,
+            precision: _,
+            rate_adjusted_hit_window: _,
+            lazer,
         } = osu;
 
         Self {
-            map: map.convert_mode(GameMode::Mania),
+            map,
             attributes: None,
             mods,
             passed_objects,
@@ -595,6 +696,8 @@ impl<'map> From<OsuPP<'map>> for ManiaPP<'map> {
             n_misses,
             acc,
             hitresult_priority,
+            lazer,
+            score_v2: None,
         }
     }
 }
@@ -666,12 +769,12 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n320(2600)
             .n200(400)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2600,
@@ -698,13 +801,13 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n320(2250)
             .n300(500)
             .n200(100)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2250,
@@ -731,13 +834,13 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n320(2000)
             .n300(500)
             .n100(100)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2000,
@@ -764,13 +867,13 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n320(2700)
             .n100(200)
             .n50(10)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2700,
@@ -797,12 +900,12 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n320(2000)
             .n50(50)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::WorstCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2000,
@@ -829,12 +932,12 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n100(200)
             .n50(50)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2546,
@@ -861,11 +964,11 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n200(500)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2503,
@@ -892,12 +995,12 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n200(500)
             .n100(200)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2509,
@@ -924,11 +1027,11 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n50(200)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2804,
@@ -955,13 +1058,13 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n200(500)
             .n100(300)
             .n50(100)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2336,
@@ -988,10 +1091,10 @@ mod tests {
 
         let state = ManiaPP::new(&map)
             .attributes(attrs)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .n_misses(2)
             .hitresult_priority(HitResultPriority::WorstCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 0,
@@ -1021,7 +1124,7 @@ mod tests {
             .n320(2000)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 2000,
@@ -1046,7 +1149,7 @@ mod tests {
             .n50(100)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::WorstCase)
-            .generate_hitresults();
+            .generate_hitresults(map.hit_objects.len());
 
         let expected = ManiaScoreState {
             n320: 0,
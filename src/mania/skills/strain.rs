@@ -1,4 +1,4 @@
-use crate::mania::difficulty_object::ManiaDifficultyObject;
+use crate::{mania::difficulty_object::ManiaDifficultyObject, util::SortedPeaks};
 
 use super::{previous, Skill, StrainDecaySkill, StrainSkill};
 
@@ -16,6 +16,7 @@ pub(crate) struct Strain {
     curr_section_end: f64,
 
     pub(crate) strain_peaks: Vec<f64>,
+    sorted_peaks: SortedPeaks,
 }
 
 impl Strain {
@@ -34,9 +35,14 @@ impl Strain {
             curr_section_peak: 0.0,
             curr_section_end: 0.0,
             strain_peaks: Vec::new(),
+            sorted_peaks: SortedPeaks::new(Self::DECAY_WEIGHT),
         }
     }
 
+    pub(crate) fn individual_strains(&self) -> &[f64] {
+        &self.individual_strains
+    }
+
     fn apply_decay(value: f64, delta_time: f64, decay_base: f64) -> f64 {
         value * decay_base.powf(delta_time / 1000.0)
     }
@@ -49,7 +55,7 @@ impl Skill for Strain {
     }
 
     #[inline]
-    fn difficulty_value(self) -> f64 {
+    fn difficulty_value(&self) -> f64 {
         <Self as StrainSkill>::difficulty_value(self)
     }
 }
@@ -82,6 +88,16 @@ impl StrainSkill for Strain {
         &mut self.strain_peaks
     }
 
+    #[inline]
+    fn sorted_peaks(&self) -> &SortedPeaks {
+        &self.sorted_peaks
+    }
+
+    #[inline]
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks {
+        &mut self.sorted_peaks
+    }
+
     #[inline]
     fn strain_value_at(&mut self, curr: &ManiaDifficultyObject) -> f64 {
         <Self as StrainDecaySkill>::strain_value_at(self, curr)
@@ -1,10 +1,11 @@
-use std::{cmp::Ordering, mem};
-
-use crate::mania::{difficulty_object::ManiaDifficultyObject, SECTION_LEN};
+use crate::{
+    mania::{difficulty_object::ManiaDifficultyObject, SECTION_LEN},
+    util::SortedPeaks,
+};
 
 pub(crate) trait Skill {
     fn process(&mut self, curr: &ManiaDifficultyObject, diff_objects: &[ManiaDifficultyObject]);
-    fn difficulty_value(self) -> f64;
+    fn difficulty_value(&self) -> f64;
 }
 
 pub(crate) trait StrainSkill: Sized + Skill {
@@ -18,6 +19,9 @@ pub(crate) trait StrainSkill: Sized + Skill {
 
     fn strain_peaks_mut(&mut self) -> &mut Vec<f64>;
 
+    fn sorted_peaks(&self) -> &SortedPeaks;
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks;
+
     fn strain_value_at(&mut self, curr: &ManiaDifficultyObject) -> f64;
 
     fn process(&mut self, curr: &ManiaDifficultyObject, diff_objects: &[ManiaDifficultyObject]) {
@@ -38,6 +42,7 @@ pub(crate) trait StrainSkill: Sized + Skill {
     fn save_curr_peak(&mut self) {
         let curr_section_peak = self.curr_section_peak();
         self.strain_peaks_mut().push(curr_section_peak);
+        self.sorted_peaks_mut().push(curr_section_peak);
     }
 
     fn start_new_section_from(
@@ -56,31 +61,11 @@ pub(crate) trait StrainSkill: Sized + Skill {
         diff_objects: &[ManiaDifficultyObject],
     ) -> f64;
 
-    fn get_curr_strain_peaks(mut self) -> Vec<f64> {
-        let mut peaks = mem::take(self.strain_peaks_mut());
-        peaks.push(self.curr_section_peak());
-
-        peaks
-    }
-
-    fn difficulty_value(self) -> f64 {
-        let mut difficulty = 0.0;
-        let mut weight = 1.0;
-
-        // * Sections with 0 strain are excluded to avoid worst-case time complexity of the following sort (e.g. /b/2351871).
-        // * These sections will not contribute to the difficulty.
-        let mut peaks = self.get_curr_strain_peaks();
-        peaks.retain(|&peak| peak > 0.0);
-        peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
-
+    fn difficulty_value(&self) -> f64 {
         // * Difficulty is the weighted sum of the highest strains from every section.
         // * We're sorting from highest to lowest strain.
-        for strain in peaks {
-            difficulty += strain * weight;
-            weight *= Self::DECAY_WEIGHT;
-        }
-
-        difficulty
+        self.sorted_peaks()
+            .weighted_sum(&[self.curr_section_peak()], 0)
     }
 }
 
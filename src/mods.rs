@@ -1,3 +1,56 @@
+use crate::{sealed::Sealed, GameMode};
+
+pub(crate) const SD: u32 = 1 << 5;
+pub(crate) const PF: u32 = 1 << 14;
+
+/// Key mod bits with their amount of columns, ordered by the amount.
+const KEY_MODS: [(u32, u8); 9] = [
+    (1 << 26, 1),
+    (1 << 28, 2),
+    (1 << 27, 3),
+    (1 << 15, 4),
+    (1 << 16, 5),
+    (1 << 17, 6),
+    (1 << 18, 7),
+    (1 << 19, 8),
+    (1 << 24, 9),
+];
+
+/// Legacy mod bits with their acronym and full name.
+pub(crate) const MODS: [(u32, &str, &str); 31] = [
+    (1 << 0, "NF", "No Fail"),
+    (1 << 1, "EZ", "Easy"),
+    (1 << 2, "TD", "Touch Device"),
+    (1 << 3, "HD", "Hidden"),
+    (1 << 4, "HR", "Hard Rock"),
+    (SD, "SD", "Sudden Death"),
+    (1 << 6, "DT", "Double Time"),
+    (1 << 7, "RX", "Relax"),
+    (1 << 8, "HT", "Half Time"),
+    (1 << 9, "NC", "Nightcore"),
+    (1 << 10, "FL", "Flashlight"),
+    (1 << 11, "AT", "Autoplay"),
+    (1 << 12, "SO", "Spun Out"),
+    (1 << 13, "AP", "Autopilot"),
+    (PF, "PF", "Perfect"),
+    (1 << 15, "4K", "4 Keys"),
+    (1 << 16, "5K", "5 Keys"),
+    (1 << 17, "6K", "6 Keys"),
+    (1 << 18, "7K", "7 Keys"),
+    (1 << 19, "8K", "8 Keys"),
+    (1 << 20, "FI", "Fade In"),
+    (1 << 21, "RD", "Random"),
+    (1 << 22, "CN", "Cinema"),
+    (1 << 23, "TP", "Target Practice"),
+    (1 << 24, "9K", "9 Keys"),
+    (1 << 25, "DS", "Dual Stages"),
+    (1 << 26, "1K", "1 Key"),
+    (1 << 27, "3K", "3 Keys"),
+    (1 << 28, "2K", "2 Keys"),
+    (u32::V2, "SV2", "Score V2"),
+    (u32::MR, "MR", "Mirror"),
+];
+
 macro_rules! impl_mods {
     ($func_name:ident, $const_name:ident) => {
         #[inline]
@@ -8,8 +61,10 @@ macro_rules! impl_mods {
 }
 
 /// Abstract type to define mods.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
 #[allow(missing_docs)]
-pub trait Mods: Copy {
+pub trait Mods: Copy + Sealed {
     const NF: u32 = 1 << 0;
     const EZ: u32 = 1 << 1;
     const TD: u32 = 1 << 2;
@@ -18,9 +73,12 @@ pub trait Mods: Copy {
     const DT: u32 = 1 << 6;
     const RX: u32 = 1 << 7;
     const HT: u32 = 1 << 8;
+    const NC: u32 = 1 << 9;
     const FL: u32 = 1 << 10;
     const SO: u32 = 1 << 12;
     const AP: u32 = 1 << 13;
+    const V2: u32 = 1 << 29;
+    const MR: u32 = 1 << 30;
 
     /// If the clock rate is affected by the mods.
     fn change_speed(self) -> bool;
@@ -28,8 +86,84 @@ pub trait Mods: Copy {
     fn change_map(self) -> bool;
     /// The clock rate with the mods.
     fn clock_rate(self) -> f64;
+    /// The clock rate with the mods but with a custom speed change for DT or HT.
+    ///
+    /// The speed change is clamped to lazer's range i.e. `1.01..=2.0` for DT
+    /// and `0.5..=0.99` for HT. Without DT or HT the speed change is ignored.
+    ///
+    /// The result can be passed to the `clock_rate` method of calculators
+    /// while the mods themselves still include DT or HT.
+    fn speed_change(self, speed_change: f64) -> f64;
     /// Multiplier for beatmap attributes with respect to the mods.
     fn od_ar_hp_multiplier(self) -> f64;
+    /// The osu!stable score multiplier of the mods in the given mode, e.g. `0.5` for EZ.
+    ///
+    /// Mods that make a score unranked such as RX or AP are ignored.
+    /// Nothing in this crate calculates scores; this is meant for score-based
+    /// grading on the caller's side e.g. normalizing osu!mania scores across mods.
+    fn score_multiplier(self, mode: GameMode) -> f64 {
+        let mut multiplier = 1.0;
+
+        if self.nf() {
+            multiplier *= 0.5;
+        }
+
+        if self.ez() {
+            multiplier *= 0.5;
+        }
+
+        if self.ht() {
+            multiplier *= if let GameMode::Mania = mode { 0.5 } else { 0.3 };
+        }
+
+        // Difficulty increasing mods don't affect the score in osu!mania
+        if let GameMode::Mania = mode {
+            return multiplier;
+        }
+
+        if self.so() && mode == GameMode::Osu {
+            multiplier *= 0.9;
+        }
+
+        if self.hd() {
+            multiplier *= 1.06;
+        }
+
+        if self.hr() {
+            multiplier *= if let GameMode::Catch = mode {
+                1.12
+            } else {
+                1.06
+            };
+        }
+
+        if self.dt() {
+            multiplier *= if let GameMode::Catch = mode {
+                1.06
+            } else {
+                1.12
+            };
+        }
+
+        if self.fl() {
+            multiplier *= 1.12;
+        }
+
+        multiplier
+    }
+
+    /// The mods without any rate-changing or difficulty-altering mods,
+    /// i.e. without EZ, HR, DT, NC, HT, and FL.
+    fn without_difficulty_mods(self) -> Self;
+    /// The full names of all contained mods separated by commas, e.g. `"Hidden, Double Time"`.
+    ///
+    /// Mods that are implied by others, i.e. DT for NC and SD for PF, are omitted.
+    /// Returns an empty string if no mods are contained.
+    fn description(self) -> String;
+    /// The amount of columns forced by a key mod, i.e. 1K to 9K.
+    ///
+    /// If multiple key mods are contained, the lowest key count is used.
+    fn key_count(self) -> Option<u8>;
     fn nf(self) -> bool;
     fn ez(self) -> bool;
     fn td(self) -> bool;
@@ -41,6 +175,8 @@ pub trait Mods: Copy {
     fn fl(self) -> bool;
     fn so(self) -> bool;
     fn ap(self) -> bool;
+    fn v2(self) -> bool;
+    fn mr(self) -> bool;
 }
 
 impl Mods for u32 {
@@ -65,6 +201,17 @@ impl Mods for u32 {
         }
     }
 
+    #[inline]
+    fn speed_change(self, speed_change: f64) -> f64 {
+        if self & Self::DT > 0 {
+            speed_change.clamp(1.01, 2.0)
+        } else if self & Self::HT > 0 {
+            speed_change.clamp(0.5, 0.99)
+        } else {
+            1.0
+        }
+    }
+
     #[inline]
     fn od_ar_hp_multiplier(self) -> f64 {
         if self & Self::HR > 0 {
@@ -76,6 +223,46 @@ impl Mods for u32 {
         }
     }
 
+    #[inline]
+    fn without_difficulty_mods(self) -> Self {
+        self & !(Self::EZ | Self::HR | Self::DT | Self::NC | Self::HT | Self::FL)
+    }
+
+    fn key_count(self) -> Option<u8> {
+        KEY_MODS
+            .iter()
+            .find(|(bit, _)| self & bit > 0)
+            .map(|(_, key_count)| *key_count)
+    }
+
+    fn description(self) -> String {
+        let mut implied = 0;
+
+        if self & Self::NC > 0 {
+            implied |= Self::DT;
+        }
+
+        if self & PF > 0 {
+            implied |= SD;
+        }
+
+        let mut description = String::new();
+
+        for &(bit, _, name) in MODS.iter() {
+            if self & bit == 0 || implied & bit > 0 {
+                continue;
+            }
+
+            if !description.is_empty() {
+                description.push_str(", ");
+            }
+
+            description.push_str(name);
+        }
+
+        description
+    }
+
     impl_mods!(nf, NF);
     impl_mods!(ez, EZ);
     impl_mods!(td, TD);
@@ -87,4 +274,6 @@ impl Mods for u32 {
     impl_mods!(fl, FL);
     impl_mods!(so, SO);
     impl_mods!(ap, AP);
+    impl_mods!(v2, V2);
+    impl_mods!(mr, MR);
 }
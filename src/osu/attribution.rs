@@ -0,0 +1,77 @@
+use std::cmp::Ordering;
+
+use super::{calculate_skills, OsuStars, SECTION_LEN};
+
+/// The strain a hit object contributed to the difficulty of an osu!standard map,
+/// see [`OsuStars::attribution`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuObjectAttribution {
+    /// The index of the hit object in the map's hit objects.
+    pub idx: usize,
+    /// The start time of the hit object in milliseconds.
+    ///
+    /// With [`repeat`](OsuStars::repeat), this is the start time within the repetition.
+    pub start_time: f64,
+    /// The aim strain right after the hit object.
+    pub aim: f64,
+    /// The speed strain right after the hit object.
+    pub speed: f64,
+    /// The raw sum of the aim and speed strain which is used to sort the objects.
+    ///
+    /// The skills are neither weighted nor scaled like for the star rating
+    /// so the sum only serves as a rough ranking across both skills.
+    pub strain: f64,
+}
+
+impl OsuStars<'_> {
+    /// List the strain of each hit object, sorted from the highest to the lowest strain.
+    ///
+    /// Useful to find the objects that contributed the most to the difficulty and thus the pp.
+    /// The first considered object does not have a strain and is not listed. With
+    /// [`repeat`](OsuStars::repeat), each repetition of an object is listed separately.
+    pub fn attribution(self) -> Vec<OsuObjectAttribution> {
+        let range =
+            self.map
+                .trimmed_range(self.start_time, self.end_time, self.object_range.clone());
+
+        let map = self
+            .map
+            .trim(self.start_time, self.end_time, self.object_range.clone());
+
+        let map = map.repeat(self.repeats);
+        let ignore_spinners = self.ignore_spinners;
+
+        // Mirrors which objects are turned into difficulty objects
+        let objects: Vec<_> = map
+            .hit_objects
+            .iter()
+            .enumerate()
+            .take(self.passed_objects.unwrap_or(usize::MAX))
+            .filter(|(_, h)| !(ignore_spinners && h.is_spinner()))
+            .map(|(i, h)| (range.start + i % range.len(), h.start_time))
+            .collect();
+
+        let (skills, _) = calculate_skills(self, SECTION_LEN);
+
+        let mut attribution: Vec<_> = skills
+            .aim
+            .object_strains
+            .iter()
+            .zip(skills.speed.object_strains.iter())
+            .zip(objects.iter().skip(1))
+            .map(
+                |((&aim, &speed), &(idx, start_time))| OsuObjectAttribution {
+                    idx,
+                    start_time,
+                    aim,
+                    speed,
+                    strain: aim + speed,
+                },
+            )
+            .collect();
+
+        attribution.sort_by(|a, b| b.strain.partial_cmp(&a.strain).unwrap_or(Ordering::Equal));
+
+        attribution
+    }
+}
@@ -3,16 +3,17 @@ use std::{
     mem,
 };
 
-use crate::{curve::CurveBuffers, Beatmap, Mods};
+use crate::{curve::CurveBuffers, Beatmap, Mods, Precision};
 
 use super::{
+    adjust_ratings,
     difficulty_object::{Distances, OsuDifficultyObject},
-    old_stacking,
+    od_hit_window, old_stacking,
     osu_object::{ObjectParameters, OsuObject, OsuObjectKind},
     scaling_factor::ScalingFactor,
     skills::{Skill, Skills},
-    stacking, OsuDifficultyAttributes, DIFFICULTY_MULTIPLIER, FADE_IN_DURATION_MULTIPLIER,
-    PERFORMANCE_BASE_MULTIPLIER, PREEMPT_MIN,
+    stacking, OsuDifficultyAttributes, OsuDifficultyParams, DIFFICULTY_MULTIPLIER,
+    FADE_IN_DURATION_MULTIPLIER, PREEMPT_MIN,
 };
 
 /// Gradually calculate the difficulty attributes of an osu!standard map.
@@ -27,7 +28,7 @@ use super::{
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{Beatmap, osu::OsuGradualDifficultyAttributes};
+/// use akatsuki_pp::{Beatmap, osu::OsuGradualDifficultyAttributes};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -55,6 +56,8 @@ pub struct OsuGradualDifficultyAttributes {
     hit_objects: Vec<OsuObject>,
     diff_objects: Vec<OsuDifficultyObject<'static>>,
     skills: Skills,
+    pub(crate) params: OsuDifficultyParams,
+    pub(crate) precision: Precision,
 }
 
 impl Debug for OsuGradualDifficultyAttributes {
@@ -70,12 +73,32 @@ impl Debug for OsuGradualDifficultyAttributes {
 
 impl OsuGradualDifficultyAttributes {
     /// Create a new difficulty attributes iterator for osu!standard maps.
+    #[inline]
     pub fn new(map: &Beatmap, mods: u32) -> Self {
-        let clock_rate = mods.clock_rate();
-        let map_attrs = map.attributes().mods(mods).build();
+        Self::with_rate_adjusted_hit_window(map, mods, true)
+    }
+
+    /// Same as [`new`](Self::new) but specify whether the hit window should be
+    /// adjusted by the clock rate, see [`OsuStars::rate_adjusted_hit_window`](crate::OsuStars::rate_adjusted_hit_window).
+    #[inline]
+    pub fn with_rate_adjusted_hit_window(
+        map: &Beatmap,
+        mods: u32,
+        rate_adjusted_hit_window: bool,
+    ) -> Self {
+        Self::with_clock_rate(map, mods, mods.clock_rate(), rate_adjusted_hit_window)
+    }
+
+    pub(crate) fn with_clock_rate(
+        map: &Beatmap,
+        mods: u32,
+        clock_rate: f64,
+        rate_adjusted_hit_window: bool,
+    ) -> Self {
+        let map_attrs = map.attributes().mods(mods).clock_rate(clock_rate).build();
         let scaling_factor = ScalingFactor::new(map_attrs.cs);
         let hr = mods.hr();
-        let hit_window = 2.0 * map_attrs.hit_windows.od;
+        let (od, hit_window) = od_hit_window(map, mods, &map_attrs, rate_adjusted_hit_window);
         let time_preempt = (map_attrs.hit_windows.ar * clock_rate) as f32 as f64;
 
         // * Preempt time can go below 450ms. Normally, this is achieved via the DT mod
@@ -95,7 +118,9 @@ impl OsuGradualDifficultyAttributes {
         let mut attrs = OsuDifficultyAttributes {
             ar: map_attrs.ar,
             hp: map_attrs.hp,
-            od: map_attrs.od,
+            od,
+            preempt: time_preempt,
+            fade_in: time_fade_in,
             ..Default::default()
         };
 
@@ -125,6 +150,7 @@ impl OsuGradualDifficultyAttributes {
             old_stacking(&mut hit_objects, stack_threshold);
         }
 
+        #[allow(clippy::manual_inspect)]
         let mut hit_objects_iter = hit_objects.iter_mut().map(|h| {
             h.post_process(hr, &scaling_factor);
 
@@ -149,6 +175,8 @@ impl OsuGradualDifficultyAttributes {
                     hit_objects: Vec::new(),
                     diff_objects: Vec::new(),
                     skills,
+                    params: OsuDifficultyParams::default(),
+                    precision: Precision::Double,
                 }
             }
         };
@@ -192,9 +220,42 @@ impl OsuGradualDifficultyAttributes {
             diff_objects: extend_lifetime(diff_objects),
             hit_objects,
             skills,
+            params: OsuDifficultyParams::default(),
+            precision: Precision::Double,
         }
     }
 
+    /// Turn the iterator into one over the live star rating,
+    /// i.e. the star rating of the played portion of the map as shown during gameplay.
+    ///
+    /// Each item is the time in milliseconds from which on the star rating is shown
+    /// alongside the star rating. Just like osu!lazer's timed difficulty attributes, that
+    /// time is the end time of the latest hit object in terms of the map's timestamps.
+    /// Hence, contrary to [`OsuStars::passed_objects`](crate::OsuStars::passed_objects),
+    /// a slider or spinner only counts once it has been completed.
+    /// To get the live star rating at a given time, take the last item whose time
+    /// is not after that time.
+    ///
+    /// The first hit object does not yield a value so the `n`-th item holds
+    /// the star rating after `n + 2` hit objects.
+    #[inline]
+    pub fn live_stars(self) -> impl ExactSizeIterator<Item = (f64, f64)> {
+        let end_times: Vec<_> = self.diff_objects[self.idx..]
+            .iter()
+            .map(|h| h.base.end_time())
+            .collect();
+
+        end_times
+            .into_iter()
+            .zip(self)
+            .map(|(end_time, attrs)| (end_time, attrs.stars))
+    }
+
+    /// Start time of the next hit object to be processed, scaled by the clock rate.
+    pub(crate) fn next_start_time(&self) -> Option<f64> {
+        self.diff_objects.get(self.idx).map(|h| h.start_time)
+    }
+
     fn increment_combo(h: &OsuObject, attrs: &mut OsuDifficultyAttributes) {
         attrs.max_combo += 1;
 
@@ -229,20 +290,20 @@ impl Iterator for OsuGradualDifficultyAttributes {
         Self::increment_combo(curr.base, &mut self.attrs);
 
         let Skills {
-            mut aim,
-            mut aim_no_sliders,
-            mut speed,
-            mut flashlight,
-        } = self.skills.clone();
+            aim,
+            aim_no_sliders,
+            speed,
+            flashlight,
+        } = &mut self.skills;
 
-        let mut aim_rating = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+        let aim_rating = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
         let aim_rating_no_sliders =
             aim_no_sliders.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
 
         let speed_notes = speed.relevant_note_count();
-        let mut speed_rating = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+        let speed_rating = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
 
-        let mut flashlight_rating = flashlight.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+        let flashlight_rating = flashlight.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
 
         let slider_factor = if aim_rating > 0.0 {
             aim_rating_no_sliders / aim_rating
@@ -250,47 +311,30 @@ impl Iterator for OsuGradualDifficultyAttributes {
             1.0
         };
 
-        if self.mods.td() {
-            aim_rating = aim_rating.powf(0.8);
-            flashlight_rating = flashlight_rating.powf(0.8);
-        }
-
-        if self.mods.rx() {
-            aim_rating *= 0.9;
-            speed_rating = 0.0;
-            flashlight_rating *= 0.7;
-        }
+        let (aim_rating, speed_rating, flashlight_rating) =
+            adjust_ratings(self.mods, aim_rating, speed_rating, flashlight_rating);
 
-        let base_aim_performance = (5.0 * (aim_rating / 0.0675).max(1.0) - 4.0).powi(3) / 100_000.0;
-        let base_speed_performance =
-            (5.0 * (speed_rating / 0.0675).max(1.0) - 4.0).powi(3) / 100_000.0;
+        let precision = self.precision;
+        let aim_rating = precision.round(aim_rating);
+        let speed_rating = precision.round(speed_rating);
+        let flashlight_rating = precision.round(flashlight_rating);
 
-        let base_flashlight_performance = if self.mods.fl() {
-            flashlight_rating * flashlight_rating * 25.0
-        } else {
-            0.0
-        };
-
-        let base_performance = ((base_aim_performance).powf(1.1)
-            + (base_speed_performance).powf(1.1)
-            + (base_flashlight_performance).powf(1.1))
-        .powf(1.0 / 1.1);
-
-        let star_rating = if base_performance > 0.00001 {
-            PERFORMANCE_BASE_MULTIPLIER.cbrt()
-                * 0.027
-                * ((100_000.0 / 2.0_f64.powf(1.0 / 1.1) * base_performance).cbrt() + 4.0)
-        } else {
-            0.0
-        };
+        let star_rating =
+            self.params
+                .combine(aim_rating, speed_rating, flashlight_rating, self.mods.fl());
 
         let mut attrs = self.attrs.clone();
         attrs.aim = aim_rating;
         attrs.speed = speed_rating;
         attrs.flashlight = flashlight_rating;
-        attrs.slider_factor = slider_factor;
-        attrs.stars = star_rating;
-        attrs.speed_note_count = speed_notes;
+        attrs.slider_factor = precision.round(slider_factor);
+        attrs.stars = precision.round(star_rating);
+        attrs.speed_note_count = precision.round(speed_notes);
+        attrs.ar = precision.round(attrs.ar);
+        attrs.od = precision.round(attrs.od);
+        attrs.hp = precision.round(attrs.hp);
+        attrs.preempt = precision.round(attrs.preempt);
+        attrs.fade_in = precision.round(attrs.fade_in);
 
         Some(attrs)
     }
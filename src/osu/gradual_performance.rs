@@ -1,4 +1,4 @@
-use crate::{Beatmap, OsuPP};
+use crate::{Accuracy, Beatmap, OsuPP};
 
 use super::{OsuGradualDifficultyAttributes, OsuPerformanceAttributes};
 
@@ -19,6 +19,14 @@ pub struct OsuScoreState {
     pub n50: usize,
     /// Amount of current misses.
     pub n_misses: usize,
+    /// Amount of successfully hit slider ends.
+    ///
+    /// Only considered for [`OsuPP::lazer`].
+    pub slider_end_hits: usize,
+    /// Amount of successfully hit slider ticks and repeats.
+    ///
+    /// Only considered for [`OsuPP::lazer`].
+    pub slider_tick_hits: usize,
 }
 
 impl OsuScoreState {
@@ -48,6 +56,73 @@ impl OsuScoreState {
 
         numerator as f64 / denominator as f64
     }
+
+    /// Calculate the accuracy between `0.0` and `1.0` for this state like osu!lazer,
+    /// i.e. including the hit slider ends and ticks out of the given maximum amounts.
+    ///
+    /// The maximum amounts are the amount of sliders and the amount
+    /// of slider ticks and repeats of the map.
+    pub fn lazer_accuracy(&self, n_slider_ends: usize, n_slider_ticks: usize) -> f64 {
+        let total_hits = self.total_hits();
+
+        if total_hits == 0 {
+            return 0.0;
+        }
+
+        let slider_end_hits = self.slider_end_hits.min(n_slider_ends);
+        let slider_tick_hits = self.slider_tick_hits.min(n_slider_ticks);
+
+        let numerator = 300 * self.n300
+            + 100 * self.n100
+            + 50 * self.n50
+            + 150 * slider_end_hits
+            + 30 * slider_tick_hits;
+        let denominator = 300 * total_hits + 150 * n_slider_ends + 30 * n_slider_ticks;
+
+        numerator as f64 / denominator as f64
+    }
+
+    /// Generate the hitresults for the given accuracy with as many 300s as possible.
+    ///
+    /// The accuracy is matched as closely as `n_objects` allows,
+    /// the max combo is left at 0. These are the same hitresults that
+    /// [`OsuPP`] generates for only an accuracy and misses with
+    /// [`HitResultPriority::WorstCase`](crate::HitResultPriority::WorstCase).
+    pub fn from_accuracy(n_objects: usize, acc: impl Into<Accuracy>, n_misses: usize) -> Self {
+        Self::from_fraction(n_objects, acc.into().fraction(), n_misses)
+    }
+
+    /// Same as [`from_accuracy`](OsuScoreState::from_accuracy) but with the
+    /// accuracy as fraction between `0.0` and `1.0`.
+    pub(crate) fn from_fraction(n_objects: usize, acc: f64, n_misses: usize) -> Self {
+        let n_misses = n_misses.min(n_objects);
+        let n_remaining = n_objects - n_misses;
+        let target_total = (acc * (6 * n_objects) as f64).round() as usize;
+
+        // Every non-miss is worth at least one point, each 300 adds five more
+        let delta = target_total.saturating_sub(n_remaining);
+        let mut n300 = (delta / 5).min(n_remaining);
+        let mut n100 = (delta - 5 * n300).min(n_remaining - n300);
+
+        // If too few objects are left for the remaining points,
+        // overshooting with another 300 might be closer
+        let missing = delta - 5 * n300 - n100;
+
+        if missing > 0 && n300 < n_remaining && 5 * (n300 + 1) - delta < missing {
+            n300 += 1;
+            n100 = 0;
+        }
+
+        Self {
+            max_combo: 0,
+            n300,
+            n100,
+            n50: n_remaining - n300 - n100,
+            n_misses,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
+        }
+    }
 }
 
 /// Gradually calculate the performance attributes of an osu!standard map.
@@ -67,7 +142,7 @@ impl OsuScoreState {
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{Beatmap, osu::{OsuGradualPerformanceAttributes, OsuScoreState}};
+/// use akatsuki_pp::{Beatmap, osu::{OsuGradualPerformanceAttributes, OsuScoreState}};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -145,8 +220,26 @@ pub struct OsuGradualPerformanceAttributes<'map> {
 impl<'map> OsuGradualPerformanceAttributes<'map> {
     /// Create a new gradual performance calculator for osu!standard maps.
     pub fn new(map: &'map Beatmap, mods: u32) -> Self {
-        let difficulty = OsuGradualDifficultyAttributes::new(map, mods);
-        let performance = OsuPP::new(map).mods(mods).passed_objects(0);
+        Self::with_rate_adjusted_hit_window(map, mods, true)
+    }
+
+    /// Same as [`new`](Self::new) but specify whether the hit window should be
+    /// adjusted by the clock rate, see [`OsuPP::rate_adjusted_hit_window`].
+    pub fn with_rate_adjusted_hit_window(
+        map: &'map Beatmap,
+        mods: u32,
+        rate_adjusted_hit_window: bool,
+    ) -> Self {
+        let difficulty = OsuGradualDifficultyAttributes::with_rate_adjusted_hit_window(
+            map,
+            mods,
+            rate_adjusted_hit_window,
+        );
+
+        let performance = OsuPP::new(map)
+            .mods(mods)
+            .passed_objects(0)
+            .rate_adjusted_hit_window(rate_adjusted_hit_window);
 
         Self {
             difficulty,
@@ -1,3 +1,5 @@
+#[cfg(feature = "attribution")]
+mod attribution;
 mod difficulty_object;
 mod gradual_difficulty;
 mod gradual_performance;
@@ -6,7 +8,15 @@ mod pp;
 mod scaling_factor;
 mod skills;
 
-use crate::{curve::CurveBuffers, parse::Pos2, AnyStars, Beatmap, GameMode, Mods};
+use std::{borrow::Cow, ops::Range};
+
+use crate::{
+    beatmap::BeatmapAttributes,
+    curve::CurveBuffers,
+    parse::PLAYFIELD_BASE_SIZE,
+    util::{SortedPeaks, StrainSummary},
+    AnyStars, Beatmap, GameMode, Mods, Precision,
+};
 
 use self::{
     difficulty_object::{Distances, OsuDifficultyObject},
@@ -17,6 +27,10 @@ use self::{
 
 pub use self::{gradual_difficulty::*, gradual_performance::*, pp::*};
 
+#[cfg(feature = "attribution")]
+#[cfg_attr(docsrs, doc(cfg(feature = "attribution")))]
+pub use self::attribution::OsuObjectAttribution;
+
 const SECTION_LEN: f64 = 400.0;
 const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
 // * Change radius to 50 to make 100 the diameter. Easier for mental maths.
@@ -26,14 +40,13 @@ const STACK_DISTANCE: f32 = 3.0;
 const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.14;
 const PREEMPT_MIN: f64 = 450.0;
 const FADE_IN_DURATION_MULTIPLIER: f64 = 0.4;
-const PLAYFIELD_BASE_SIZE: Pos2 = Pos2 { x: 512.0, y: 384.0 };
 
 /// Difficulty calculator on osu!standard maps.
 ///
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{OsuStars, Beatmap};
+/// use akatsuki_pp::{OsuStars, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -52,6 +65,14 @@ pub struct OsuStars<'map> {
     pub(crate) mods: u32,
     pub(crate) passed_objects: Option<usize>,
     pub(crate) clock_rate: Option<f64>,
+    pub(crate) start_time: Option<f64>,
+    pub(crate) end_time: Option<f64>,
+    pub(crate) object_range: Option<Range<usize>>,
+    pub(crate) repeats: usize,
+    pub(crate) ignore_spinners: bool,
+    pub(crate) precision: Precision,
+    pub(crate) params: OsuDifficultyParams,
+    pub(crate) rate_adjusted_hit_window: bool,
 }
 
 impl<'map> OsuStars<'map> {
@@ -63,6 +84,14 @@ impl<'map> OsuStars<'map> {
             mods: 0,
             passed_objects: None,
             clock_rate: None,
+            start_time: None,
+            end_time: None,
+            object_range: None,
+            repeats: 1,
+            ignore_spinners: false,
+            precision: Precision::Double,
+            params: OsuDifficultyParams::default(),
+            rate_adjusted_hit_window: true,
         }
     }
 
@@ -109,28 +138,120 @@ impl<'map> OsuStars<'map> {
         self
     }
 
+    /// Ignore all hit objects that start before the given time in milliseconds.
+    ///
+    /// Unlike [`passed_objects`](Self::passed_objects), the remaining objects are
+    /// treated as if they were the whole map.
+    #[inline]
+    pub fn start_time(mut self, start_time: f64) -> Self {
+        self.start_time = Some(start_time);
+
+        self
+    }
+
+    /// Ignore all hit objects that start at or after the given time in milliseconds.
+    #[inline]
+    pub fn end_time(mut self, end_time: f64) -> Self {
+        self.end_time = Some(end_time);
+
+        self
+    }
+
+    /// Only consider the hit objects whose index is within `[start, end)`.
+    ///
+    /// Just like for [`start_time`](Self::start_time), the remaining objects are
+    /// treated as if they were the whole map. If a time range is specified too,
+    /// only objects within both ranges are considered.
+    #[inline]
+    pub fn object_range(mut self, start: usize, end: usize) -> Self {
+        self.object_range = Some(start..end);
+
+        self
+    }
+
+    /// Play the map `repeats` times in a row, e.g. for endurance challenges.
+    ///
+    /// The hit objects are virtually repeated, each repetition starting one beat after
+    /// the previous one ended, so length-dependent values like the max combo and
+    /// the strain accumulation reflect the longer map. Values below 2 leave the map as is
+    /// and values above [`Beatmap::MAX_REPEATS`] are capped.
+    #[inline]
+    pub fn repeat(mut self, repeats: usize) -> Self {
+        self.repeats = repeats;
+
+        self
+    }
+
+    /// Whether spinners should be left out of the difficulty calculation.
+    ///
+    /// Ignored spinners are still counted towards the max combo
+    /// but `n_spinners` of the resulting attributes will be 0.
+    #[inline]
+    pub fn ignore_spinners(mut self, ignore_spinners: bool) -> Self {
+        self.ignore_spinners = ignore_spinners;
+
+        self
+    }
+
+    /// Specify the floating point precision that the resulting attributes are rounded to.
+    ///
+    /// With [`Precision::Single`] the skill ratings are rounded to single
+    /// precision before the star rating is computed from them. This only rounds
+    /// the output, the strains themselves are still calculated with double precision.
+    ///
+    /// Ignored by [`strains`](OsuStars::strains) and when converting to other modes.
+    #[inline]
+    pub fn precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+
+        self
+    }
+
+    /// Override the constants used to combine the skill ratings into the star rating.
+    ///
+    /// Only useful for experimenting with difficulty adjustments, the defaults match osu!.
+    #[inline]
+    pub fn params(mut self, params: OsuDifficultyParams) -> Self {
+        self.params = params;
+
+        self
+    }
+
+    /// Whether the hit window should be adjusted by the clock rate.
+    ///
+    /// Defaults to `true`. If set to `false`, the clock rate still applies to the timing
+    /// of hit objects but the hit window and thus `od` are calculated as if no rate was applied.
+    #[inline]
+    pub fn rate_adjusted_hit_window(mut self, rate_adjusted_hit_window: bool) -> Self {
+        self.rate_adjusted_hit_window = rate_adjusted_hit_window;
+
+        self
+    }
+
     /// Calculate all difficulty related values, including stars.
     #[inline]
     pub fn calculate(self) -> OsuDifficultyAttributes {
         let mods = self.mods;
+        let precision = self.precision;
+        let params = self.params;
 
-        let (skills, mut attrs) = calculate_skills(self);
+        let (skills, mut attrs) = calculate_skills(self, SECTION_LEN);
 
         let Skills {
-            mut aim,
-            mut aim_no_sliders,
+            aim,
+            aim_no_sliders,
             mut speed,
-            mut flashlight,
+            flashlight,
         } = skills;
 
-        let mut aim_rating = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+        let aim_rating = aim.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
         let aim_rating_no_sliders =
             aim_no_sliders.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
 
         let speed_notes = speed.relevant_note_count();
-        let mut speed_rating = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+        let speed_rating = speed.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
 
-        let mut flashlight_rating = flashlight.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
+        let flashlight_rating = flashlight.difficulty_value().sqrt() * DIFFICULTY_MULTIPLIER;
 
         let slider_factor = if aim_rating > 0.0 {
             aim_rating_no_sliders / aim_rating
@@ -138,51 +259,26 @@ impl<'map> OsuStars<'map> {
             1.0
         };
 
-        if mods.td() {
-            aim_rating = aim_rating.powf(0.8);
-            flashlight_rating = flashlight_rating.powf(0.8);
-        }
-
-        if mods.rx() {
-            aim_rating *= 0.9;
-            speed_rating = 0.0;
-            flashlight_rating *= 0.7;
-        }
-
-        if mods.ap() {
-            aim_rating = 0.0;
-            flashlight_rating *= 0.4;
-        }
+        let (aim_rating, speed_rating, flashlight_rating) =
+            adjust_ratings(mods, aim_rating, speed_rating, flashlight_rating);
 
-        let base_aim_performance = (5.0 * (aim_rating / 0.0675).max(1.0) - 4.0).powi(3) / 100_000.0;
-        let base_speed_performance =
-            (5.0 * (speed_rating / 0.0675).max(1.0) - 4.0).powi(3) / 100_000.0;
+        let aim_rating = precision.round(aim_rating);
+        let speed_rating = precision.round(speed_rating);
+        let flashlight_rating = precision.round(flashlight_rating);
 
-        let base_flashlight_performance = if mods.fl() {
-            flashlight_rating * flashlight_rating * 25.0
-        } else {
-            0.0
-        };
-
-        let base_performance = ((base_aim_performance).powf(1.1)
-            + (base_speed_performance).powf(1.1)
-            + (base_flashlight_performance).powf(1.1))
-        .powf(1.0 / 1.1);
-
-        let star_rating = if base_performance > 0.00001 {
-            PERFORMANCE_BASE_MULTIPLIER.cbrt()
-                * 0.027
-                * ((100_000.0 / 2.0_f64.powf(1.0 / 1.1) * base_performance).cbrt() + 4.0)
-        } else {
-            0.0
-        };
+        let star_rating = params.combine(aim_rating, speed_rating, flashlight_rating, mods.fl());
 
         attrs.aim = aim_rating;
         attrs.speed = speed_rating;
         attrs.flashlight = flashlight_rating;
-        attrs.slider_factor = slider_factor;
-        attrs.stars = star_rating;
-        attrs.speed_note_count = speed_notes;
+        attrs.slider_factor = precision.round(slider_factor);
+        attrs.stars = precision.round(star_rating);
+        attrs.speed_note_count = precision.round(speed_notes);
+        attrs.ar = precision.round(attrs.ar);
+        attrs.od = precision.round(attrs.od);
+        attrs.hp = precision.round(attrs.hp);
+        attrs.preempt = precision.round(attrs.preempt);
+        attrs.fade_in = precision.round(attrs.fade_in);
 
         attrs
     }
@@ -192,23 +288,245 @@ impl<'map> OsuStars<'map> {
     /// Suitable to plot the difficulty of a map over time.
     #[inline]
     pub fn strains(self) -> OsuStrains {
-        let (skills, _) = calculate_skills(self);
+        self.strains_with_section_len(SECTION_LEN)
+    }
+
+    /// Calculate the skill strains with a custom section length in milliseconds.
+    ///
+    /// Smaller section lengths give a finer resolution of the strains.
+    /// The section length is at least 1ms and [`OsuStars::strains`] uses 400ms.
+    #[inline]
+    pub fn strains_with_section_len(self, section_len: f64) -> OsuStrains {
+        let section_len = section_len.max(1.0);
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let (skills, _) = calculate_skills(self, section_len);
 
         let Skills {
-            aim,
+            mut aim,
             aim_no_sliders,
             speed,
             flashlight,
         } = skills;
 
+        let start_time = if aim.strain_peaks.is_empty() {
+            0.0
+        } else {
+            let curr_section_end = *skills::StrainSkill::curr_section_end(&mut aim);
+            let n_peaks = aim.strain_peaks.len();
+
+            crate::util::first_section_start(curr_section_end, n_peaks, section_len, clock_rate)
+        };
+
         OsuStrains {
-            section_len: SECTION_LEN,
+            section_len,
+            start_time,
+            clock_rate,
             aim: aim.strain_peaks,
             aim_no_sliders: aim_no_sliders.strain_peaks,
             speed: speed.strain_peaks,
             flashlight: flashlight.strain_peaks,
         }
     }
+
+    /// Calculate the skill strains as if relax was enabled.
+    ///
+    /// Relax automates tapping so the speed strain peaks are all zero, only
+    /// the aim and flashlight peaks remain.
+    #[inline]
+    pub fn relax_strains(self) -> OsuStrains {
+        let mods = self.mods | u32::RX;
+
+        self.mods(mods).strains()
+    }
+
+    /// Calculate the star rating at the end of each strain section.
+    ///
+    /// The value for a section only considers hit objects up to the section's end.
+    /// The `i`-th value belongs to the section of the `i`-th strain peak of
+    /// [`OsuStars::strains`]. Since those don't include the final, unfinished
+    /// section, the result contains one more value: the star rating of the whole map.
+    ///
+    /// All options of the calculator are considered, including
+    /// [`ignore_spinners`](Self::ignore_spinners), [`precision`](Self::precision),
+    /// and [`params`](Self::params).
+    pub fn section_stars(self) -> Vec<f64> {
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let map = self
+            .map
+            .trim(self.start_time, self.end_time, self.object_range.clone());
+        let map = map.repeat(self.repeats);
+        let mut passed_objects = self
+            .passed_objects
+            .unwrap_or(map.hit_objects.len())
+            .min(map.hit_objects.len());
+
+        let map = if self.ignore_spinners {
+            passed_objects -= map.hit_objects[..passed_objects]
+                .iter()
+                .filter(|h| h.is_spinner())
+                .count();
+
+            let mut map = map.into_owned();
+            map.hit_objects.retain(|h| !h.is_spinner());
+            map.n_spinners = 0;
+
+            Cow::Owned(map)
+        } else {
+            map
+        };
+
+        let take = passed_objects.saturating_sub(1);
+
+        let mut gradual = OsuGradualDifficultyAttributes::with_clock_rate(
+            &map,
+            self.mods,
+            clock_rate,
+            self.rate_adjusted_hit_window,
+        );
+
+        gradual.params = self.params;
+        gradual.precision = self.precision;
+
+        let mut section_stars = Vec::new();
+        let mut section_end = None;
+        let mut stars = None;
+
+        while gradual.idx < take {
+            let start_time = match gradual.next_start_time() {
+                Some(start_time) => start_time,
+                None => break,
+            };
+
+            let section_end =
+                section_end.get_or_insert_with(|| (start_time / SECTION_LEN).ceil() * SECTION_LEN);
+
+            while start_time > *section_end {
+                section_stars.extend(stars);
+                *section_end += SECTION_LEN;
+            }
+
+            stars = gradual.next().map(|attrs| attrs.stars);
+        }
+
+        section_stars.extend(stars);
+
+        section_stars
+    }
+
+    /// Calculate the star rating of each sliding window of `window_ms` milliseconds.
+    ///
+    /// Windows are made of consecutive strain sections and advance by one section at a time.
+    /// Each item is the window's start time in the map's timestamps alongside its star rating
+    /// which only considers the strain peaks within the window.
+    pub fn windowed_stars(self, window_ms: f64) -> impl Iterator<Item = (f64, f64)> {
+        let mods = self.mods;
+        let params = self.params;
+        let clock_rate = self.clock_rate.unwrap_or_else(|| mods.clock_rate());
+
+        // The first section ends after the first difficulty object i.e. the second hit object
+        let range =
+            self.map
+                .trimmed_range(self.start_time, self.end_time, self.object_range.clone());
+
+        // Sections in terms of the map's timestamps
+        let section_len = crate::util::section_len_with_clock_rate(SECTION_LEN, clock_rate);
+
+        let first_section = self.map.hit_objects[range]
+            .get(1)
+            .filter(|_| self.passed_objects.unwrap_or(usize::MAX) > 1)
+            .map(|h| (h.start_time / section_len).ceil());
+
+        let (skills, _) = calculate_skills(self, SECTION_LEN);
+
+        let Skills {
+            mut aim,
+            mut speed,
+            mut flashlight,
+            ..
+        } = skills;
+
+        let aim_peaks = skills::StrainSkill::get_curr_strain_peaks(&aim);
+        let speed_peaks = skills::StrainSkill::get_curr_strain_peaks(&speed);
+        let flashlight_peaks = skills::StrainSkill::get_curr_strain_peaks(&flashlight);
+
+        let window_len = ((window_ms / section_len).ceil() as usize).max(1);
+
+        let n_windows = match first_section {
+            Some(_) => aim_peaks.len().saturating_sub(window_len) + 1,
+            None => 0,
+        };
+
+        (0..n_windows).map(move |i| {
+            let end = (i + window_len).min(aim_peaks.len());
+
+            let aim_difficulty = window_difficulty(&mut aim, &aim_peaks[i..end]);
+            let speed_difficulty = window_difficulty(&mut speed, &speed_peaks[i..end]);
+            let flashlight_difficulty =
+                window_difficulty(&mut flashlight, &flashlight_peaks[i..end]);
+
+            let section_start = (first_section.unwrap_or(0.0) + i as f64 - 1.0) * section_len;
+
+            let stars = star_rating(
+                mods,
+                params,
+                aim_difficulty,
+                speed_difficulty,
+                flashlight_difficulty,
+            );
+
+            (section_start, stars)
+        })
+    }
+}
+
+fn window_difficulty<S: skills::StrainSkill>(skill: &mut S, peaks: &[f64]) -> f64 {
+    *skills::StrainSkill::sorted_peaks_mut(skill) = SortedPeaks::from_peaks(peaks, S::DECAY_WEIGHT);
+    *skills::StrainSkill::curr_section_peak(skill) = 0.0;
+
+    <S as Skill>::difficulty_value(skill)
+}
+
+fn star_rating(
+    mods: u32,
+    params: OsuDifficultyParams,
+    aim_difficulty: f64,
+    speed_difficulty: f64,
+    flashlight_difficulty: f64,
+) -> f64 {
+    let (aim_rating, speed_rating, flashlight_rating) = adjust_ratings(
+        mods,
+        aim_difficulty.sqrt() * DIFFICULTY_MULTIPLIER,
+        speed_difficulty.sqrt() * DIFFICULTY_MULTIPLIER,
+        flashlight_difficulty.sqrt() * DIFFICULTY_MULTIPLIER,
+    );
+
+    params.combine(aim_rating, speed_rating, flashlight_rating, mods.fl())
+}
+
+/// Adjust the aim, speed, and flashlight rating for TD, RX, and AP.
+fn adjust_ratings(
+    mods: u32,
+    mut aim_rating: f64,
+    mut speed_rating: f64,
+    mut flashlight_rating: f64,
+) -> (f64, f64, f64) {
+    if mods.td() {
+        aim_rating = aim_rating.powf(0.8);
+        flashlight_rating = flashlight_rating.powf(0.8);
+    }
+
+    if mods.rx() {
+        aim_rating *= 0.9;
+        speed_rating = 0.0;
+        flashlight_rating *= 0.7;
+    }
+
+    if mods.ap() {
+        aim_rating = 0.0;
+        flashlight_rating *= 0.4;
+    }
+
+    (aim_rating, speed_rating, flashlight_rating)
 }
 
 /// The result of calculating the strains on a osu! map.
@@ -217,6 +535,14 @@ impl<'map> OsuStars<'map> {
 pub struct OsuStrains {
     /// Time in ms inbetween two strains.
     pub section_len: f64, // TODO: remove field, make it a method
+    /// Start of the first strain section in milliseconds in terms of the map's timestamps.
+    ///
+    /// The section of the `n`-th strain peak starts at
+    /// `start_time + n * section_len_with_clock_rate(clock_rate)`, which is also the time
+    /// that peak accessors such as [`aim_peak`](Self::aim_peak) return alongside the peak.
+    pub start_time: f64,
+    /// The clock rate that was used to calculate the strains.
+    pub clock_rate: f64,
     /// Strain peaks of the aim skill.
     pub aim: Vec<f64>,
     /// Strain peaks of the aim skill without sliders.
@@ -228,29 +554,128 @@ pub struct OsuStrains {
 }
 
 impl OsuStrains {
+    /// Time in ms inbetween two strains in terms of the map's timestamps,
+    /// e.g. 600ms instead of the default 400ms for DT.
+    #[inline]
+    pub fn section_len_with_clock_rate(&self, clock_rate: f64) -> f64 {
+        crate::util::section_len_with_clock_rate(self.section_len, clock_rate)
+    }
+
     /// Returns the number of strain peaks per skill.
     #[inline]
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.aim.len()
     }
+
+    /// Summary statistics of the aim strain peaks.
+    #[inline]
+    pub fn aim_summary(&self) -> StrainSummary {
+        StrainSummary::new(&self.aim)
+    }
+
+    /// Summary statistics of the aim strain peaks without sliders.
+    #[inline]
+    pub fn aim_no_sliders_summary(&self) -> StrainSummary {
+        StrainSummary::new(&self.aim_no_sliders)
+    }
+
+    /// Summary statistics of the speed strain peaks.
+    #[inline]
+    pub fn speed_summary(&self) -> StrainSummary {
+        StrainSummary::new(&self.speed)
+    }
+
+    /// Summary statistics of the flashlight strain peaks.
+    #[inline]
+    pub fn flashlight_summary(&self) -> StrainSummary {
+        StrainSummary::new(&self.flashlight)
+    }
+
+    /// The highest aim strain peak as `(time, value)`, or `None` if there are no peaks.
+    #[inline]
+    pub fn aim_peak(&self) -> Option<(f64, f64)> {
+        crate::util::strain_peak(
+            &self.aim,
+            self.start_time,
+            self.section_len_with_clock_rate(self.clock_rate),
+        )
+    }
+
+    /// The highest aim strain peak without sliders as `(time, value)`, or `None` if there are no peaks.
+    #[inline]
+    pub fn aim_no_sliders_peak(&self) -> Option<(f64, f64)> {
+        crate::util::strain_peak(
+            &self.aim_no_sliders,
+            self.start_time,
+            self.section_len_with_clock_rate(self.clock_rate),
+        )
+    }
+
+    /// The highest speed strain peak as `(time, value)`, or `None` if there are no peaks.
+    #[inline]
+    pub fn speed_peak(&self) -> Option<(f64, f64)> {
+        crate::util::strain_peak(
+            &self.speed,
+            self.start_time,
+            self.section_len_with_clock_rate(self.clock_rate),
+        )
+    }
+
+    /// The highest flashlight strain peak as `(time, value)`, or `None` if there are no peaks.
+    #[inline]
+    pub fn flashlight_peak(&self) -> Option<(f64, f64)> {
+        crate::util::strain_peak(
+            &self.flashlight,
+            self.start_time,
+            self.section_len_with_clock_rate(self.clock_rate),
+        )
+    }
+}
+
+/// The overall difficulty and the doubled 300 hit window,
+/// optionally calculated as if no clock rate was applied.
+fn od_hit_window(
+    map: &Beatmap,
+    mods: u32,
+    map_attrs: &BeatmapAttributes,
+    rate_adjusted_hit_window: bool,
+) -> (f64, f64) {
+    if rate_adjusted_hit_window {
+        return (map_attrs.od, 2.0 * map_attrs.hit_windows.od);
+    }
+
+    let map_attrs = map.attributes().mods(mods).clock_rate(1.0).build();
+
+    (map_attrs.od, 2.0 * map_attrs.hit_windows.od)
 }
 
-fn calculate_skills(params: OsuStars<'_>) -> (Skills, OsuDifficultyAttributes) {
+fn calculate_skills(params: OsuStars<'_>, section_len: f64) -> (Skills, OsuDifficultyAttributes) {
     let OsuStars {
         map,
         mods,
         passed_objects,
         clock_rate,
+        start_time,
+        end_time,
+        object_range,
+        repeats,
+        ignore_spinners,
+        precision: _,
+        params: _,
+        rate_adjusted_hit_window,
     } = params;
 
+    let map = map.trim(start_time, end_time, object_range);
+    let map = map.repeat(repeats);
+    let map = map.as_ref();
     let take = passed_objects.unwrap_or(map.hit_objects.len());
     let clock_rate = clock_rate.unwrap_or_else(|| mods.clock_rate());
 
     let map_attrs = map.attributes().mods(mods).clock_rate(clock_rate).build();
     let scaling_factor = ScalingFactor::new(map_attrs.cs);
     let hr = mods.hr();
-    let hit_window = 2.0 * map_attrs.hit_windows.od;
+    let (od, hit_window) = od_hit_window(map, mods, &map_attrs, rate_adjusted_hit_window);
     let time_preempt = (map_attrs.hit_windows.ar * clock_rate) as f32 as f64;
 
     // * Preempt time can go below 450ms. Normally, this is achieved via the DT mod
@@ -270,7 +695,9 @@ fn calculate_skills(params: OsuStars<'_>) -> (Skills, OsuDifficultyAttributes) {
     let mut attrs = OsuDifficultyAttributes {
         ar: map_attrs.ar,
         hp: map_attrs.hp,
-        od: map_attrs.od,
+        od,
+        preempt: time_preempt,
+        fade_in: time_fade_in,
         ..Default::default()
     };
 
@@ -281,13 +708,23 @@ fn calculate_skills(params: OsuStars<'_>) -> (Skills, OsuDifficultyAttributes) {
         curve_bufs: CurveBuffers::default(),
     };
 
+    let mut n_ignored = 0;
+
     let mut hit_objects: Vec<_> = map
         .hit_objects
         .iter()
         .take(take)
+        .filter(|h| {
+            let ignore = ignore_spinners && h.is_spinner();
+            n_ignored += ignore as usize;
+
+            !ignore
+        })
         .map(|h| OsuObject::new(h, &mut params))
         .collect();
 
+    attrs.max_combo += n_ignored;
+
     let stack_threshold = time_preempt * map.stack_leniency as f64;
 
     if map.version >= 6 {
@@ -296,6 +733,7 @@ fn calculate_skills(params: OsuStars<'_>) -> (Skills, OsuDifficultyAttributes) {
         old_stacking(&mut hit_objects, stack_threshold);
     }
 
+    #[allow(clippy::manual_inspect)]
     let mut hit_objects = hit_objects.iter_mut().map(|h| {
         h.post_process(hr, &scaling_factor);
 
@@ -308,7 +746,8 @@ fn calculate_skills(params: OsuStars<'_>) -> (Skills, OsuDifficultyAttributes) {
         time_preempt,
         time_fade_in,
         hit_window,
-    );
+    )
+    .with_section_len(section_len);
 
     let last = match hit_objects.next() {
         Some(prev) => prev,
@@ -503,7 +942,15 @@ fn old_stacking(hit_objects: &mut [OsuObject], stack_threshold: f64) {
 }
 
 /// The result of a difficulty calculation on an osu!standard map.
+///
+/// The struct is `#[non_exhaustive]` so it can't be created through a struct
+/// literal outside of this crate. Use [`Default`] instead and set the fields manually.
+///
+/// Note that the [`Default`] attributes are all zero, just like the attributes
+/// of a map without hit objects. Use [`is_empty`](Self::is_empty) to tell them
+/// apart from the attributes of an actual map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
 pub struct OsuDifficultyAttributes {
     /// The aim portion of the total strain.
     pub aim: f64,
@@ -521,6 +968,16 @@ pub struct OsuDifficultyAttributes {
     pub od: f64,
     /// The health drain rate.
     pub hp: f64,
+    /// The time in milliseconds that a hit object is visible before its start time,
+    /// adjusted by AR-changing mods.
+    ///
+    /// Same as the hit objects' time i.e. not scaled by the clock rate.
+    pub preempt: f64,
+    /// The time in milliseconds that a hit object takes to fade in,
+    /// shortened by the hidden mod.
+    ///
+    /// Same as the hit objects' time i.e. not scaled by the clock rate.
+    pub fade_in: f64,
     /// The amount of circles.
     pub n_circles: usize,
     /// The amount of sliders.
@@ -539,10 +996,89 @@ impl OsuDifficultyAttributes {
     pub fn max_combo(&self) -> usize {
         self.max_combo
     }
+
+    /// Whether no hit object was considered, e.g. because the attributes
+    /// are [`Default`] or the map has no (passed) objects.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n_circles + self.n_sliders + self.n_spinners == 0
+    }
+
+    /// Recompute the star rating from the aim, speed, and flashlight ratings.
+    ///
+    /// Useful after adjusting the skill ratings manually. See [`combined_stars`].
+    #[inline]
+    pub fn combined_stars(&self, mods: u32) -> f64 {
+        combined_stars(self.aim, self.speed, self.flashlight, mods.fl())
+    }
+}
+
+/// Combine the mod-adjusted aim, speed, and flashlight ratings into the star rating.
+///
+/// The flashlight rating is only considered if `fl` is `true`.
+#[inline]
+pub fn combined_stars(aim_rating: f64, speed_rating: f64, flashlight_rating: f64, fl: bool) -> f64 {
+    OsuDifficultyParams::default().combine(aim_rating, speed_rating, flashlight_rating, fl)
+}
+
+/// Constants of the osu!standard star rating that can be adjusted through
+/// [`OsuStars::params`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OsuDifficultyParams {
+    /// Multiplier of the aim performance before it is combined with the other skills.
+    pub aim_weight: f64,
+    /// Multiplier of the speed performance before it is combined with the other skills.
+    pub speed_weight: f64,
+    /// Exponent of the norm that sums up the performance of all skills.
+    pub norm_exponent: f64,
+}
+
+impl OsuDifficultyParams {
+    fn combine(&self, aim_rating: f64, speed_rating: f64, flashlight_rating: f64, fl: bool) -> f64 {
+        let base_aim_performance =
+            (5.0 * (aim_rating / 0.0675).max(1.0) - 4.0).powi(3) / 100_000.0 * self.aim_weight;
+        let base_speed_performance =
+            (5.0 * (speed_rating / 0.0675).max(1.0) - 4.0).powi(3) / 100_000.0 * self.speed_weight;
+
+        let base_flashlight_performance = if fl {
+            flashlight_rating * flashlight_rating * 25.0
+        } else {
+            0.0
+        };
+
+        let base_performance = ((base_aim_performance).powf(self.norm_exponent)
+            + (base_speed_performance).powf(self.norm_exponent)
+            + (base_flashlight_performance).powf(self.norm_exponent))
+        .powf(1.0 / self.norm_exponent);
+
+        if base_performance > 0.00001 {
+            PERFORMANCE_BASE_MULTIPLIER.cbrt()
+                * 0.027
+                * ((100_000.0 / 2.0_f64.powf(1.0 / self.norm_exponent) * base_performance).cbrt()
+                    + 4.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Default for OsuDifficultyParams {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            aim_weight: 1.0,
+            speed_weight: 1.0,
+            norm_exponent: 1.1,
+        }
+    }
 }
 
 /// The result of a performance calculation on an osu!standard map.
+///
+/// The struct is `#[non_exhaustive]` so it can't be created through a struct
+/// literal outside of this crate. Use [`new`](Self::new) or [`Default`] instead.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
 pub struct OsuPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: OsuDifficultyAttributes,
@@ -561,6 +1097,18 @@ pub struct OsuPerformanceAttributes {
 }
 
 impl OsuPerformanceAttributes {
+    /// Create new performance attributes with the given pp value.
+    ///
+    /// All pp portions are initialized with zero.
+    #[inline]
+    pub fn new(difficulty: OsuDifficultyAttributes, pp: f64) -> Self {
+        Self {
+            difficulty,
+            pp,
+            ..Default::default()
+        }
+    }
+
     /// Return the star value.
     #[inline]
     pub fn stars(&self) -> f64 {
@@ -2,8 +2,9 @@ use super::{
     OsuDifficultyAttributes, OsuPerformanceAttributes, OsuScoreState, PERFORMANCE_BASE_MULTIPLIER,
 };
 use crate::{
-    AnyPP, Beatmap, DifficultyAttributes, GameMode, HitResultPriority, Mods, OsuStars,
-    PerformanceAttributes,
+    util::{difficulty_range, erf},
+    Accuracy, AnyPP, Beatmap, DifficultyAttributes, GameMode, HitResultPriority, Mods, OsuStars,
+    PerformanceAttributes, Precision,
 };
 
 /// Performance calculator on osu!standard maps.
@@ -11,7 +12,7 @@ use crate::{
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{OsuPP, Beatmap};
+/// use akatsuki_pp::{Accuracy, OsuPP, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -21,7 +22,7 @@ use crate::{
 /// let pp_result = OsuPP::new(&map)
 ///     .mods(8 + 64) // HDDT
 ///     .combo(1234)
-///     .accuracy(98.5)
+///     .accuracy(Accuracy::from_percent(98.5).unwrap())
 ///     .n_misses(1)
 ///     .calculate();
 ///
@@ -30,7 +31,7 @@ use crate::{
 /// let next_result = OsuPP::new(&map)
 ///     .attributes(pp_result) // reusing previous results for performance
 ///     .mods(8 + 64)  // has to be the same to reuse attributes
-///     .accuracy(99.5)
+///     .accuracy(Accuracy::from_percent(99.5).unwrap())
 ///     .calculate();
 ///
 /// println!("PP: {} | Stars: {}", next_result.pp(), next_result.stars());
@@ -48,9 +49,16 @@ pub struct OsuPP<'map> {
     pub(crate) n100: Option<usize>,
     pub(crate) n50: Option<usize>,
     pub(crate) n_misses: Option<usize>,
+    pub(crate) n_slider_breaks: Option<usize>,
+    pub(crate) slider_end_hits: Option<usize>,
+    pub(crate) slider_tick_hits: Option<usize>,
     pub(crate) passed_objects: Option<usize>,
     pub(crate) clock_rate: Option<f64>,
     pub(crate) hitresult_priority: Option<HitResultPriority>,
+    pub(crate) unstable_rate: Option<f64>,
+    pub(crate) precision: Precision,
+    pub(crate) rate_adjusted_hit_window: bool,
+    pub(crate) lazer: bool,
 }
 
 impl<'map> OsuPP<'map> {
@@ -68,9 +76,16 @@ impl<'map> OsuPP<'map> {
             n100: None,
             n50: None,
             n_misses: None,
+            n_slider_breaks: None,
+            slider_end_hits: None,
+            slider_tick_hits: None,
             passed_objects: None,
             clock_rate: None,
             hitresult_priority: None,
+            unstable_rate: None,
+            precision: Precision::Double,
+            rate_adjusted_hit_window: true,
+            lazer: false,
         }
     }
 
@@ -107,6 +122,21 @@ impl<'map> OsuPP<'map> {
         self
     }
 
+    /// Strip all rate-changing and difficulty-altering mods,
+    /// see [`Mods::without_difficulty_mods`].
+    ///
+    /// The score's accuracy, combo, and hit results stay the same so the
+    /// result can be compared to the actual play, e.g. "if played nomod".
+    /// Previously provided attributes and clock rate are discarded.
+    #[inline]
+    pub fn nomod(mut self) -> Self {
+        self.mods = self.mods.without_difficulty_mods();
+        self.attributes = None;
+        self.clock_rate = None;
+
+        self
+    }
+
     /// Specify the max combo of the play.
     #[inline]
     pub fn combo(mut self, combo: usize) -> Self {
@@ -157,6 +187,39 @@ impl<'map> OsuPP<'map> {
         self
     }
 
+    /// Specify the amount of slider breaks of a play.
+    ///
+    /// A slider break interrupts the combo like a miss but since the slider
+    /// itself is still hit, it doesn't count as miss for the accuracy or the
+    /// generated hitresults. The effective amount of misses is at least the
+    /// sum of misses and slider breaks, even if the combo suggests fewer.
+    #[inline]
+    pub fn n_slider_breaks(mut self, n_slider_breaks: usize) -> Self {
+        self.n_slider_breaks = Some(n_slider_breaks);
+
+        self
+    }
+
+    /// Specify the amount of successfully hit slider ends of a play.
+    ///
+    /// Only considered for [`lazer`](OsuPP::lazer). Defaults to all slider ends.
+    #[inline]
+    pub fn slider_end_hits(mut self, slider_end_hits: usize) -> Self {
+        self.slider_end_hits = Some(slider_end_hits);
+
+        self
+    }
+
+    /// Specify the amount of successfully hit slider ticks and repeats of a play.
+    ///
+    /// Only considered for [`lazer`](OsuPP::lazer). Defaults to all slider ticks and repeats.
+    #[inline]
+    pub fn slider_tick_hits(mut self, slider_tick_hits: usize) -> Self {
+        self.slider_tick_hits = Some(slider_tick_hits);
+
+        self
+    }
+
     /// Amount of passed objects for partial plays, e.g. a fail.
     ///
     /// If you want to calculate the performance after every few objects, instead of
@@ -188,6 +251,8 @@ impl<'map> OsuPP<'map> {
             n100,
             n50,
             n_misses,
+            slider_end_hits,
+            slider_tick_hits,
         } = state;
 
         self.combo = Some(max_combo);
@@ -195,15 +260,67 @@ impl<'map> OsuPP<'map> {
         self.n100 = Some(n100);
         self.n50 = Some(n50);
         self.n_misses = Some(n_misses);
+        self.slider_end_hits = Some(slider_end_hits);
+        self.slider_tick_hits = Some(slider_tick_hits);
+
+        self
+    }
+
+    /// Specify the floating point precision that the difficulty attributes are rounded to,
+    /// see [`OsuStars::precision`].
+    ///
+    /// Ignored if attributes were provided.
+    #[inline]
+    pub fn precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
 
         self
     }
 
-    /// Specify the accuracy of a play between `0.0` and `100.0`.
+    /// Whether the hit window should be adjusted by the clock rate,
+    /// see [`OsuStars::rate_adjusted_hit_window`].
+    ///
+    /// Ignored if attributes were provided.
+    #[inline]
+    pub fn rate_adjusted_hit_window(mut self, rate_adjusted_hit_window: bool) -> Self {
+        self.rate_adjusted_hit_window = rate_adjusted_hit_window;
+
+        self
+    }
+
+    /// Whether the accuracy should be rewarded like in osu!lazer instead of stable.
+    ///
+    /// osu!lazer judges slider heads for accuracy so sliders count towards the
+    /// accuracy value alongside circles. Slider ends and ticks are judged too so
+    /// their hits, see [`slider_end_hits`](OsuPP::slider_end_hits) and
+    /// [`slider_tick_hits`](OsuPP::slider_tick_hits), are part of the accuracy.
+    /// Both clients award combo for slider ends so the max combo is the same either way.
+    ///
+    /// Defaults to `false`.
+    #[inline]
+    pub fn lazer(mut self, lazer: bool) -> Self {
+        self.lazer = lazer;
+
+        self
+    }
+
+    /// Specify the accuracy of a play, see [`Accuracy`].
     /// This will be used to generate matching hitresults.
     #[inline]
-    pub fn accuracy(mut self, acc: f64) -> Self {
-        self.acc = Some(acc / 100.0);
+    pub fn accuracy(mut self, acc: impl Into<Accuracy>) -> Self {
+        self.acc = Some(acc.into().fraction());
+
+        self
+    }
+
+    /// Specify the unstable rate of a play to estimate its accuracy.
+    ///
+    /// See [`accuracy_from_unstable_rate`] for how the accuracy is estimated.
+    /// If an accuracy is specified through [`accuracy`](OsuPP::accuracy),
+    /// the unstable rate will be ignored.
+    #[inline]
+    pub fn unstable_rate(mut self, unstable_rate: f64) -> Self {
+        self.unstable_rate = Some(unstable_rate);
 
         self
     }
@@ -233,7 +350,8 @@ impl<'map> OsuPP<'map> {
                 (Some(_), None, Some(_)) => n100 = n_objects.saturating_sub(n300 + n50 + n_misses),
                 (None, Some(_), Some(_)) => n300 = n_objects.saturating_sub(n100 + n50 + n_misses),
                 (Some(_), None, None) => {
-                    let delta = (target_total - n_objects.saturating_sub(n_misses))
+                    let delta = target_total
+                        .saturating_sub(n_objects.saturating_sub(n_misses))
                         .saturating_sub(n300 * 5);
 
                     n100 = delta % 5;
@@ -252,8 +370,9 @@ impl<'map> OsuPP<'map> {
                     }
                 }
                 (None, Some(_), None) => {
-                    let delta =
-                        (target_total - n_objects.saturating_sub(n_misses)).saturating_sub(n100);
+                    let delta = target_total
+                        .saturating_sub(n_objects.saturating_sub(n_misses))
+                        .saturating_sub(n100);
 
                     n300 = delta / 5;
 
@@ -264,7 +383,7 @@ impl<'map> OsuPP<'map> {
                     n50 = n_objects - n300 - n100 - n_misses;
                 }
                 (None, None, Some(_)) => {
-                    let delta = target_total - n_objects.saturating_sub(n_misses);
+                    let delta = target_total.saturating_sub(n_objects.saturating_sub(n_misses));
 
                     n300 = delta / 5;
                     n100 = delta % 5;
@@ -295,11 +414,10 @@ impl<'map> OsuPP<'map> {
                     }
                 }
                 (None, None, None) => {
-                    let delta = target_total - n_objects.saturating_sub(n_misses);
-
-                    n300 = delta / 5;
-                    n100 = delta % 5;
-                    n50 = n_objects.saturating_sub(n300 + n100 + n_misses);
+                    let state = OsuScoreState::from_fraction(n_objects, acc, n_misses);
+                    n300 = state.n300;
+                    n100 = state.n100;
+                    n50 = state.n50;
 
                     if let HitResultPriority::BestCase = priority {
                         // Shift n50 to n100 by sacrificing n300
@@ -345,13 +463,110 @@ impl<'map> OsuPP<'map> {
             n100,
             n50,
             n_misses,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         }
     }
 
+    /// Calculate the performance of a full combo with 100% accuracy, i.e. an SS,
+    /// for the same map, mods, clock rate, and amount of passed objects.
+    ///
+    /// Specified accuracy, combo, and hitresults are ignored.
+    pub fn max_pp(&self) -> OsuPerformanceAttributes {
+        let mut calculator = self.clone();
+        calculator.acc = None;
+        calculator.combo = None;
+        calculator.n300 = None;
+        calculator.n100 = None;
+        calculator.n50 = None;
+        calculator.n_misses = None;
+        calculator.n_slider_breaks = None;
+        calculator.slider_end_hits = None;
+        calculator.slider_tick_hits = None;
+        calculator.unstable_rate = None;
+
+        calculator.calculate()
+    }
+
+    /// Estimate the accuracy between `0.0` and `100.0` that is required
+    /// to achieve `target_pp` with a full combo, i.e. the inverse of
+    /// calculating the pp for an [`accuracy`](OsuPP::accuracy).
+    ///
+    /// The accuracy is determined through a binary search up to a precision of `0.0001`.
+    /// Since accuracies are rounded to hitresults, the result is not necessarily
+    /// the lowest accuracy whose pp reaches the target but its pp always does.
+    /// Returns `None` if not even an SS reaches the target.
+    ///
+    /// Specified accuracy, combo, and hitresults are ignored.
+    pub fn acc_for_pp(&self, target_pp: f64) -> Option<f64> {
+        let max = self.max_pp();
+
+        if max.pp < target_pp || target_pp.is_nan() {
+            return None;
+        }
+
+        let mut calculator = self.clone().attributes(max.difficulty);
+        calculator.combo = None;
+        calculator.n300 = None;
+        calculator.n100 = None;
+        calculator.n50 = None;
+        calculator.n_misses = None;
+        calculator.n_slider_breaks = None;
+        calculator.slider_end_hits = None;
+        calculator.slider_tick_hits = None;
+        calculator.unstable_rate = None;
+
+        let pp_for_acc = |acc: f64| {
+            let acc = Accuracy::from_percent(acc).unwrap_or_default();
+
+            calculator.clone().accuracy(acc).calculate().pp
+        };
+
+        let (mut lower, mut upper) = (0.0, 100.0);
+
+        if pp_for_acc(lower) >= target_pp {
+            return Some(lower);
+        }
+
+        while upper - lower > 0.0001 {
+            let mid = (lower + upper) / 2.0;
+
+            if pp_for_acc(mid) >= target_pp {
+                upper = mid;
+            } else {
+                lower = mid;
+            }
+        }
+
+        Some(upper)
+    }
+
     /// Calculate all performance related values, including pp and stars.
-    pub fn calculate(mut self) -> OsuPerformanceAttributes {
+    #[inline]
+    pub fn calculate(self) -> OsuPerformanceAttributes {
+        self.into_inner().calculate()
+    }
+
+    /// Calculate only the accuracy portion of the performance,
+    /// i.e. [`OsuPerformanceAttributes::pp_acc`], without evaluating the other skills.
+    ///
+    /// The value depends on the overall difficulty, the amount of circles, and the hitresults.
+    pub fn acc_pp(self) -> f64 {
+        let inner = self.into_inner();
+
+        if inner.state.total_hits() == 0 {
+            return 0.0;
+        }
+
+        inner.compute_accuracy_value()
+    }
+
+    fn into_inner(mut self) -> OsuPpInner {
         let attrs = self.attributes.take().unwrap_or_else(|| {
-            let mut calculator = OsuStars::new(self.map).mods(self.mods);
+            let mut calculator = OsuStars::new(self.map)
+                .mods(self.mods)
+                .precision(self.precision)
+                .rate_adjusted_hit_window(self.rate_adjusted_hit_window);
 
             if let Some(passed_objects) = self.passed_objects {
                 calculator = calculator.passed_objects(passed_objects);
@@ -364,19 +579,77 @@ impl<'map> OsuPP<'map> {
             calculator.calculate()
         });
 
-        let state = self.generate_hitresults(attrs.max_combo);
-        let effective_miss_count = calculate_effective_misses(&attrs, &state);
+        if let (None, Some(unstable_rate)) = (self.acc, self.unstable_rate) {
+            self.acc = Some(accuracy_from_unstable_rate(attrs.od, unstable_rate));
+        }
+
+        let mut state = self.generate_hitresults(attrs.max_combo);
+
+        // Slider heads, ends, ticks, and repeats each award one combo
+        let n_slider_ends = attrs.n_sliders;
+        let n_slider_ticks = attrs
+            .max_combo
+            .saturating_sub(attrs.n_circles + attrs.n_spinners + 2 * attrs.n_sliders);
+
+        state.slider_end_hits = self
+            .slider_end_hits
+            .map_or(n_slider_ends, |n| n.min(n_slider_ends));
+        state.slider_tick_hits = self
+            .slider_tick_hits
+            .map_or(n_slider_ticks, |n| n.min(n_slider_ticks));
+
+        let acc = if self.lazer {
+            state.lazer_accuracy(n_slider_ends, n_slider_ticks)
+        } else {
+            state.accuracy()
+        };
 
-        let inner = OsuPpInner {
+        let n_slider_breaks = self.n_slider_breaks.unwrap_or(0);
+        let effective_miss_count = calculate_effective_misses(&attrs, &state, n_slider_breaks);
+
+        OsuPpInner {
             attrs,
             mods: self.mods,
-            acc: state.accuracy(),
+            acc,
             state,
             effective_miss_count,
-        };
+            lazer: self.lazer,
+        }
+    }
+}
 
-        inner.calculate()
+/// Estimate the accuracy between `0.0` and `1.0` of a play with the given
+/// unstable rate on a map with the given overall difficulty.
+///
+/// The unstable rate is ten times the standard deviation of hit errors
+/// in milliseconds, as displayed in-game. Hit errors are assumed to be
+/// normally distributed around zero so the probability of each hitresult
+/// follows from the hit windows of the overall difficulty.
+///
+/// The overall difficulty should already be adjusted to mods and clock rate,
+/// e.g. [`OsuDifficultyAttributes::od`] or [`BeatmapAttributes::od`](crate::beatmap::BeatmapAttributes::od).
+///
+/// A non-positive unstable rate results in `1.0`, a NaN or infinite one in `0.0`.
+pub fn accuracy_from_unstable_rate(od: f64, unstable_rate: f64) -> f64 {
+    let window_300 = difficulty_range(od, 80.0, 50.0, 20.0).max(0.0);
+    let window_100 = difficulty_range(od, 140.0, 100.0, 60.0).max(0.0);
+    let window_50 = difficulty_range(od, 200.0, 150.0, 100.0).max(0.0);
+
+    let deviation = unstable_rate / 10.0;
+
+    if deviation <= 0.0 {
+        return 1.0;
+    } else if !deviation.is_finite() {
+        return 0.0;
     }
+
+    let hit_chance = |window: f64| erf(window / (deviation * std::f64::consts::SQRT_2));
+
+    let p300 = hit_chance(window_300);
+    let p100 = hit_chance(window_100) - p300;
+    let p50 = hit_chance(window_50) - p300 - p100;
+
+    ((6.0 * p300 + 2.0 * p100 + p50) / 6.0).clamp(0.0, 1.0)
 }
 
 struct OsuPpInner {
@@ -385,6 +658,7 @@ struct OsuPpInner {
     acc: f64,
     state: OsuScoreState,
     effective_miss_count: f64,
+    lazer: bool,
 }
 
 impl OsuPpInner {
@@ -504,7 +778,8 @@ impl OsuPpInner {
         if self.attrs.n_sliders > 0 {
             let estimate_slider_ends_dropped =
                 ((self.state.n100 + self.state.n50 + self.state.n_misses)
-                    .min(self.attrs.max_combo - self.state.max_combo) as f64)
+                    .min(self.attrs.max_combo.saturating_sub(self.state.max_combo))
+                    as f64)
                     .clamp(0.0, estimate_diff_sliders);
             let slider_nerf_factor = (1.0 - self.attrs.slider_factor)
                 * (1.0 - estimate_slider_ends_dropped / estimate_diff_sliders).powi(3)
@@ -600,7 +875,12 @@ impl OsuPpInner {
 
         // * This percentage only considers HitCircles of any value - in this part
         // * of the calculation we focus on hitting the timing hit window.
-        let amount_hit_objects_with_acc = self.attrs.n_circles;
+        let mut amount_hit_objects_with_acc = self.attrs.n_circles;
+
+        // osu!lazer judges slider heads so they have a timing hit window too
+        if self.lazer {
+            amount_hit_objects_with_acc += self.attrs.n_sliders;
+        }
 
         let better_acc_percentage = if amount_hit_objects_with_acc > 0 {
             let sub = self.state.total_hits() - amount_hit_objects_with_acc;
@@ -682,23 +962,52 @@ impl OsuPpInner {
     }
 }
 
-fn calculate_effective_misses(attrs: &OsuDifficultyAttributes, state: &OsuScoreState) -> f64 {
+fn calculate_effective_misses(
+    attrs: &OsuDifficultyAttributes,
+    state: &OsuScoreState,
+    n_slider_breaks: usize,
+) -> f64 {
+    let miss_count = effective_miss_count(
+        state.max_combo,
+        attrs.max_combo,
+        state.n_misses,
+        attrs.n_sliders,
+    );
+
+    // Known slider breaks are combo breaks that may have been hit as 300s
+    let known_breaks = state.n_misses + n_slider_breaks;
+
+    // * Clamp miss count to maximum amount of possible breaks
+    miss_count
+        .min((state.n100 + state.n50 + state.n_misses).max(known_breaks) as f64)
+        .max(known_breaks as f64)
+}
+
+/// Estimate the amount of misses including slider breaks of a play from its combo.
+///
+/// A play whose combo is below the map's max combo minus 10% of its sliders is assumed
+/// to have broken its combo `threshold / combo` many times. The result is never
+/// less than `n_misses` and is `n_misses` for maps without sliders.
+///
+/// [`OsuPP`] additionally caps the estimate to the amount of non-300 hitresults.
+pub fn effective_miss_count(
+    combo: usize,
+    max_combo: usize,
+    n_misses: usize,
+    n_sliders: usize,
+) -> f64 {
     // * Guess the number of misses + slider breaks from combo
     let mut combo_based_miss_count = 0.0;
 
-    if attrs.n_sliders > 0 {
-        let full_combo_threshold = attrs.max_combo as f64 - 0.1 * attrs.n_sliders as f64;
+    if n_sliders > 0 {
+        let full_combo_threshold = max_combo as f64 - 0.1 * n_sliders as f64;
 
-        if (state.max_combo as f64) < full_combo_threshold {
-            combo_based_miss_count = full_combo_threshold / (state.max_combo as f64).max(1.0);
+        if (combo as f64) < full_combo_threshold {
+            combo_based_miss_count = full_combo_threshold / (combo as f64).max(1.0);
         }
     }
 
-    // * Clamp miss count to maximum amount of possible breaks
-    combo_based_miss_count =
-        combo_based_miss_count.min((state.n100 + state.n50 + state.n_misses) as f64);
-
-    combo_based_miss_count.max(state.n_misses as f64)
+    combo_based_miss_count.max(n_misses as f64)
 }
 
 /// Abstract type to provide flexibility when passing difficulty attributes to a performance calculation.
@@ -764,6 +1073,8 @@ mod test {
             ar: 9.300000190734863,
             od: 8.800000190734863,
             hp: 5.0,
+            preempt: 555.0,
+            fade_in: 400.0,
             n_circles: 307,
             n_sliders: 293,
             n_spinners: 1,
@@ -794,6 +1105,8 @@ mod test {
             n100: 20,
             n50: 279,
             n_misses: 2,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         };
 
         assert_eq!(state, expected);
@@ -819,6 +1132,8 @@ mod test {
             n100: 289,
             n50: 10,
             n_misses: 2,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         };
 
         assert_eq!(state, expected);
@@ -843,6 +1158,8 @@ mod test {
             n100: 589,
             n50: 10,
             n_misses: 2,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         };
 
         assert_eq!(state, expected);
@@ -869,6 +1186,8 @@ mod test {
             n100: 50,
             n50: 249,
             n_misses: 2,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         };
 
         assert_eq!(state, expected);
@@ -882,7 +1201,7 @@ mod test {
         let state = OsuPP::new(&map)
             .attributes(attrs)
             .combo(500)
-            .accuracy(98.0)
+            .accuracy(Accuracy::from_percent(98.0).unwrap())
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
             .generate_hitresults(max_combo);
@@ -893,6 +1212,8 @@ mod test {
             n100: 15,
             n50: 0,
             n_misses: 2,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         };
 
         assert_eq!(
@@ -912,7 +1233,7 @@ mod test {
         let state = OsuPP::new(&map)
             .attributes(attrs)
             .combo(500)
-            .accuracy(95.0)
+            .accuracy(Accuracy::from_percent(95.0).unwrap())
             .n100(15)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
@@ -924,6 +1245,8 @@ mod test {
             n100: 15,
             n50: 22,
             n_misses: 2,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         };
 
         assert_eq!(
@@ -943,7 +1266,7 @@ mod test {
         let state = OsuPP::new(&map)
             .attributes(attrs)
             .combo(500)
-            .accuracy(95.0)
+            .accuracy(Accuracy::from_percent(95.0).unwrap())
             .n50(10)
             .n_misses(2)
             .hitresult_priority(HitResultPriority::BestCase)
@@ -955,6 +1278,8 @@ mod test {
             n100: 29,
             n50: 10,
             n_misses: 2,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         };
 
         assert_eq!(
@@ -974,7 +1299,7 @@ mod test {
         let state = OsuPP::new(&map)
             .attributes(attrs)
             .combo(500)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .hitresult_priority(HitResultPriority::BestCase)
             .generate_hitresults(max_combo);
 
@@ -984,6 +1309,8 @@ mod test {
             n100: 89,
             n50: 1,
             n_misses: 0,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         };
 
         assert_eq!(
@@ -1003,7 +1330,7 @@ mod test {
         let state = OsuPP::new(&map)
             .attributes(attrs)
             .combo(500)
-            .accuracy(90.0)
+            .accuracy(Accuracy::from_percent(90.0).unwrap())
             .hitresult_priority(HitResultPriority::WorstCase)
             .generate_hitresults(max_combo);
 
@@ -1013,6 +1340,8 @@ mod test {
             n100: 4,
             n50: 69,
             n_misses: 0,
+            slider_end_hits: 0,
+            slider_tick_hits: 0,
         };
 
         assert_eq!(
@@ -1023,4 +1352,27 @@ mod test {
             expected.accuracy()
         );
     }
+
+    #[test]
+    fn hitresults_acc_match_score_state() {
+        let (map, attrs) = test_data();
+        let max_combo = attrs.max_combo();
+        let n_objects = map.hit_objects.len();
+
+        for &acc in &[80.0, 95.0, 99.5, 100.0] {
+            let state = OsuPP::new(&map)
+                .attributes(attrs.clone())
+                .accuracy(Accuracy::from_percent(acc).unwrap())
+                .n_misses(3)
+                .hitresult_priority(HitResultPriority::WorstCase)
+                .generate_hitresults(max_combo);
+
+            let expected = OsuScoreState {
+                max_combo,
+                ..OsuScoreState::from_accuracy(n_objects, Accuracy::from_percent(acc).unwrap(), 3)
+            };
+
+            assert_eq!(state, expected, "{}%", acc);
+        }
+    }
 }
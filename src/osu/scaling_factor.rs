@@ -4,6 +4,8 @@ use super::NORMALIZED_RADIUS;
 
 const OBJECT_RADIUS: f32 = 64.0;
 
+/// Object radius based on the mod-adjusted circle size and the factor
+/// by which distances are normalized to a radius of [`NORMALIZED_RADIUS`].
 #[derive(Copy, Clone, Debug)]
 pub(crate) struct ScalingFactor {
     pub(crate) factor: f32,
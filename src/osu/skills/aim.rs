@@ -1,6 +1,9 @@
 use std::f64::consts::{FRAC_PI_2, PI};
 
-use crate::osu::difficulty_object::OsuDifficultyObject;
+use crate::{
+    osu::{difficulty_object::OsuDifficultyObject, SECTION_LEN},
+    util::SortedPeaks,
+};
 
 use super::{previous, previous_start_time, OsuStrainSkill, Skill, StrainSkill};
 
@@ -9,8 +12,12 @@ pub(crate) struct Aim {
     curr_strain: f64,
     curr_section_peak: f64,
     curr_section_end: f64,
+    pub(crate) section_len: f64,
     pub(crate) strain_peaks: Vec<f64>,
+    sorted_peaks: SortedPeaks,
     with_sliders: bool,
+    #[cfg(feature = "attribution")]
+    pub(crate) object_strains: Vec<f64>,
 }
 
 impl Aim {
@@ -22,8 +29,12 @@ impl Aim {
             curr_strain: 0.0,
             curr_section_peak: 0.0,
             curr_section_end: 0.0,
+            section_len: SECTION_LEN,
             strain_peaks: Vec::new(),
+            sorted_peaks: SortedPeaks::new(Self::DECAY_WEIGHT),
             with_sliders,
+            #[cfg(feature = "attribution")]
+            object_strains: Vec::new(),
         }
     }
 
@@ -43,17 +54,37 @@ impl Skill for Aim {
     }
 
     #[inline]
-    fn difficulty_value(&mut self) -> f64 {
+    fn difficulty_value(&self) -> f64 {
         <Self as OsuStrainSkill>::difficulty_value(self)
     }
 }
 
 impl StrainSkill for Aim {
+    #[inline]
+    fn strain_peaks(&self) -> &[f64] {
+        &self.strain_peaks
+    }
+
     #[inline]
     fn strain_peaks_mut(&mut self) -> &mut Vec<f64> {
         &mut self.strain_peaks
     }
 
+    #[inline]
+    fn sorted_peaks(&self) -> &SortedPeaks {
+        &self.sorted_peaks
+    }
+
+    #[inline]
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks {
+        &mut self.sorted_peaks
+    }
+
+    #[inline]
+    fn curr_peak(&self) -> f64 {
+        self.curr_section_peak
+    }
+
     #[inline]
     fn curr_section_peak(&mut self) -> &mut f64 {
         &mut self.curr_section_peak
@@ -64,6 +95,11 @@ impl StrainSkill for Aim {
         &mut self.curr_section_end
     }
 
+    #[inline]
+    fn section_len(&self) -> f64 {
+        self.section_len
+    }
+
     #[inline]
     fn strain_value_at(
         &mut self,
@@ -74,6 +110,12 @@ impl StrainSkill for Aim {
         self.curr_strain += AimEvaluator::evaluate_diff_of(curr, diff_objects, self.with_sliders)
             * Self::SKILL_MULTIPLIER;
 
+        // Only the aim including sliders is attributed to objects
+        #[cfg(feature = "attribution")]
+        if self.with_sliders {
+            self.object_strains.push(self.curr_strain);
+        }
+
         self.curr_strain
     }
 
@@ -88,7 +130,7 @@ impl StrainSkill for Aim {
     }
 
     #[inline]
-    fn difficulty_value(&mut self) -> f64 {
+    fn difficulty_value(&self) -> f64 {
         <Self as OsuStrainSkill>::difficulty_value(self)
     }
 }
@@ -1,5 +1,6 @@
 use crate::{
-    osu::{difficulty_object::OsuDifficultyObject, osu_object::OsuObjectKind},
+    osu::{difficulty_object::OsuDifficultyObject, osu_object::OsuObjectKind, SECTION_LEN},
+    util::SortedPeaks,
     Mods,
 };
 
@@ -10,7 +11,9 @@ pub(crate) struct Flashlight {
     curr_strain: f64,
     curr_section_peak: f64,
     curr_section_end: f64,
+    pub(crate) section_len: f64,
     pub(crate) strain_peaks: Vec<f64>,
+    sorted_peaks: SortedPeaks,
     has_hidden_mod: bool,
     scaling_factor: f64,
     time_preempt: f64,
@@ -26,7 +29,9 @@ impl Flashlight {
             curr_strain: 0.0,
             curr_section_peak: 0.0,
             curr_section_end: 0.0,
+            section_len: SECTION_LEN,
             strain_peaks: Vec::new(),
+            sorted_peaks: SortedPeaks::new(Self::DECAY_WEIGHT),
             has_hidden_mod: mods.hd(),
             scaling_factor: 52.0 / radius as f64,
             time_preempt,
@@ -50,7 +55,7 @@ impl Skill for Flashlight {
     }
 
     #[inline]
-    fn difficulty_value(&mut self) -> f64 {
+    fn difficulty_value(&self) -> f64 {
         <Self as StrainSkill>::difficulty_value(self)
     }
 }
@@ -58,11 +63,31 @@ impl Skill for Flashlight {
 impl StrainSkill for Flashlight {
     const DECAY_WEIGHT: f64 = 0.9;
 
+    #[inline]
+    fn strain_peaks(&self) -> &[f64] {
+        &self.strain_peaks
+    }
+
     #[inline]
     fn strain_peaks_mut(&mut self) -> &mut Vec<f64> {
         &mut self.strain_peaks
     }
 
+    #[inline]
+    fn sorted_peaks(&self) -> &SortedPeaks {
+        &self.sorted_peaks
+    }
+
+    #[inline]
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks {
+        &mut self.sorted_peaks
+    }
+
+    #[inline]
+    fn curr_peak(&self) -> f64 {
+        self.curr_section_peak
+    }
+
     #[inline]
     fn curr_section_peak(&mut self) -> &mut f64 {
         &mut self.curr_section_peak
@@ -73,6 +98,11 @@ impl StrainSkill for Flashlight {
         &mut self.curr_section_end
     }
 
+    #[inline]
+    fn section_len(&self) -> f64 {
+        self.section_len
+    }
+
     #[inline]
     fn strain_value_at(
         &mut self,
@@ -103,8 +133,8 @@ impl StrainSkill for Flashlight {
     }
 
     #[inline]
-    fn difficulty_value(&mut self) -> f64 {
-        self.get_curr_strain_peaks().into_iter().sum::<f64>() * Self::DIFFICULTY_MULTIPLER
+    fn difficulty_value(&self) -> f64 {
+        (self.sorted_peaks.sum() + self.curr_section_peak) * Self::DIFFICULTY_MULTIPLER
     }
 }
 
@@ -214,8 +244,7 @@ impl FlashlightEvaluator {
             let pixel_travel_dist = osu_curr.dists.lazy_travel_dist as f64 / scaling_factor;
 
             // * Reward sliders based on velocity.
-            slider_bonus = ((pixel_travel_dist / osu_curr.dists.travel_time as f64
-                - Self::MIN_VELOCITY)
+            slider_bonus = ((pixel_travel_dist / osu_curr.dists.travel_time - Self::MIN_VELOCITY)
                 .max(0.0))
             .sqrt();
 
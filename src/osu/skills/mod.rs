@@ -36,6 +36,15 @@ impl Skills {
         }
     }
 
+    pub(crate) fn with_section_len(mut self, section_len: f64) -> Self {
+        self.aim.section_len = section_len;
+        self.aim_no_sliders.section_len = section_len;
+        self.speed.section_len = section_len;
+        self.flashlight.section_len = section_len;
+
+        self
+    }
+
     pub(crate) fn process(
         &mut self,
         curr: &OsuDifficultyObject<'_>,
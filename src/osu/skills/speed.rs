@@ -1,6 +1,10 @@
-use std::{cmp::Ordering, f64::consts::PI};
+use std::f64::consts::PI;
 
-use crate::{osu::difficulty_object::OsuDifficultyObject, Mods};
+use crate::{
+    osu::{difficulty_object::OsuDifficultyObject, SECTION_LEN},
+    util::SortedPeaks,
+    Mods,
+};
 
 use super::{next, previous, previous_start_time, OsuStrainSkill, Skill, StrainSkill};
 
@@ -9,9 +13,13 @@ pub(crate) struct Speed {
     curr_strain: f64,
     curr_section_peak: f64,
     curr_section_end: f64,
+    pub(crate) section_len: f64,
     curr_rhythm: f64,
     pub(crate) strain_peaks: Vec<f64>,
-    object_strains: Vec<f64>,
+    sorted_peaks: SortedPeaks,
+    pub(crate) object_strains: Vec<f64>,
+    max_object_strain: f64,
+    relevant_note_sum: Option<f64>,
     hit_window: f64,
     mods: u32,
 }
@@ -25,9 +33,13 @@ impl Speed {
             curr_strain: 0.0,
             curr_section_peak: 0.0,
             curr_section_end: 0.0,
+            section_len: SECTION_LEN,
             curr_rhythm: 0.0,
             strain_peaks: Vec::new(),
+            sorted_peaks: SortedPeaks::new(Self::DECAY_WEIGHT),
             object_strains: Vec::new(),
+            max_object_strain: 0.0,
+            relevant_note_sum: Some(0.0),
             hit_window,
             mods,
         }
@@ -37,17 +49,44 @@ impl Speed {
         Self::STRAIN_DECAY_BASE.powf(ms / 1000.0)
     }
 
-    pub(crate) fn relevant_note_count(&self) -> f64 {
-        self.object_strains
-            .iter()
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-            .copied()
-            .filter(|&n| n > 0.0)
-            .map_or(0.0, |max_strain| {
-                self.object_strains.iter().fold(0.0, |sum, strain| {
-                    sum + (1.0 + (-(strain / max_strain * 12.0 - 6.0)).exp()).recip()
-                })
-            })
+    pub(crate) fn relevant_note_count(&mut self) -> f64 {
+        if self.max_object_strain <= 0.0 {
+            return 0.0;
+        }
+
+        if let Some(sum) = self.relevant_note_sum {
+            return sum;
+        }
+
+        let max_strain = self.max_object_strain;
+
+        let sum = self.object_strains.iter().fold(0.0, |sum, &strain| {
+            sum + Self::relevant_note_weight(strain, max_strain)
+        });
+
+        self.relevant_note_sum = Some(sum);
+
+        sum
+    }
+
+    fn relevant_note_weight(strain: f64, max_strain: f64) -> f64 {
+        (1.0 + (-(strain / max_strain * 12.0 - 6.0)).exp()).recip()
+    }
+
+    // Keeps the relevant note count up to date so that it doesn't need to be
+    // recomputed over all objects after every object. A new maximum strain changes
+    // the weight of all objects so the sum is only recomputed once it's requested.
+    fn push_object_strain(&mut self, strain: f64) {
+        self.object_strains.push(strain);
+
+        if strain > self.max_object_strain {
+            self.max_object_strain = strain;
+            self.relevant_note_sum = None;
+        } else if self.max_object_strain > 0.0 {
+            if let Some(ref mut sum) = self.relevant_note_sum {
+                *sum += Self::relevant_note_weight(strain, self.max_object_strain);
+            }
+        }
     }
 }
 
@@ -62,17 +101,37 @@ impl Skill for Speed {
     }
 
     #[inline]
-    fn difficulty_value(&mut self) -> f64 {
+    fn difficulty_value(&self) -> f64 {
         <Self as OsuStrainSkill>::difficulty_value(self)
     }
 }
 
 impl StrainSkill for Speed {
+    #[inline]
+    fn strain_peaks(&self) -> &[f64] {
+        &self.strain_peaks
+    }
+
     #[inline]
     fn strain_peaks_mut(&mut self) -> &mut Vec<f64> {
         &mut self.strain_peaks
     }
 
+    #[inline]
+    fn sorted_peaks(&self) -> &SortedPeaks {
+        &self.sorted_peaks
+    }
+
+    #[inline]
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks {
+        &mut self.sorted_peaks
+    }
+
+    #[inline]
+    fn curr_peak(&self) -> f64 {
+        self.curr_section_peak
+    }
+
     #[inline]
     fn curr_section_peak(&mut self) -> &mut f64 {
         &mut self.curr_section_peak
@@ -83,12 +142,24 @@ impl StrainSkill for Speed {
         &mut self.curr_section_end
     }
 
+    #[inline]
+    fn section_len(&self) -> f64 {
+        self.section_len
+    }
+
     #[inline]
     fn strain_value_at(
         &mut self,
         curr: &OsuDifficultyObject<'_>,
         diff_objects: &[OsuDifficultyObject<'_>],
     ) -> f64 {
+        // Tapping is automated with relax so objects don't add any speed strain
+        if self.mods.rx() {
+            self.push_object_strain(0.0);
+
+            return 0.0;
+        }
+
         self.curr_strain *= Self::strain_decay(curr.strain_time);
         self.curr_strain +=
             SpeedEvaluator::evaluate_diff_of(curr, diff_objects, self.hit_window, self.mods)
@@ -96,7 +167,7 @@ impl StrainSkill for Speed {
         self.curr_rhythm = RhythmEvaluator::evaluate_diff_of(curr, diff_objects, self.hit_window);
 
         let total_strain = self.curr_strain * self.curr_rhythm;
-        self.object_strains.push(total_strain);
+        self.push_object_strain(total_strain);
 
         total_strain
     }
@@ -113,7 +184,7 @@ impl StrainSkill for Speed {
     }
 
     #[inline]
-    fn difficulty_value(&mut self) -> f64 {
+    fn difficulty_value(&self) -> f64 {
         <Self as OsuStrainSkill>::difficulty_value(self)
     }
 }
@@ -1,18 +1,26 @@
-use std::{cmp::Ordering, mem};
+use std::cmp::Ordering;
 
-use crate::osu::{difficulty_object::OsuDifficultyObject, SECTION_LEN};
+use crate::{
+    osu::difficulty_object::OsuDifficultyObject,
+    util::{lerp, SortedPeaks},
+};
 
 pub(crate) trait Skill {
     fn process(&mut self, curr: &OsuDifficultyObject<'_>, diff_objects: &[OsuDifficultyObject<'_>]);
-    fn difficulty_value(&mut self) -> f64;
+    fn difficulty_value(&self) -> f64;
 }
 
 pub(crate) trait StrainSkill: Skill + Sized {
     const DECAY_WEIGHT: f64 = 0.9;
 
+    fn strain_peaks(&self) -> &[f64];
     fn strain_peaks_mut(&mut self) -> &mut Vec<f64>;
+    fn sorted_peaks(&self) -> &SortedPeaks;
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks;
+    fn curr_peak(&self) -> f64;
     fn curr_section_peak(&mut self) -> &mut f64;
     fn curr_section_end(&mut self) -> &mut f64;
+    fn section_len(&self) -> f64;
 
     fn strain_value_at(
         &mut self,
@@ -34,7 +42,7 @@ pub(crate) trait StrainSkill: Skill + Sized {
     ) {
         // * The first object doesn't generate a strain, so we begin with an incremented section end
         if curr.idx == 0 {
-            let section_len = SECTION_LEN as f64;
+            let section_len = self.section_len();
             *self.curr_section_end() = (curr.start_time / section_len).ceil() * section_len;
         }
 
@@ -46,7 +54,7 @@ pub(crate) trait StrainSkill: Skill + Sized {
                 self.start_new_section_from(section_end, curr, diff_objects);
             }
 
-            *self.curr_section_end() += SECTION_LEN as f64;
+            *self.curr_section_end() += self.section_len();
         }
 
         *self.curr_section_peak() = self
@@ -58,6 +66,7 @@ pub(crate) trait StrainSkill: Skill + Sized {
     fn save_curr_peak(&mut self) {
         let peak = *self.curr_section_peak();
         self.strain_peaks_mut().push(peak);
+        self.sorted_peaks_mut().push(peak);
     }
 
     #[inline]
@@ -73,15 +82,17 @@ pub(crate) trait StrainSkill: Skill + Sized {
         *self.curr_section_peak() = self.calculate_initial_strain(time, curr, diff_objects);
     }
 
-    fn difficulty_value(&mut self) -> f64;
+    fn difficulty_value(&self) -> f64;
 
     #[inline]
-    fn get_curr_strain_peaks(&mut self) -> Vec<f64> {
-        let curr_peak = *self.curr_section_peak();
-        let mut strain_peaks = mem::take(self.strain_peaks_mut());
-        strain_peaks.push(curr_peak);
+    fn get_curr_strain_peaks(&self) -> Vec<f64> {
+        let strain_peaks = self.strain_peaks();
 
-        strain_peaks
+        let mut peaks = Vec::with_capacity(strain_peaks.len() + 1);
+        peaks.extend_from_slice(strain_peaks);
+        peaks.push(self.curr_peak());
+
+        peaks
     }
 }
 
@@ -90,39 +101,42 @@ pub(crate) trait OsuStrainSkill: StrainSkill + Sized {
     const REDUCED_STRAIN_BASELINE: f64 = 0.75;
     const DIFFICULTY_MULTIPLER: f64 = 1.06;
 
-    fn difficulty_value(&mut self) -> f64 {
-        let mut difficulty = 0.0;
-        let mut weight = 1.0;
+    fn difficulty_value(&self) -> f64 {
+        let curr_peak = self.curr_peak();
+        let peaks = self.sorted_peaks();
 
-        // * Sections with 0 strain are excluded to avoid worst-case time complexity of the following sort (e.g. /b/2351871).
-        // * These sections will not contribute to the difficulty.
-        let mut peaks = self.get_curr_strain_peaks();
+        // Sections with 0 strain don't need to be excluded since they come last
+        // and thus don't affect the weight of other sections.
 
-        peaks.retain(|&peak| peak > 0.0);
-        peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        // Only the highest strains, including the current section's, are reduced.
+        // All others are already sorted and can be weighed as a whole.
+        let curr_idx = peaks.count_greater(curr_peak);
+        let curr_is_reduced = curr_idx < Self::REDUCED_SECTION_COUNT;
 
-        let peak_iter = peaks.iter_mut().take(Self::REDUCED_SECTION_COUNT);
+        let mut highest = peaks.highest(Self::REDUCED_SECTION_COUNT);
 
-        fn lerp(start: f64, end: f64, amount: f64) -> f64 {
-            start + (end - start) * amount
+        if curr_is_reduced {
+            highest.insert(curr_idx, curr_peak);
+            highest.truncate(Self::REDUCED_SECTION_COUNT);
         }
 
+        let skip = highest.len() - usize::from(curr_is_reduced);
+
         // * We are reducing the highest strains first to account for extreme difficulty spikes
-        for (i, strain) in peak_iter.enumerate() {
+        for (i, strain) in highest.iter_mut().enumerate() {
             let clamped = (i as f32 / Self::REDUCED_SECTION_COUNT as f32).clamp(0.0, 1.0) as f64;
             let scale = (lerp(1.0, 10.0, clamped)).log10();
             *strain *= lerp(Self::REDUCED_STRAIN_BASELINE, 1.0, scale);
         }
 
-        peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        if !curr_is_reduced {
+            highest.push(curr_peak);
+        }
+
+        highest.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
 
         // * Difficulty is the weighted sum of the highest strains from every section.
         // * We're sorting from highest to lowest strain.
-        for strain in peaks {
-            difficulty += strain * weight;
-            weight *= Self::DECAY_WEIGHT;
-        }
-
-        difficulty * Self::DIFFICULTY_MULTIPLER
+        peaks.weighted_sum(&highest, skip) * Self::DIFFICULTY_MULTIPLER
     }
 }
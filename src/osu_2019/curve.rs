@@ -34,13 +34,13 @@ impl BezierBuffers {
         let additional = len - self.left.len();
 
         self.left
-            .extend(iter::repeat(Pos2::zero()).take(additional));
+            .extend(iter::repeat_n(Pos2::zero(), additional));
         self.right
-            .extend(iter::repeat(Pos2::zero()).take(additional));
+            .extend(iter::repeat_n(Pos2::zero(), additional));
         self.midpoints
-            .extend(iter::repeat(Pos2::zero()).take(additional));
+            .extend(iter::repeat_n(Pos2::zero(), additional));
         self.left_child
-            .extend(iter::repeat(Pos2::zero()).take(additional));
+            .extend(iter::repeat_n(Pos2::zero(), additional));
     }
 }
 
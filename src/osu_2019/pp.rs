@@ -1,12 +1,12 @@
 use super::stars::{stars, OsuDifficultyAttributes, OsuPerformanceAttributes};
-use crate::{Beatmap, Mods};
+use crate::{Accuracy, Beatmap, Mods};
 
 /// Calculator for pp on osu!standard maps.
 ///
 /// # Example
 ///
 /// ```
-/// # use rosu_pp::{OsuPP, Beatmap};
+/// # use akatsuki_pp::{osu_2019::OsuPP, Accuracy, Beatmap};
 /// # /*
 /// let map: Beatmap = ...
 /// # */
@@ -15,7 +15,7 @@ use crate::{Beatmap, Mods};
 ///     .mods(8 + 64) // HDDT
 ///     .combo(1234)
 ///     .misses(1)
-///     .accuracy(98.5) // should be set last
+///     .accuracy(Accuracy::from_percent(98.5).unwrap()) // should be set last
 ///     .calculate();
 ///
 /// println!("PP: {} | Stars: {}", attrs.pp(), attrs.stars());
@@ -23,7 +23,7 @@ use crate::{Beatmap, Mods};
 /// let next_result = OsuPP::new(&map)
 ///     .attributes(attrs) // reusing previous results for performance
 ///     .mods(8 + 64)      // has to be the same to reuse attributes
-///     .accuracy(99.5)
+///     .accuracy(Accuracy::from_percent(99.5).unwrap())
 ///     .calculate();
 ///
 /// println!("PP: {} | Stars: {}", next_result.pp(), next_result.stars());
@@ -134,14 +134,14 @@ impl<'m> OsuPP<'m> {
         self
     }
 
-    /// Generate the hit results with respect to the given accuracy between `0` and `100`.
+    /// Generate the hit results with respect to the given accuracy, see [`Accuracy`].
     ///
     /// Be sure to set `misses` beforehand!
     /// In case of a partial play, be also sure to set `passed_objects` beforehand!
-    pub fn accuracy(mut self, acc: f32) -> Self {
+    pub fn accuracy(mut self, acc: impl Into<Accuracy>) -> Self {
         let n_objects = self.passed_objects.unwrap_or(self.map.hit_objects.len());
 
-        let acc = acc / 100.0;
+        let acc = acc.into().fraction() as f32;
 
         if self.n100.or(self.n50).is_some() {
             let mut n100 = self.n100.unwrap_or(0);
@@ -217,7 +217,7 @@ impl<'m> OsuPP<'m> {
                 } else if self.n50.is_none() {
                     self.n50.replace(remaining);
                 } else {
-                    *self.n300.as_mut().unwrap() += remaining;
+                    *self.n300.get_or_insert(0) += remaining;
                 }
             } else {
                 self.n300.get_or_insert(0);
@@ -230,6 +230,22 @@ impl<'m> OsuPP<'m> {
         }
     }
 
+    /// Calculate the performance of a full combo with 100% accuracy, i.e. an SS,
+    /// for the same map, mods, and amount of passed objects.
+    ///
+    /// Specified accuracy, combo, and hitresults are ignored.
+    pub fn max_pp(&self) -> OsuPerformanceAttributes {
+        let mut calculator = self.clone();
+        calculator.acc = None;
+        calculator.combo = None;
+        calculator.n300 = None;
+        calculator.n100 = None;
+        calculator.n50 = None;
+        calculator.n_misses = 0;
+
+        calculator.calculate()
+    }
+
     /// Returns an object which contains the pp and [`DifficultyAttributes`](crate::osu::DifficultyAttributes)
     /// containing stars and other attributes.
     pub fn calculate(mut self) -> OsuPerformanceAttributes {
@@ -480,7 +496,7 @@ impl<'m> OsuPP<'m> {
             1.52163_f32.powf(attributes.od as f32) * better_acc_percentage.powi(24) * 2.83;
 
         // Bonus for many hitcircles
-        acc_value *= ((n_circles as f32 / 1000.0).powf(0.3)).min(1.15);
+        acc_value *= ((n_circles / 1000.0).powf(0.3)).min(1.15);
 
         // HD bonus
         if self.mods.hd() {
@@ -566,13 +582,13 @@ mod test {
 
         let calculator = OsuPP::new(&map)
             .passed_objects(total_objects)
-            .accuracy(target_acc);
+            .accuracy(Accuracy::from_percent(target_acc).unwrap());
 
         let numerator = 6 * calculator.n300.unwrap_or(0)
             + 2 * calculator.n100.unwrap_or(0)
             + calculator.n50.unwrap_or(0);
         let denominator = 6 * total_objects;
-        let acc = 100.0 * numerator as f32 / denominator as f32;
+        let acc = 100.0 * numerator as f64 / denominator as f64;
 
         assert!(
             (target_acc - acc).abs() < 1.0,
@@ -593,7 +609,7 @@ mod test {
         let calculator = OsuPP::new(&map)
             .passed_objects(total_objects)
             .n50(n50)
-            .accuracy(target_acc);
+            .accuracy(Accuracy::from_percent(target_acc).unwrap());
 
         assert!(
             (calculator.n50.unwrap() as i32 - n50 as i32).abs() <= 4,
@@ -606,7 +622,7 @@ mod test {
             + 2 * calculator.n100.unwrap_or(0)
             + calculator.n50.unwrap_or(0);
         let denominator = 6 * total_objects;
-        let acc = 100.0 * numerator as f32 / denominator as f32;
+        let acc = 100.0 * numerator as f64 / denominator as f64;
 
         assert!(
             (target_acc - acc).abs() < 1.0,
@@ -4,6 +4,9 @@ use std::{error::Error as StdError, fmt, io::Error as IoError, num::ParseFloatEr
 pub type ParseResult<T> = Result<T, ParseError>;
 
 /// Anything that could go wrong while parsing a [`Beatmap`](crate::Beatmap).
+///
+/// The error is `Send + Sync + 'static` so it can be boxed into a
+/// `Box<dyn std::error::Error + Send + Sync>` or passed between threads.
 #[derive(Debug)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum ParseError {
@@ -12,11 +15,19 @@ pub enum ParseError {
     /// The initial data of an `.osu` file was incorrect.
     IncorrectFileHeader,
     /// Line in `.osu` was unexpectedly not of the form `key:value`.
-    BadLine,
+    BadLine {
+        /// The line number within the file, starting at 1.
+        line: usize,
+    },
     /// Line in `.osu` that contains a slider was not in the proper format.
     InvalidCurvePoints,
     /// Expected a decimal number, got something else.
     InvalidDecimalNumber,
+    /// Line in the `[Difficulty]` section was not of the form `key:value`.
+    InvalidDifficultyLine {
+        /// The line number within the file, starting at 1.
+        line: usize,
+    },
     /// Failed to parse game mode.
     InvalidMode,
     /// Expected an additional field.
@@ -32,9 +43,12 @@ impl fmt::Display for ParseError {
             Self::IncorrectFileHeader => {
                 write!(f, "expected `osu file format v` at file begin")
             }
-            Self::BadLine => f.write_str("line not in `Key:Value` pattern"),
+            Self::BadLine { line } => write!(f, "line {} not in `Key:Value` pattern", line),
             Self::InvalidCurvePoints => f.write_str("invalid curve point"),
             Self::InvalidDecimalNumber => f.write_str("invalid float number"),
+            Self::InvalidDifficultyLine { line } => {
+                write!(f, "line {} of the difficulty section is invalid", line)
+            }
             Self::InvalidMode => f.write_str("invalid mode"),
             Self::MissingField(field) => write!(f, "missing field `{}`", field),
             Self::UnknownHitObjectKind => f.write_str("unsupported hitobject kind"),
@@ -47,9 +61,10 @@ impl StdError for ParseError {
         match self {
             Self::IoError(inner) => Some(inner),
             Self::IncorrectFileHeader => None,
-            Self::BadLine => None,
+            Self::BadLine { .. } => None,
             Self::InvalidCurvePoints => None,
             Self::InvalidDecimalNumber => None,
+            Self::InvalidDifficultyLine { .. } => None,
             Self::InvalidMode => None,
             Self::MissingField(_) => None,
             Self::UnknownHitObjectKind => None,
@@ -1,6 +1,8 @@
 use std::cmp::Ordering;
 
-use super::{PathControlPoint, Pos2};
+use crate::{GameMode, Mods};
+
+use super::{PathControlPoint, Pos2, PLAYFIELD_BASE_SIZE};
 
 /// "Intermediate" hitobject created through parsing.
 /// Each mode will handle them differently.
@@ -12,6 +14,11 @@ pub struct HitObject {
     pub start_time: f64,
     /// The type of the object.
     pub kind: HitObjectKind,
+    /// Whether the new combo flag is set for the object.
+    ///
+    /// This is the raw flag of the `.osu` file and irrelevant for difficulty and performance
+    /// but useful to e.g. cycle combo colors.
+    pub new_combo: bool,
 }
 
 impl HitObject {
@@ -27,6 +34,24 @@ impl HitObject {
         }
     }
 
+    /// The position of the object in playfield coordinates after applying the mods.
+    ///
+    /// The playfield spans from `(0, 0)` to `(512, 384)` and with [`HR`](crate::Mods::HR)
+    /// the position is flipped vertically just like in osu!standard. Other modes don't flip
+    /// positions for `HR` so their positions stay unchanged. Stacking is not considered
+    /// and positions outside of the playfield are kept as they are.
+    #[inline]
+    pub fn position(&self, mode: GameMode, mods: u32) -> Pos2 {
+        if mode == GameMode::Osu && mods.hr() {
+            Pos2 {
+                x: self.pos.x,
+                y: PLAYFIELD_BASE_SIZE.y - self.pos.y,
+            }
+        } else {
+            self.pos
+        }
+    }
+
     /// If the object is a circle.
     #[inline]
     pub fn is_circle(&self) -> bool {
@@ -59,6 +84,10 @@ pub enum HitObjectKind {
     /// A circle object.
     Circle,
     /// A full slider object.
+    ///
+    /// Malformed sliders are handled as follows: if the pixel length is not positive or not
+    /// finite, the length is derived from the control points instead. If the control points
+    /// contain non-finite coordinates or all coincide, the slider has no path and is treated as a circle.
     Slider {
         /// Total length of the slider in pixels.
         pixel_len: Option<f64>,
@@ -81,3 +110,40 @@ pub enum HitObjectKind {
         end_time: f64,
     },
 }
+
+impl HitObjectKind {
+    /// Apply the policy for malformed sliders, see [`HitObjectKind::Slider`].
+    ///
+    /// Returns `true` if the slider was turned into a circle.
+    pub(crate) fn sanitize(&mut self) -> bool {
+        let (pixel_len, control_points) = match self {
+            Self::Slider {
+                pixel_len,
+                control_points,
+                ..
+            } => (pixel_len, control_points),
+            _ => return false,
+        };
+
+        if pixel_len.is_some_and(|len| !(len.is_finite() && len > 0.0)) {
+            *pixel_len = None;
+        }
+
+        let has_path = match control_points.split_first() {
+            Some((first, rest)) => {
+                let finite = |pos: Pos2| pos.x.is_finite() && pos.y.is_finite();
+
+                finite(first.pos)
+                    && rest.iter().all(|point| finite(point.pos))
+                    && rest.iter().any(|point| point.pos != first.pos)
+            }
+            None => return false,
+        };
+
+        if !has_path {
+            *self = Self::Circle;
+        }
+
+        !has_path
+    }
+}
@@ -4,12 +4,18 @@ mod hitsound;
 mod pos2;
 mod reader;
 mod sort;
+#[cfg(feature = "metrics")]
+mod stats;
 
 pub use error::{ParseError, ParseResult};
 pub use hitobject::{HitObject, HitObjectKind};
 pub use hitsound::HitSound;
 pub use pos2::Pos2;
+pub(crate) use pos2::PLAYFIELD_BASE_SIZE;
 pub use slider_parsing::*;
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub use stats::ParseStats;
 
 use reader::FileReader;
 pub(crate) use sort::legacy_sort;
@@ -125,7 +131,9 @@ macro_rules! parse_general_body {
                 break;
             }
 
-            let (key, value) = $reader.split_colon().ok_or(ParseError::BadLine)?;
+            let (key, value) = $reader.split_colon().ok_or(ParseError::BadLine {
+                line: $reader.line(),
+            })?;
 
             if key == b"Mode" {
                 mode = match value {
@@ -141,6 +149,14 @@ macro_rules! parse_general_body {
                 if let Some(val) = f32::parse_in_range(value) {
                     stack_leniency = Some(val);
                 }
+            } else if key == b"AudioLeadIn" {
+                if let Some(val) = f64::parse_in_range(value) {
+                    $self.audio_lead_in = val;
+                }
+            } else if key == b"PreviewTime" {
+                if let Some(val) = f64::parse_in_range(value) {
+                    $self.preview_time = val;
+                }
             }
         }
 
@@ -162,14 +178,30 @@ macro_rules! parse_metadata_body {
                 break;
             }
 
-            let (key, value) = $reader.split_colon().ok_or(ParseError::BadLine)?;
+            let (key, value) = $reader.split_colon().ok_or(ParseError::BadLine {
+                line: $reader.line(),
+            })?;
 
-            if key == b"Creator" {
-                $self.creator = value.to_string();
-            } else if key == b"BeatmapID" {
-                if let Some(val) = u32::from_str(value).ok() {
-                    $self.beatmap_id = val;
+            match key {
+                b"Title" => $self.title = value.to_string(),
+                b"TitleUnicode" => $self.title_unicode = value.to_string(),
+                b"Artist" => $self.artist = value.to_string(),
+                b"ArtistUnicode" => $self.artist_unicode = value.to_string(),
+                b"Creator" => $self.creator = value.to_string(),
+                b"Version" => $self.difficulty_name = value.to_string(),
+                b"Source" => $self.source = value.to_string(),
+                b"Tags" => $self.tags = value.to_string(),
+                b"BeatmapID" => {
+                    if let Ok(val) = u32::from_str(value) {
+                        $self.beatmap_id = val;
+                    }
                 }
+                b"BeatmapSetID" => {
+                    if let Ok(val) = u32::from_str(value) {
+                        $self.beatmap_set_id = val;
+                    }
+                }
+                _ => {}
             }
         }
 
@@ -195,7 +227,11 @@ macro_rules! parse_difficulty_body {
                 break;
             }
 
-            let (key, value) = $reader.split_colon().ok_or(ParseError::BadLine)?;
+            let (key, value) = $reader
+                .split_colon()
+                .ok_or(ParseError::InvalidDifficultyLine {
+                    line: $reader.line(),
+                })?;
 
             match key {
                 b"ApproachRate" => {
@@ -262,25 +298,33 @@ macro_rules! parse_events_body {
             };
 
             let mut split = line.split(',');
+            let event = split.next().unwrap_or_default();
 
-            // We're only interested in breaks
-            if let Some(b'2') = split.next().and_then(|value| value.bytes().next()) {
-                let start_time = split
-                    .next()
-                    .next_field("break start")
-                    .map(f64::parse_in_range)?;
+            // We're only interested in breaks, the background, and whether there is a storyboard
+            match event {
+                "0" | "Background" if $self.background_file.is_none() => {
+                    $self.background_file = parse_event_filename(line);
+                }
+                "Sprite" | "4" | "Animation" | "6" => $self.has_storyboard = true,
+                _ if event.starts_with('2') => {
+                    let start_time = split
+                        .next()
+                        .next_field("break start")
+                        .map(f64::parse_in_range)?;
 
-                let end_time = split
-                    .next()
-                    .next_field("break end")
-                    .map(f64::parse_in_range)?;
-
-                if let (Some(start_time), Some(end_time)) = (start_time, end_time) {
-                    $self.breaks.push(Break {
-                        start_time,
-                        end_time,
-                    });
+                    let end_time = split
+                        .next()
+                        .next_field("break end")
+                        .map(f64::parse_in_range)?;
+
+                    if let (Some(start_time), Some(end_time)) = (start_time, end_time) {
+                        $self.breaks.push(Break {
+                            start_time,
+                            end_time,
+                        });
+                    }
                 }
+                _ => {}
             }
         }
 
@@ -288,6 +332,20 @@ macro_rules! parse_events_body {
     }};
 }
 
+/// Extract the filename of a background or video event i.e. its third field without quotes.
+fn parse_event_filename(line: &str) -> Option<String> {
+    let filename = line.splitn(3, ',').nth(2)?.trim_start();
+
+    let filename = match filename.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next()?,
+        None => filename.split(',').next()?,
+    };
+
+    let filename = filename.trim();
+
+    (!filename.is_empty()).then(|| filename.to_owned())
+}
+
 macro_rules! parse_timingpoints_body {
     ($self:ident, $reader:ident, $section:ident) => {{
         let mut empty = true;
@@ -490,6 +548,8 @@ macro_rules! parse_hitobjects_body {
                 Err(_) => continue,
             };
 
+            let new_combo = kind & Self::NEW_COMBO_FLAG > 0;
+
             let mut sound: u8 = match split.next().next_field("sound")?.parse() {
                 Ok(sound) => sound,
                 Err(_) => continue,
@@ -574,7 +634,7 @@ macro_rules! parse_hitobjects_body {
                 point_split.clear();
                 point_split.extend(control_point_iter);
 
-                #[allow(clippy::blocks_in_if_conditions)]
+                #[allow(clippy::blocks_in_conditions)]
                 while {
                     end_idx += 1;
 
@@ -646,12 +706,19 @@ macro_rules! parse_hitobjects_body {
                         Status::Err(err) => return Err(err),
                     }
 
-                    HitObjectKind::Slider {
+                    let mut slider = HitObjectKind::Slider {
                         repeats,
                         pixel_len,
                         control_points,
                         edge_sounds,
+                    };
+
+                    if slider.sanitize() {
+                        $self.n_sliders -= 1;
+                        $self.n_circles += 1;
                     }
+
+                    slider
                 }
             } else if kind & Self::SPINNER_FLAG > 0 {
                 $self.n_spinners += 1;
@@ -700,6 +767,7 @@ macro_rules! parse_hitobjects_body {
                 pos,
                 start_time: time,
                 kind,
+                new_combo,
             });
 
             $self.sounds.push(sound);
@@ -739,7 +807,7 @@ fn parse_custom_sound(sound: &str) -> u8 {
     sound
         .bytes()
         .try_fold(0_u8, |sound, byte| match byte {
-            b'0'..=b'9' => Some(sound.wrapping_mul(10).wrapping_add((byte & 0xF) as u8)),
+            b'0'..=b'9' => Some(sound.wrapping_mul(10).wrapping_add(byte & 0xF)),
             _ => None,
         })
         .unwrap_or(0)
@@ -795,6 +863,8 @@ macro_rules! parse_body {
             }
         }
 
+        map.checksum = Some(reader.md5());
+
         Ok(map)
     }};
 }
@@ -802,7 +872,7 @@ macro_rules! parse_body {
 impl Beatmap {
     const CIRCLE_FLAG: u8 = 1 << 0;
     const SLIDER_FLAG: u8 = 1 << 1;
-    // const NEW_COMBO_FLAG: u8 = 1 << 2;
+    const NEW_COMBO_FLAG: u8 = 1 << 2;
     const SPINNER_FLAG: u8 = 1 << 3;
     // const COMBO_OFFSET_FLAG: u8 = (1 << 4) | (1 << 5) | (1 << 6);
     const HOLD_FLAG: u8 = 1 << 7;
@@ -869,7 +939,7 @@ mod slider_parsing {
         let mut start_idx = 0;
         let mut end_idx = 0;
 
-        #[allow(clippy::blocks_in_if_conditions)]
+        #[allow(clippy::blocks_in_conditions)]
         while {
             end_idx += 1;
 
@@ -975,6 +1045,18 @@ impl Beatmap {
         parse_body!(input)
     }
 
+    /// Same as [`parse`](Beatmap::parse) but also returns [`ParseStats`]
+    /// such as how long parsing took.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn parse_with_stats<R: Read>(input: R) -> ParseResult<(Self, ParseStats)> {
+        let start = std::time::Instant::now();
+        let map = Self::parse(input)?;
+        let stats = ParseStats::new(&map, start.elapsed());
+
+        Ok((map, stats))
+    }
+
     fn parse_general<R: Read>(
         &mut self,
         reader: &mut FileReader<R>,
@@ -1050,6 +1132,20 @@ impl Beatmap {
         parse_body!(input)
     }
 
+    /// Same as [`parse`](Beatmap::parse) but also returns [`ParseStats`]
+    /// such as how long parsing took.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub async fn parse_with_stats<R: AsyncRead + Unpin>(
+        input: R,
+    ) -> ParseResult<(Self, ParseStats)> {
+        let start = std::time::Instant::now();
+        let map = Self::parse(input).await?;
+        let stats = ParseStats::new(&map, start.elapsed());
+
+        Ok((map, stats))
+    }
+
     async fn parse_general<R: AsyncRead + Unpin>(
         &mut self,
         reader: &mut FileReader<R>,
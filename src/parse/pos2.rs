@@ -1,6 +1,9 @@
 use std::fmt;
 use std::ops;
 
+/// The size of the osu!standard playfield.
+pub(crate) const PLAYFIELD_BASE_SIZE: Pos2 = Pos2 { x: 512.0, y: 384.0 };
+
 /// Simple (x, y) coordinate / vector
 #[derive(Clone, Copy, Default, PartialEq)]
 pub struct Pos2 {
@@ -9,7 +9,7 @@ use tokio::io::{AsyncBufReadExt, AsyncRead as Read, BufReader};
 #[cfg(feature = "async_std")]
 use async_std::io::{prelude::BufReadExt, BufReader, Read};
 
-use crate::ParseError;
+use crate::{util::Md5, ParseError};
 
 #[derive(Eq, PartialEq)]
 enum Encoding {
@@ -20,6 +20,8 @@ enum Encoding {
 pub(crate) struct FileReader<R> {
     buf: Vec<u8>,
     encoding: Encoding,
+    line: usize,
+    md5: Md5,
 
     #[cfg(feature = "async_std")]
     inner: BufReader<R>,
@@ -62,6 +64,8 @@ macro_rules! impl_reader {
                 Self {
                     buf: Vec::with_capacity(32),
                     encoding: Encoding::Utf8,
+                    line: 0,
+                    md5: Md5::new(),
                     inner: BufReader::new(src),
                 }
             }
@@ -80,11 +84,13 @@ macro_rules! impl_reader {
         loop {
             $self.buf.clear();
             let bytes = read_until!($self)?;
+            $self.md5.update(&$self.buf);
 
             if bytes == 0 {
                 return Ok(bytes);
             }
 
+            $self.line += 1;
             $self.truncate();
 
             if !$self.buf.is_empty() {
@@ -144,7 +150,7 @@ impl<R> FileReader<R> {
                 let mut n = 0;
 
                 for byte in &self.buf[idx..] {
-                    if !(b'0'..=b'9').contains(byte) {
+                    if !byte.is_ascii_digit() {
                         break;
                     }
 
@@ -156,6 +162,16 @@ impl<R> FileReader<R> {
             .ok_or(ParseError::IncorrectFileHeader)
     }
 
+    /// Returns the number of the line that was read last, starting at 1.
+    pub(crate) fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the MD5 hash of all raw bytes that were read so far.
+    pub(crate) fn md5(&self) -> [u8; 16] {
+        self.md5.clone().finalize()
+    }
+
     /// Returns the bytes inbetween '[' and ']'.
     pub(crate) fn get_section(&self) -> Option<&[u8]> {
         if self.buf[0] == b'[' {
@@ -202,7 +218,7 @@ impl<R> FileReader<R> {
         }
 
         // necessary check for the edge case `//\r\n`
-        if self.buf.starts_with(&[b'/', b'/']) {
+        if self.buf.starts_with(b"//") {
             return self.buf.clear();
         }
 
@@ -43,12 +43,15 @@ fn depth_limited_quick_sort(
             keys.swap(mid, j);
         }
 
+        // copy the pivot since swaps below may move the object at `mid`
+        let pivot = keys[mid].start_time;
+
         loop {
-            while keys[i] < keys[mid] {
+            while keys[i].start_time < pivot {
                 i += 1;
             }
 
-            while keys[mid] < keys[j] {
+            while pivot < keys[j].start_time {
                 j -= 1;
             }
 
@@ -68,7 +71,8 @@ fn depth_limited_quick_sort(
 
         depth_limit -= 1;
 
-        if j.saturating_sub(left) <= right - i {
+        // `i` may exceed `right` so compare signed like the original algorithm
+        if (j as isize - left as isize) <= (right as isize - i as isize) {
             if left < j {
                 depth_limited_quick_sort(keys, left, j, depth_limit);
             }
@@ -117,3 +121,52 @@ fn down_heap(keys: &mut [HitObject], mut i: usize, n: usize, lo: usize) {
         i = child;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{legacy_sort, HitObject};
+    use crate::parse::{HitObjectKind, Pos2};
+
+    fn circle(start_time: f64) -> HitObject {
+        HitObject {
+            pos: Pos2::default(),
+            start_time,
+            kind: HitObjectKind::Circle,
+            new_combo: false,
+        }
+    }
+
+    fn permutations(n: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![Vec::new()];
+        }
+
+        let mut perms = Vec::new();
+
+        for perm in permutations(n - 1) {
+            for k in 0..n {
+                let mut perm = perm.clone();
+                perm.insert(k, n - 1);
+                perms.push(perm);
+            }
+        }
+
+        perms
+    }
+
+    #[test]
+    fn small_inputs() {
+        // some of these orders make the left partition index pass `right`
+        for n in 1..=7 {
+            for perm in permutations(n) {
+                let mut objects: Vec<_> = perm.iter().map(|&t| circle(t as f64)).collect();
+                legacy_sort(&mut objects);
+
+                let times: Vec<_> = objects.iter().map(|h| h.start_time as usize).collect();
+                let expected: Vec<_> = (0..n).collect();
+
+                assert_eq!(times, expected, "input: {:?}", perm);
+            }
+        }
+    }
+}
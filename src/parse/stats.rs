@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use crate::Beatmap;
+
+/// Metrics about parsing a [`Beatmap`], see [`Beatmap::parse_with_stats`].
+///
+/// Useful to find maps that are unusually expensive to parse.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// How long it took to parse the map.
+    pub duration: Duration,
+    /// The amount of hit objects.
+    pub n_objects: usize,
+    /// The amount of sliders, including osu!mania hold notes.
+    pub n_sliders: usize,
+}
+
+impl ParseStats {
+    pub(crate) fn new(map: &Beatmap, duration: Duration) -> Self {
+        Self {
+            duration,
+            n_objects: map.hit_objects.len(),
+            n_sliders: map.n_sliders as usize,
+        }
+    }
+}
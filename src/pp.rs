@@ -1,17 +1,23 @@
+use std::iter;
+
 use crate::{
+    calc_error::{self, CalcError},
     catch::{CatchDifficultyAttributes, CatchPP, CatchPerformanceAttributes},
     mania::{ManiaDifficultyAttributes, ManiaPP, ManiaPerformanceAttributes},
     osu::{OsuDifficultyAttributes, OsuPP, OsuPerformanceAttributes},
     taiko::{TaikoDifficultyAttributes, TaikoPP, TaikoPerformanceAttributes},
-    Beatmap, DifficultyAttributes, GameMode, PerformanceAttributes, ScoreState,
+    Accuracy, Beatmap, DifficultyAttributes, GameMode, Mods, PerformanceAttributes, ScoreState,
 };
 
+/// Difficulty-relevant mods and, for osu!mania, the forced key count.
+pub(crate) type DifficultyKey = (u32, Option<u8>);
+
 /// Performance calculator on maps of any mode.
 ///
 /// # Example
 ///
 /// ```no_run
-/// use rosu_pp::{AnyPP, Beatmap};
+/// use akatsuki_pp::{Accuracy, AnyPP, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -21,7 +27,7 @@ use crate::{
 /// let pp_result = AnyPP::new(&map)
 ///     .mods(8 + 64) // HDDT
 ///     .combo(1234)
-///     .accuracy(98.5)
+///     .accuracy(Accuracy::from_percent(98.5).unwrap())
 ///     .n_misses(1)
 ///     .calculate();
 ///
@@ -30,7 +36,7 @@ use crate::{
 /// let next_result = AnyPP::new(&map)
 ///     .attributes(pp_result) // reusing previous results for performance
 ///     .mods(8 + 64) // has to be the same to reuse attributes
-///     .accuracy(99.5)
+///     .accuracy(Accuracy::from_percent(99.5).unwrap())
 ///     .calculate();
 ///
 /// println!("PP: {} | Stars: {}", next_result.pp(), next_result.stars());
@@ -72,6 +78,92 @@ impl<'map> AnyPP<'map> {
         }
     }
 
+    /// Consume the performance calculator and calculate performance attributes
+    /// for the given parameters without panicking.
+    ///
+    /// Contrary to [`calculate`](Self::calculate), the map is first checked for the known
+    /// causes of panics or endless loops such as non-finite difficulty settings, hit object
+    /// times, or positions. Any other panic during the calculation is caught and returned
+    /// as [`CalcError::Panic`], unless the crate is compiled with `panic = "abort"`.
+    /// Note that the panic hook is still invoked so the panic message is printed regardless.
+    pub fn try_calculate(self) -> Result<PerformanceAttributes, CalcError> {
+        calc_error::validate(self.map())?;
+
+        calc_error::catch_panic(|| self.calculate())
+    }
+
+    /// Calculate the performance of a full combo with 100% accuracy, i.e. an SS,
+    /// for the same map, mods, clock rate, and amount of passed objects.
+    ///
+    /// Specified accuracy, combo, and hitresults are ignored.
+    #[inline]
+    pub fn max_pp(&self) -> PerformanceAttributes {
+        match self {
+            Self::Osu(o) => PerformanceAttributes::Osu(o.max_pp()),
+            Self::Taiko(t) => PerformanceAttributes::Taiko(t.max_pp()),
+            Self::Catch(f) => PerformanceAttributes::Catch(f.max_pp()),
+            Self::Mania(m) => PerformanceAttributes::Mania(m.max_pp()),
+        }
+    }
+
+    /// Calculate the performance for the current mods and for the current mods
+    /// combined with each of the given `mods`, e.g. to find out which mod would
+    /// give the most pp for a play.
+    ///
+    /// The first element of the result belongs to the current mods and the
+    /// `i`-th of the given mods corresponds to the element at index `i + 1`.
+    /// Difficulty attributes are only calculated once for all mod combinations
+    /// that don't affect the difficulty differently, e.g. NF, SO, or HD in
+    /// modes other than osu!standard.
+    ///
+    /// ```no_run
+    /// use akatsuki_pp::{Accuracy, AnyPP, Beatmap, Mods};
+    ///
+    /// # /*
+    /// let map: Beatmap = ...
+    /// # */
+    /// # let map = Beatmap::default();
+    /// let results = AnyPP::new(&map)
+    ///     .mods(64) // DT
+    ///     .accuracy(Accuracy::from_percent(98.5).unwrap())
+    ///     .calculate_each_mod(&[u32::HD, u32::HR, u32::FL]);
+    ///
+    /// let base = results[0].pp();
+    ///
+    /// for (mods, result) in ["HD", "HR", "FL"].iter().zip(&results[1..]) {
+    ///     println!("+{}: {:+.2}pp", mods, result.pp() - base);
+    /// }
+    /// ```
+    pub fn calculate_each_mod(self, mods: &[u32]) -> Vec<PerformanceAttributes> {
+        let base = self.base_mods();
+        let mut calculated: Vec<(DifficultyKey, DifficultyAttributes)> = Vec::new();
+        let mut results = Vec::with_capacity(mods.len() + 1);
+
+        for mods in iter::once(base).chain(mods.iter().map(|&mods| base | mods)) {
+            let key = self.difficulty_key(mods);
+
+            let calc = if mods == base {
+                self.clone()
+            } else {
+                self.clone().without_attributes().mods(mods)
+            };
+
+            let result = match calculated.iter().find(|(k, _)| *k == key) {
+                Some((_, attrs)) => calc.attributes(attrs.clone()).calculate(),
+                None => {
+                    let result = calc.calculate();
+                    calculated.push((key, AttributeProvider::attributes(result.clone())));
+
+                    result
+                }
+            };
+
+            results.push(result);
+        }
+
+        results
+    }
+
     /// Provide the result of a previous difficulty or performance calculation.
     /// If you already calculated the attributes for the current map-mod combination,
     /// be sure to put them in here so that they don't have to be recalculated.
@@ -112,6 +204,38 @@ impl<'map> AnyPP<'map> {
         }
     }
 
+    /// Strip all rate-changing and difficulty-altering mods,
+    /// see [`Mods::without_difficulty_mods`](crate::Mods::without_difficulty_mods).
+    ///
+    /// The score's accuracy, combo, and hit results stay the same so the
+    /// result can be compared to the actual play, e.g. "if played nomod".
+    /// Previously provided attributes and clock rate are discarded.
+    #[inline]
+    pub fn nomod(self) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.nomod()),
+            Self::Taiko(t) => Self::Taiko(t.nomod()),
+            Self::Catch(f) => Self::Catch(f.nomod()),
+            Self::Mania(m) => Self::Mania(m.nomod()),
+        }
+    }
+
+    /// Whether the max combo should be counted like in osu!lazer instead of stable.
+    ///
+    /// Only osu!mania counts combo differently, see [`ManiaStars::lazer`](crate::ManiaStars::lazer).
+    /// The max combo of the other modes is the same for both clients.
+    ///
+    /// For osu!standard, slider heads are also considered for the accuracy,
+    /// see [`OsuPP::lazer`](crate::OsuPP::lazer).
+    #[inline]
+    pub fn lazer(self, lazer: bool) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.lazer(lazer)),
+            Self::Mania(m) => Self::Mania(m.lazer(lazer)),
+            other => other,
+        }
+    }
+
     /// Amount of passed objects for partial plays, e.g. a fail.
     ///
     /// If you want to calculate the performance after every few objects, instead of
@@ -151,9 +275,11 @@ impl<'map> AnyPP<'map> {
         }
     }
 
-    /// Set the accuracy between `0.0` and `100.0`.
+    /// Set the accuracy, see [`Accuracy`].
     #[inline]
-    pub fn accuracy(self, acc: f64) -> Self {
+    pub fn accuracy(self, acc: impl Into<Accuracy>) -> Self {
+        let acc = acc.into();
+
         match self {
             Self::Osu(o) => Self::Osu(o.accuracy(acc)),
             Self::Taiko(t) => Self::Taiko(t.accuracy(acc)),
@@ -249,6 +375,66 @@ impl<'map> AnyPP<'map> {
             Self::Mania(m) => Self::Mania(m.n320(n_geki)),
         }
     }
+
+    fn map(&self) -> &Beatmap {
+        match self {
+            Self::Osu(o) => o.map,
+            Self::Taiko(t) => t.map,
+            Self::Catch(f) => f.map,
+            Self::Mania(m) => m.map,
+        }
+    }
+
+    fn base_mods(&self) -> u32 {
+        match self {
+            Self::Osu(o) => o.mods,
+            Self::Taiko(t) => t.mods,
+            Self::Catch(f) => f.mods,
+            Self::Mania(m) => m.mods,
+        }
+    }
+
+    fn without_attributes(mut self) -> Self {
+        match self {
+            Self::Osu(ref mut o) => o.attributes = None,
+            Self::Taiko(ref mut t) => t.attributes = None,
+            Self::Catch(ref mut f) => f.attributes = None,
+            Self::Mania(ref mut m) => m.attributes = None,
+        }
+
+        self
+    }
+
+    /// The part of the mods that affects the difficulty attributes of the mode.
+    fn difficulty_key(&self, mods: u32) -> DifficultyKey {
+        let mode = match self {
+            Self::Osu(_) => GameMode::Osu,
+            Self::Taiko(_) => GameMode::Taiko,
+            Self::Catch(_) => GameMode::Catch,
+            Self::Mania(_) => GameMode::Mania,
+        };
+
+        difficulty_key(mode, mods)
+    }
+}
+
+/// The part of the mods that affects the difficulty attributes of the mode.
+pub(crate) fn difficulty_key(mode: GameMode, mods: u32) -> DifficultyKey {
+    const RATE: u32 = u32::DT | u32::NC | u32::HT;
+
+    match mode {
+        GameMode::Osu => {
+            let relevant = u32::EZ | u32::HR | u32::HD | u32::FL | u32::TD | u32::RX | u32::AP;
+
+            (mods & (RATE | relevant), None)
+        }
+        GameMode::Taiko => (mods & (RATE | u32::EZ | u32::HR), None),
+        GameMode::Catch => (mods & (RATE | u32::EZ | u32::HR | u32::MR), None),
+        GameMode::Mania => (
+            mods & (RATE | u32::EZ | u32::HR | u32::MR),
+            mods.key_count(),
+        ),
+    }
 }
 
 /// While generating remaining hitresults, decide how they should be distributed.
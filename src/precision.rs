@@ -0,0 +1,34 @@
+/// The floating point precision that the output difficulty attributes are rounded to.
+///
+/// This is purely output rounding, e.g. to compare against attributes that were
+/// stored as single precision floats. The skill and strain calculation itself always
+/// uses double precision so the result does not reproduce osu!stable's single precision
+/// calculation and may still differ from it.
+///
+/// Only [`OsuStars`](crate::OsuStars) and [`OsuPP`](crate::OsuPP) consider it;
+/// gradual calculations, strains, and converts to other modes are not rounded.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Precision {
+    /// Keep the double precision output.
+    Double,
+    /// Round the output to single precision.
+    Single,
+}
+
+impl Precision {
+    /// Round the value to the precision.
+    #[inline]
+    pub fn round(self, value: f64) -> f64 {
+        match self {
+            Self::Double => value,
+            Self::Single => value as f32 as f64,
+        }
+    }
+}
+
+impl Default for Precision {
+    #[inline]
+    fn default() -> Self {
+        Self::Double
+    }
+}
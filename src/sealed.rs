@@ -0,0 +1,7 @@
+/// Prevents public traits from being implemented outside of this crate
+/// so that methods can be added to them without breaking changes.
+pub trait Sealed {}
+
+impl Sealed for u32 {}
+
+impl Sealed for crate::Beatmap {}
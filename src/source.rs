@@ -0,0 +1,164 @@
+use std::{error::Error as StdError, fmt};
+
+#[cfg(any(feature = "async_tokio", feature = "async_std"))]
+use std::{future::Future, pin::Pin};
+
+use crate::{AnyStars, Beatmap, DifficultyAttributes, ParseError};
+
+/// Provider of `.osu` file contents by their beatmap id.
+///
+/// This crate does not do any networking itself so implement this trait for
+/// your HTTP client, a local file cache, or anything else that can fetch maps.
+/// Closures of the form `Fn(u32) -> Result<Vec<u8>, E>` implement it already.
+///
+/// # Example
+///
+/// ```
+/// use akatsuki_pp::{calculate_by_id, BeatmapSource};
+/// use std::io;
+///
+/// struct MapDirectory;
+///
+/// impl BeatmapSource for MapDirectory {
+///     type Error = io::Error;
+///
+///     fn fetch(&self, map_id: u32) -> Result<Vec<u8>, Self::Error> {
+///         std::fs::read(format!("./maps/{}.osu", map_id))
+///     }
+/// }
+///
+/// # /*
+/// let attrs = calculate_by_id(2785319, &MapDirectory, 8 + 64).unwrap();
+/// println!("Stars: {}", attrs.stars());
+/// # */
+/// ```
+#[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+pub trait BeatmapSource {
+    /// The error when fetching fails.
+    type Error;
+
+    /// Fetch the content of the `.osu` file with the given beatmap id.
+    fn fetch(&self, map_id: u32) -> Result<Vec<u8>, Self::Error>;
+}
+
+#[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+impl<F, E> BeatmapSource for F
+where
+    F: Fn(u32) -> Result<Vec<u8>, E>,
+{
+    type Error = E;
+
+    #[inline]
+    fn fetch(&self, map_id: u32) -> Result<Vec<u8>, Self::Error> {
+        self(map_id)
+    }
+}
+
+/// Provider of `.osu` file contents by their beatmap id.
+///
+/// This crate does not do any networking itself so implement this trait for
+/// your HTTP client, a local file cache, or anything else that can fetch maps.
+/// Closures of the form `Fn(u32) -> impl Future<Output = Result<Vec<u8>, E>>` implement it already.
+///
+/// # Example
+///
+/// ```no_run
+/// use akatsuki_pp::{calculate_by_id, BeatmapSource, FetchFuture};
+/// use std::io;
+///
+/// struct MapDirectory;
+///
+/// impl BeatmapSource for MapDirectory {
+///     type Error = io::Error;
+///
+///     fn fetch(&self, map_id: u32) -> FetchFuture<'_, Self::Error> {
+///         Box::pin(async move { tokio::fs::read(format!("./maps/{}.osu", map_id)).await })
+///     }
+/// }
+///
+/// # /*
+/// let attrs = calculate_by_id(2785319, &MapDirectory, 8 + 64).await.unwrap();
+/// println!("Stars: {}", attrs.stars());
+/// # */
+/// ```
+#[cfg(any(feature = "async_tokio", feature = "async_std"))]
+pub trait BeatmapSource {
+    /// The error when fetching fails.
+    type Error;
+
+    /// Fetch the content of the `.osu` file with the given beatmap id.
+    fn fetch(&self, map_id: u32) -> FetchFuture<'_, Self::Error>;
+}
+
+/// The future returned by [`BeatmapSource::fetch`].
+#[cfg(any(feature = "async_tokio", feature = "async_std"))]
+pub type FetchFuture<'a, E> = Pin<Box<dyn Future<Output = Result<Vec<u8>, E>> + Send + 'a>>;
+
+#[cfg(any(feature = "async_tokio", feature = "async_std"))]
+impl<F, Fut, E> BeatmapSource for F
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = Result<Vec<u8>, E>> + Send + 'static,
+{
+    type Error = E;
+
+    #[inline]
+    fn fetch(&self, map_id: u32) -> FetchFuture<'_, Self::Error> {
+        Box::pin(self(map_id))
+    }
+}
+
+/// Anything that could go wrong in [`calculate_by_id`].
+#[derive(Debug)]
+pub enum SourceError<E> {
+    /// The [`BeatmapSource`] failed to fetch the map.
+    Fetch(E),
+    /// The fetched content could not be parsed.
+    Parse(ParseError),
+}
+
+impl<E: fmt::Display> fmt::Display for SourceError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fetch(err) => write!(f, "failed to fetch map: {}", err),
+            Self::Parse(_) => f.write_str("failed to parse fetched map"),
+        }
+    }
+}
+
+impl<E: StdError + 'static> StdError for SourceError<E> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Fetch(err) => Some(err),
+            Self::Parse(err) => Some(err),
+        }
+    }
+}
+
+/// Fetch a map through the given [`BeatmapSource`] and calculate its difficulty attributes with the mods.
+#[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+pub fn calculate_by_id<S: BeatmapSource + ?Sized>(
+    map_id: u32,
+    source: &S,
+    mods: u32,
+) -> Result<DifficultyAttributes, SourceError<S::Error>> {
+    let bytes = source.fetch(map_id).map_err(SourceError::Fetch)?;
+    let map = Beatmap::from_bytes(&bytes).map_err(SourceError::Parse)?;
+
+    Ok(AnyStars::new(&map).mods(mods).calculate())
+}
+
+/// Fetch a map through the given [`BeatmapSource`] and calculate its difficulty attributes with the mods.
+#[cfg(any(feature = "async_tokio", feature = "async_std"))]
+pub async fn calculate_by_id<S: BeatmapSource + ?Sized>(
+    map_id: u32,
+    source: &S,
+    mods: u32,
+) -> Result<DifficultyAttributes, SourceError<S::Error>> {
+    let bytes = source.fetch(map_id).await.map_err(SourceError::Fetch)?;
+    let map = Beatmap::from_bytes(&bytes)
+        .await
+        .map_err(SourceError::Parse)?;
+
+    Ok(AnyStars::new(&map).mods(mods).calculate())
+}
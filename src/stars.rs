@@ -1,4 +1,5 @@
 use crate::{
+    calc_error::{self, CalcError},
     Beatmap, CatchStars, DifficultyAttributes, GameMode, ManiaStars, OsuStars, Strains, TaikoStars,
 };
 
@@ -7,7 +8,7 @@ use crate::{
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{AnyStars, Beatmap};
+/// use akatsuki_pp::{AnyStars, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -71,6 +72,18 @@ impl<'map> AnyStars<'map> {
         }
     }
 
+    /// Whether the max combo should be counted like in osu!lazer instead of stable.
+    ///
+    /// Only osu!mania counts combo differently, see [`ManiaStars::lazer`](crate::ManiaStars::lazer).
+    /// The max combo of the other modes is the same for both clients.
+    #[inline]
+    pub fn lazer(self, lazer: bool) -> Self {
+        match self {
+            Self::Mania(m) => Self::Mania(m.lazer(lazer)),
+            other => other,
+        }
+    }
+
     /// Amount of passed objects for partial plays, e.g. a fail.
     ///
     /// If you want to calculate the performance after every few objects, instead of
@@ -99,6 +112,60 @@ impl<'map> AnyStars<'map> {
         }
     }
 
+    /// Ignore all hit objects that start before the given time in milliseconds.
+    ///
+    /// Unlike [`passed_objects`](Self::passed_objects), the remaining objects are
+    /// treated as if they were the whole map.
+    #[inline]
+    pub fn start_time(self, start_time: f64) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.start_time(start_time)),
+            Self::Taiko(t) => Self::Taiko(t.start_time(start_time)),
+            Self::Catch(f) => Self::Catch(f.start_time(start_time)),
+            Self::Mania(m) => Self::Mania(m.start_time(start_time)),
+        }
+    }
+
+    /// Ignore all hit objects that start at or after the given time in milliseconds.
+    #[inline]
+    pub fn end_time(self, end_time: f64) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.end_time(end_time)),
+            Self::Taiko(t) => Self::Taiko(t.end_time(end_time)),
+            Self::Catch(f) => Self::Catch(f.end_time(end_time)),
+            Self::Mania(m) => Self::Mania(m.end_time(end_time)),
+        }
+    }
+
+    /// Only consider the hit objects whose index is within `[start, end)`.
+    ///
+    /// Just like for [`start_time`](Self::start_time), the remaining objects are
+    /// treated as if they were the whole map. If a time range is specified too,
+    /// only objects within both ranges are considered.
+    #[inline]
+    pub fn object_range(self, start: usize, end: usize) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.object_range(start, end)),
+            Self::Taiko(t) => Self::Taiko(t.object_range(start, end)),
+            Self::Catch(f) => Self::Catch(f.object_range(start, end)),
+            Self::Mania(m) => Self::Mania(m.object_range(start, end)),
+        }
+    }
+
+    /// Play the map `repeats` times in a row, e.g. for endurance challenges.
+    ///
+    /// The hit objects are virtually repeated, each repetition starting one beat after
+    /// the previous one ended. Values below 2 leave the map as is.
+    #[inline]
+    pub fn repeat(self, repeats: usize) -> Self {
+        match self {
+            Self::Osu(o) => Self::Osu(o.repeat(repeats)),
+            Self::Taiko(t) => Self::Taiko(t.repeat(repeats)),
+            Self::Catch(f) => Self::Catch(f.repeat(repeats)),
+            Self::Mania(m) => Self::Mania(m.repeat(repeats)),
+        }
+    }
+
     /// Consume the difficulty calculator and calculate
     /// difficulty attributes for the given parameters.
     #[inline]
@@ -111,6 +178,20 @@ impl<'map> AnyStars<'map> {
         }
     }
 
+    /// Consume the difficulty calculator and calculate difficulty attributes
+    /// for the given parameters without panicking.
+    ///
+    /// Contrary to [`calculate`](Self::calculate), the map is first checked for the known
+    /// causes of panics or endless loops such as non-finite difficulty settings, hit object
+    /// times, or positions. Any other panic during the calculation is caught and returned
+    /// as [`CalcError::Panic`], unless the crate is compiled with `panic = "abort"`.
+    /// Note that the panic hook is still invoked so the panic message is printed regardless.
+    pub fn try_calculate(self) -> Result<DifficultyAttributes, CalcError> {
+        calc_error::validate(self.map())?;
+
+        calc_error::catch_panic(|| self.calculate())
+    }
+
     /// Consume the difficulty calculator and calculate
     /// skill strains for the given parameters.
     ///
@@ -124,4 +205,13 @@ impl<'map> AnyStars<'map> {
             Self::Mania(m) => Strains::Mania(m.strains()),
         }
     }
+
+    fn map(&self) -> &Beatmap {
+        match self {
+            Self::Osu(o) => o.map,
+            Self::Taiko(t) => t.map,
+            Self::Catch(f) => f.map,
+            Self::Mania(m) => m.map,
+        }
+    }
 }
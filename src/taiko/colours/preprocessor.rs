@@ -160,9 +160,9 @@ impl ColourDifficultyPreprocessor {
             let old = curr_hit_pattern.as_ref().map(Rc::downgrade);
             let curr_hit_pattern = curr_hit_pattern.insert(RepeatingHitPatterns::new(old));
 
-            let mut is_coupled = data.get(2).map_or(false, |other| {
-                data[0].borrow().is_repetition_of(&other.borrow())
-            });
+            let mut is_coupled = data
+                .get(2)
+                .is_some_and(|other| data[0].borrow().is_repetition_of(&other.borrow()));
 
             if is_coupled {
                 // * If so, add the current AlternatingMonoPattern to the encoded payload and start repeatedly checking if the
@@ -173,9 +173,9 @@ impl ColourDifficultyPreprocessor {
                         .alternating_mono_patterns
                         .push(data.pop_front().unwrap());
 
-                    is_coupled = data.get(2).map_or(false, |other| {
-                        data[0].borrow().is_repetition_of(&other.borrow())
-                    });
+                    is_coupled = data
+                        .get(2)
+                        .is_some_and(|other| data[0].borrow().is_repetition_of(&other.borrow()));
                 }
 
                 // * Skip over viewed data and add the rest to the payload
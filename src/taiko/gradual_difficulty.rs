@@ -1,4 +1,4 @@
-use std::{borrow::Cow, cell::RefCell, rc::Rc, vec::IntoIter};
+use std::{cell::RefCell, rc::Rc, vec::IntoIter};
 
 use crate::{beatmap::BeatmapHitWindows, taiko::rescale, Beatmap, GameMode, Mods};
 
@@ -22,7 +22,7 @@ use super::{
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{Beatmap, taiko::TaikoGradualDifficultyAttributes};
+/// use akatsuki_pp::{Beatmap, taiko::TaikoGradualDifficultyAttributes};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -55,7 +55,7 @@ impl TaikoGradualDifficultyAttributes {
     /// Create a new difficulty attributes iterator for osu!taiko maps.
     pub fn new(map: &Beatmap, mods: u32) -> Self {
         let map = map.convert_mode(GameMode::Taiko);
-        let is_convert = matches!(map, Cow::Owned(_));
+        let is_convert = map.is_convert;
         let peaks = Peaks::new();
         let clock_rate = mods.clock_rate();
 
@@ -165,7 +165,7 @@ impl Iterator for TaikoGradualDifficultyAttributes {
             mut rhythm_rating,
             mut stamina_rating,
             mut combined_rating,
-        } = self.peaks.clone().difficulty_values();
+        } = self.peaks.difficulty_values();
 
         colour_rating *= DIFFICULTY_MULTIPLIER;
         rhythm_rating *= DIFFICULTY_MULTIPLIER;
@@ -1,4 +1,4 @@
-use crate::{Beatmap, TaikoPP};
+use crate::{Accuracy, Beatmap, TaikoPP};
 
 use super::{TaikoGradualDifficultyAttributes, TaikoPerformanceAttributes};
 
@@ -6,6 +6,8 @@ use super::{TaikoGradualDifficultyAttributes, TaikoPerformanceAttributes};
 /// maximum combo so far and what are the current hitresults.
 ///
 /// This struct is used for [`TaikoGradualPerformanceAttributes`].
+///
+/// Large notes (finishers) are counted as a single hit result.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct TaikoScoreState {
     /// Maximum combo that the score has had so far.
@@ -46,6 +48,26 @@ impl TaikoScoreState {
 
         numerator as f64 / denominator as f64
     }
+
+    /// Generate the hitresults for the given accuracy with as many 300s as possible.
+    ///
+    /// The accuracy is matched as closely as `n_objects` allows,
+    /// the max combo is left at 0.
+    pub fn from_accuracy(n_objects: usize, acc: impl Into<Accuracy>, n_misses: usize) -> Self {
+        let n_misses = n_misses.min(n_objects);
+        let n_remaining = n_objects - n_misses;
+        let target_total = (acc.into().fraction() * (2 * n_objects) as f64).round() as usize;
+
+        // Every non-miss is worth at least one point, each 300 adds one more
+        let n300 = target_total.saturating_sub(n_remaining).min(n_remaining);
+
+        Self {
+            max_combo: 0,
+            n300,
+            n100: n_remaining - n300,
+            n_misses,
+        }
+    }
 }
 
 /// Gradually calculate the performance attributes of an osu!taiko map.
@@ -65,7 +87,7 @@ impl TaikoScoreState {
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{Beatmap, taiko::{TaikoGradualPerformanceAttributes, TaikoScoreState}};
+/// use akatsuki_pp::{Beatmap, taiko::{TaikoGradualPerformanceAttributes, TaikoScoreState}};
 ///
 /// # /*
 /// let map: Beatmap = ...
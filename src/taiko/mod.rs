@@ -7,7 +7,7 @@ mod rim;
 mod skills;
 mod taiko_object;
 
-use std::{borrow::Cow, cell::RefCell, rc::Rc};
+use std::{cell::RefCell, ops::Range, rc::Rc};
 
 pub use self::{gradual_difficulty::*, gradual_performance::*, pp::*};
 
@@ -29,7 +29,7 @@ const DIFFICULTY_MULTIPLIER: f64 = 1.35;
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{TaikoStars, Beatmap};
+/// use akatsuki_pp::{TaikoStars, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -44,19 +44,23 @@ const DIFFICULTY_MULTIPLIER: f64 = 1.35;
 /// ```
 #[derive(Clone, Debug)]
 pub struct TaikoStars<'map> {
-    map: Cow<'map, Beatmap>,
+    pub(crate) map: &'map Beatmap,
     mods: u32,
     passed_objects: Option<usize>,
     clock_rate: Option<f64>,
     is_convert: bool,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    object_range: Option<Range<usize>>,
+    repeats: usize,
+    rate_adjusted_hit_window: bool,
 }
 
 impl<'map> TaikoStars<'map> {
     /// Create a new difficulty calculator for osu!taiko maps.
     #[inline]
     pub fn new(map: &'map Beatmap) -> Self {
-        let map = map.convert_mode(GameMode::Taiko);
-        let is_convert = matches!(map, Cow::Owned(_));
+        let is_convert = map.is_convert;
 
         Self {
             map,
@@ -64,6 +68,11 @@ impl<'map> TaikoStars<'map> {
             passed_objects: None,
             clock_rate: None,
             is_convert,
+            start_time: None,
+            end_time: None,
+            object_range: None,
+            repeats: 1,
+            rate_adjusted_hit_window: true,
         }
     }
 
@@ -107,21 +116,82 @@ impl<'map> TaikoStars<'map> {
         self
     }
 
+    /// Ignore all hit objects that start before the given time in milliseconds.
+    ///
+    /// Unlike [`passed_objects`](Self::passed_objects), the remaining objects are
+    /// treated as if they were the whole map.
+    #[inline]
+    pub fn start_time(mut self, start_time: f64) -> Self {
+        self.start_time = Some(start_time);
+
+        self
+    }
+
+    /// Ignore all hit objects that start at or after the given time in milliseconds.
+    #[inline]
+    pub fn end_time(mut self, end_time: f64) -> Self {
+        self.end_time = Some(end_time);
+
+        self
+    }
+
+    /// Only consider the hit objects whose index is within `[start, end)`.
+    ///
+    /// Just like for [`start_time`](Self::start_time), the remaining objects are
+    /// treated as if they were the whole map. If a time range is specified too,
+    /// only objects within both ranges are considered.
+    #[inline]
+    pub fn object_range(mut self, start: usize, end: usize) -> Self {
+        self.object_range = Some(start..end);
+
+        self
+    }
+
+    /// Play the map `repeats` times in a row, e.g. for endurance challenges.
+    ///
+    /// The hit objects are virtually repeated, each repetition starting one beat after
+    /// the previous one ended, so length-dependent values like the max combo and
+    /// the strain accumulation reflect the longer map. Values below 2 leave the map as is
+    /// and values above [`Beatmap::MAX_REPEATS`] are capped.
+    #[inline]
+    pub fn repeat(mut self, repeats: usize) -> Self {
+        self.repeats = repeats;
+
+        self
+    }
+
+    /// Whether the hit window should be adjusted by the clock rate.
+    ///
+    /// Defaults to `true`. If set to `false`, the clock rate still applies to the timing
+    /// of hit objects but the hit window is calculated as if no rate was applied.
+    /// Useful to isolate the effect of the clock rate on the difficulty.
+    #[inline]
+    pub fn rate_adjusted_hit_window(mut self, rate_adjusted_hit_window: bool) -> Self {
+        self.rate_adjusted_hit_window = rate_adjusted_hit_window;
+
+        self
+    }
+
     /// Calculate all difficulty related values, including stars.
     #[inline]
     pub fn calculate(self) -> TaikoDifficultyAttributes {
-        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let clock_rate = if self.rate_adjusted_hit_window {
+            self.clock_rate.unwrap_or_else(|| self.mods.clock_rate())
+        } else {
+            1.0
+        };
 
-        let BeatmapHitWindows { od: hit_window, .. } = self
-            .map
+        let map = self.map.convert_mode(GameMode::Taiko);
+
+        let BeatmapHitWindows { od: hit_window, .. } = map
             .attributes()
             .mods(self.mods)
             .clock_rate(clock_rate)
             .hit_windows();
 
-        let is_convert = self.is_convert || matches!(self.map, Cow::Owned(_));
+        let is_convert = self.is_convert || map.is_convert;
 
-        let (peaks, max_combo) = calculate_skills(self);
+        let (mut peaks, max_combo) = calculate_skills(&map, self);
 
         let PeaksDifficultyValues {
             mut colour_rating,
@@ -165,16 +235,32 @@ impl<'map> TaikoStars<'map> {
     /// Suitable to plot the difficulty of a map over time.
     #[inline]
     pub fn strains(self) -> TaikoStrains {
-        let (peaks, _) = calculate_skills(self);
+        let clock_rate = self.clock_rate.unwrap_or_else(|| self.mods.clock_rate());
+        let map = self.map.convert_mode(GameMode::Taiko);
+        let (peaks, _) = calculate_skills(&map, self);
 
         let PeaksRaw {
+            curr_section_end,
             colour,
             rhythm,
             stamina,
         } = peaks.into_raw();
 
+        let start_time = if colour.is_empty() {
+            0.0
+        } else {
+            crate::util::first_section_start(
+                curr_section_end,
+                colour.len(),
+                SECTION_LEN as f64,
+                clock_rate,
+            )
+        };
+
         TaikoStrains {
             section_len: SECTION_LEN as f64,
+            start_time,
+            clock_rate,
             color: colour,
             rhythm,
             stamina,
@@ -188,6 +274,14 @@ impl<'map> TaikoStars<'map> {
 pub struct TaikoStrains {
     /// Time in ms inbetween two strains.
     pub section_len: f64,
+    /// Start of the first strain section in milliseconds in terms of the map's timestamps.
+    ///
+    /// The section of the `n`-th strain peak starts at
+    /// `start_time + n * section_len_with_clock_rate(clock_rate)`, which is also the time
+    /// that peak accessors such as [`color_peak`](Self::color_peak) return alongside the peak.
+    pub start_time: f64,
+    /// The clock rate that was used to calculate the strains.
+    pub clock_rate: f64,
     /// Strain peaks of the color skill.
     pub color: Vec<f64>,
     /// Strain peaks of the rhythm skill.
@@ -197,23 +291,67 @@ pub struct TaikoStrains {
 }
 
 impl TaikoStrains {
+    /// Time in ms inbetween two strains in terms of the map's timestamps,
+    /// e.g. 300ms instead of 400ms for HT.
+    #[inline]
+    pub fn section_len_with_clock_rate(&self, clock_rate: f64) -> f64 {
+        crate::util::section_len_with_clock_rate(self.section_len, clock_rate)
+    }
+
     /// Returns the number of strain peaks per skill.
     #[inline]
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.color.len()
     }
+
+    /// The highest color strain peak as `(time, value)`, or `None` if there are no peaks.
+    #[inline]
+    pub fn color_peak(&self) -> Option<(f64, f64)> {
+        crate::util::strain_peak(
+            &self.color,
+            self.start_time,
+            self.section_len_with_clock_rate(self.clock_rate),
+        )
+    }
+
+    /// The highest rhythm strain peak as `(time, value)`, or `None` if there are no peaks.
+    #[inline]
+    pub fn rhythm_peak(&self) -> Option<(f64, f64)> {
+        crate::util::strain_peak(
+            &self.rhythm,
+            self.start_time,
+            self.section_len_with_clock_rate(self.clock_rate),
+        )
+    }
+
+    /// The highest stamina strain peak as `(time, value)`, or `None` if there are no peaks.
+    #[inline]
+    pub fn stamina_peak(&self) -> Option<(f64, f64)> {
+        crate::util::strain_peak(
+            &self.stamina,
+            self.start_time,
+            self.section_len_with_clock_rate(self.clock_rate),
+        )
+    }
 }
 
-fn calculate_skills(params: TaikoStars<'_>) -> (Peaks, usize) {
+fn calculate_skills(map: &Beatmap, params: TaikoStars<'_>) -> (Peaks, usize) {
     let TaikoStars {
-        map,
+        map: _,
         mods,
         passed_objects,
         clock_rate,
         is_convert: _,
+        start_time,
+        end_time,
+        object_range,
+        repeats,
+        rate_adjusted_hit_window: _,
     } = params;
 
+    let map = map.trim(start_time, end_time, object_range);
+    let map = map.repeat(repeats);
     let mut take = passed_objects.unwrap_or(map.hit_objects.len());
     let clock_rate = clock_rate.unwrap_or_else(|| mods.clock_rate());
 
@@ -286,7 +424,15 @@ fn rescale(stars: f64) -> f64 {
 }
 
 /// The result of a difficulty calculation on an osu!taiko map.
+///
+/// The struct is `#[non_exhaustive]` so it can't be created through a struct
+/// literal outside of this crate. Use [`Default`] instead and set the fields manually.
+///
+/// Note that the [`Default`] attributes are all zero, just like the attributes
+/// of a map without hit objects. Use [`is_empty`](Self::is_empty) to tell them
+/// apart from the attributes of an actual map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
 pub struct TaikoDifficultyAttributes {
     /// The difficulty corresponding to the stamina skill.
     pub stamina: f64,
@@ -310,10 +456,21 @@ impl TaikoDifficultyAttributes {
     pub fn max_combo(&self) -> usize {
         self.max_combo
     }
+
+    /// Whether no hit object was considered, e.g. because the attributes
+    /// are [`Default`] or the map has no (passed) circles.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.max_combo == 0
+    }
 }
 
 /// The result of a performance calculation on an osu!taiko map.
+///
+/// The struct is `#[non_exhaustive]` so it can't be created through a struct
+/// literal outside of this crate. Use [`new`](Self::new) or [`Default`] instead.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
 pub struct TaikoPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: TaikoDifficultyAttributes,
@@ -328,6 +485,18 @@ pub struct TaikoPerformanceAttributes {
 }
 
 impl TaikoPerformanceAttributes {
+    /// Create new performance attributes with the given pp value.
+    ///
+    /// All pp portions are initialized with zero.
+    #[inline]
+    pub fn new(difficulty: TaikoDifficultyAttributes, pp: f64) -> Self {
+        Self {
+            difficulty,
+            pp,
+            ..Default::default()
+        }
+    }
+
     /// Return the star value.
     #[inline]
     pub fn stars(&self) -> f64 {
@@ -362,14 +531,27 @@ impl<'map> From<OsuStars<'map>> for TaikoStars<'map> {
             mods,
             passed_objects,
             clock_rate,
+            start_time,
+            end_time,
+            object_range,
+            repeats,
+            ignore_spinners: _,
+            precision: _,
+            params: _,
+            rate_adjusted_hit_window,
         } = osu;
 
         Self {
-            map: map.convert_mode(GameMode::Taiko),
+            map,
             mods,
             passed_objects,
             clock_rate,
             is_convert: true,
+            start_time,
+            end_time,
+            object_range,
+            repeats,
+            rate_adjusted_hit_window,
         }
     }
 }
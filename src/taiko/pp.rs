@@ -1,16 +1,18 @@
-use std::borrow::Cow;
-
 use super::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoScoreState, TaikoStars};
 use crate::{
-    Beatmap, DifficultyAttributes, GameMode, HitResultPriority, Mods, OsuPP, PerformanceAttributes,
+    Accuracy, Beatmap, DifficultyAttributes, HitResultPriority, Mods, OsuPP, PerformanceAttributes,
 };
 
 /// Performance calculator on osu!taiko maps.
 ///
+/// Large notes (finishers) count as a single hit result just like in osu!stable i.e. hitting
+/// one with both keys only awards bonus score. Their judgement must be folded into
+/// `n300`, `n100`, and `n_misses` and has no separate effect on accuracy or pp.
+///
 /// # Example
 ///
 /// ```
-/// use rosu_pp::{TaikoPP, Beatmap};
+/// use akatsuki_pp::{Accuracy, TaikoPP, Beatmap};
 ///
 /// # /*
 /// let map: Beatmap = ...
@@ -20,7 +22,7 @@ use crate::{
 /// let pp_result = TaikoPP::new(&map)
 ///     .mods(8 + 64) // HDDT
 ///     .combo(1234)
-///     .accuracy(98.5)
+///     .accuracy(Accuracy::from_percent(98.5).unwrap())
 ///     .n_misses(1)
 ///     .calculate();
 ///
@@ -29,7 +31,7 @@ use crate::{
 /// let next_result = TaikoPP::new(&map)
 ///     .attributes(pp_result) // reusing previous results for performance
 ///     .mods(8 + 64) // has to be the same to reuse attributes
-///     .accuracy(99.5)
+///     .accuracy(Accuracy::from_percent(99.5).unwrap())
 ///     .calculate();
 ///
 /// println!("PP: {} | Stars: {}", next_result.pp(), next_result.stars());
@@ -37,9 +39,9 @@ use crate::{
 #[derive(Clone, Debug)]
 #[allow(clippy::upper_case_acronyms)]
 pub struct TaikoPP<'map> {
-    pub(crate) map: Cow<'map, Beatmap>,
-    attributes: Option<TaikoDifficultyAttributes>,
-    mods: u32,
+    pub(crate) map: &'map Beatmap,
+    pub(crate) attributes: Option<TaikoDifficultyAttributes>,
+    pub(crate) mods: u32,
     combo: Option<usize>,
     acc: Option<f64>,
     passed_objects: Option<usize>,
@@ -56,7 +58,7 @@ impl<'map> TaikoPP<'map> {
     #[inline]
     pub fn new(map: &'map Beatmap) -> Self {
         Self {
-            map: map.convert_mode(GameMode::Taiko),
+            map,
             attributes: None,
             mods: 0,
             combo: None,
@@ -92,6 +94,21 @@ impl<'map> TaikoPP<'map> {
         self
     }
 
+    /// Strip all rate-changing and difficulty-altering mods,
+    /// see [`Mods::without_difficulty_mods`].
+    ///
+    /// The score's accuracy, combo, and hit results stay the same so the
+    /// result can be compared to the actual play, e.g. "if played nomod".
+    /// Previously provided attributes and clock rate are discarded.
+    #[inline]
+    pub fn nomod(mut self) -> Self {
+        self.mods = self.mods.without_difficulty_mods();
+        self.attributes = None;
+        self.clock_rate = None;
+
+        self
+    }
+
     /// Specify the max combo of the play.
     #[inline]
     pub fn combo(mut self, combo: usize) -> Self {
@@ -129,16 +146,16 @@ impl<'map> TaikoPP<'map> {
     /// Specify the amount of misses of the play.
     #[inline]
     pub fn n_misses(mut self, n_misses: usize) -> Self {
-        self.n_misses = Some(n_misses.min(self.map.n_circles as usize));
+        self.n_misses = Some(n_misses);
 
         self
     }
 
-    /// Specify the accuracy of a play between `0.0` and `100.0`.
+    /// Specify the accuracy of a play, see [`Accuracy`].
     /// This will be used to generate matching hitresults.
     #[inline]
-    pub fn accuracy(mut self, acc: f64) -> Self {
-        self.acc = Some(acc / 100.0);
+    pub fn accuracy(mut self, acc: impl Into<Accuracy>) -> Self {
+        self.acc = Some(acc.into().fraction());
 
         self
     }
@@ -183,12 +200,27 @@ impl<'map> TaikoPP<'map> {
         self
     }
 
+    /// Calculate the performance of a full combo with 100% accuracy, i.e. an SS,
+    /// for the same map, mods, clock rate, and amount of passed objects.
+    ///
+    /// Specified accuracy, combo, and hitresults are ignored.
+    pub fn max_pp(&self) -> TaikoPerformanceAttributes {
+        let mut calculator = self.clone();
+        calculator.acc = None;
+        calculator.combo = None;
+        calculator.n300 = None;
+        calculator.n100 = None;
+        calculator.n_misses = None;
+
+        calculator.calculate()
+    }
+
     /// Calculate all performance related values, including pp and stars.
     pub fn calculate(mut self) -> TaikoPerformanceAttributes {
         let attrs = self.attributes.take().unwrap_or_else(|| {
-            let mut calculator = TaikoStars::new(self.map.as_ref())
+            let mut calculator = TaikoStars::new(self.map)
                 .mods(self.mods)
-                .is_convert(matches!(self.map, Cow::Owned(_)));
+                .is_convert(self.map.is_convert);
 
             if let Some(passed_objects) = self.passed_objects {
                 calculator = calculator.passed_objects(passed_objects);
@@ -221,7 +253,7 @@ impl<'map> TaikoPP<'map> {
 
         let mut n300 = self.n300.unwrap_or(0);
         let mut n100 = self.n100.unwrap_or(0);
-        let n_misses = self.n_misses.unwrap_or(0);
+        let n_misses = self.n_misses.map_or(0, |n| n.min(total_result_count));
 
         if let Some(acc) = self.acc {
             match (self.n300, self.n100) {
@@ -399,13 +431,20 @@ impl<'map> From<OsuPP<'map>> for TaikoPP<'map> {
             n100,
             n50: _,
             n_misses,
+            n_slider_breaks: _,
+            slider_end_hits: _,
+            slider_tick_hits: _,
             passed_objects,
             clock_rate,
             hitresult_priority,
+            unstable_rate: _,
+            precision: _,
+            rate_adjusted_hit_window: _,
+            lazer: _,
         } = osu;
 
         Self {
-            map: map.convert_mode(GameMode::Taiko),
+            map,
             attributes: None,
             mods,
             combo,
@@ -540,7 +579,7 @@ mod test {
         let state = TaikoPP::new(&map)
             .attributes(attrs)
             .combo(100)
-            .accuracy(97.2)
+            .accuracy(Accuracy::from_percent(97.2).unwrap())
             .n_misses(2)
             .hitresult_priority(HitResultPriority::WorstCase)
             .generate_hitresults(max_combo);
@@ -3,9 +3,12 @@ use std::{
     rc::{Rc, Weak},
 };
 
-use crate::taiko::{
-    colours::{AlternatingMonoPattern, MonoStreak, RepeatingHitPatterns},
-    difficulty_object::{ObjectLists, TaikoDifficultyObject},
+use crate::{
+    taiko::{
+        colours::{AlternatingMonoPattern, MonoStreak, RepeatingHitPatterns},
+        difficulty_object::{ObjectLists, TaikoDifficultyObject},
+    },
+    util::SortedPeaks,
 };
 
 use super::{Skill, StrainDecaySkill, StrainSkill};
@@ -16,6 +19,7 @@ pub(crate) struct Colour {
     curr_section_peak: f64,
     curr_section_end: f64,
     pub(crate) strain_peaks: Vec<f64>,
+    sorted_peaks: SortedPeaks,
 }
 
 impl Colour {
@@ -25,6 +29,7 @@ impl Colour {
             curr_section_peak: 0.0,
             curr_section_end: 0.0,
             strain_peaks: Vec::new(),
+            sorted_peaks: SortedPeaks::new(Self::DECAY_WEIGHT),
         }
     }
 }
@@ -36,7 +41,7 @@ impl Skill for Colour {
     }
 
     #[inline]
-    fn difficulty_value(self) -> f64 {
+    fn difficulty_value(&mut self) -> f64 {
         <Self as StrainSkill>::difficulty_value(self)
     }
 }
@@ -47,6 +52,16 @@ impl StrainSkill for Colour {
         &mut self.strain_peaks
     }
 
+    #[inline]
+    fn sorted_peaks(&self) -> &SortedPeaks {
+        &self.sorted_peaks
+    }
+
+    #[inline]
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks {
+        &mut self.sorted_peaks
+    }
+
     #[inline]
     fn curr_section_peak(&mut self) -> &mut f64 {
         &mut self.curr_section_peak
@@ -1,6 +1,7 @@
-use std::cmp::Ordering;
-
-use crate::taiko::difficulty_object::{ObjectLists, TaikoDifficultyObject};
+use crate::{
+    taiko::difficulty_object::{ObjectLists, TaikoDifficultyObject},
+    util::SortedPeaks,
+};
 
 use super::{colour::Colour, rhythm::Rhythm, stamina::Stamina, Skill, StrainSkill};
 
@@ -9,6 +10,7 @@ pub(crate) struct Peaks {
     colour: Colour,
     rhythm: Rhythm,
     stamina: Stamina,
+    combined_peaks: SortedPeaks,
 }
 
 impl Peaks {
@@ -23,15 +25,16 @@ impl Peaks {
             colour: Colour::new(),
             rhythm: Rhythm::new(),
             stamina: Stamina::new(),
+            combined_peaks: SortedPeaks::new(0.9),
         }
     }
 
-    pub(crate) fn difficulty_values(self) -> PeaksDifficultyValues {
-        let colour_rating = <Colour as StrainSkill>::difficulty_value(self.colour.clone())
+    pub(crate) fn difficulty_values(&mut self) -> PeaksDifficultyValues {
+        let colour_rating = <Colour as StrainSkill>::difficulty_value(&mut self.colour)
             * Self::COLOUR_SKILL_MULTIPLIER;
-        let rhythm_rating = <Rhythm as StrainSkill>::difficulty_value(self.rhythm.clone())
+        let rhythm_rating = <Rhythm as StrainSkill>::difficulty_value(&mut self.rhythm)
             * Self::RHYTHM_SKILL_MULTIPLIER;
-        let stamina_rating = <Stamina as StrainSkill>::difficulty_value(self.stamina.clone())
+        let stamina_rating = <Stamina as StrainSkill>::difficulty_value(&mut self.stamina)
             * Self::STAMINA_SKILL_MULTIPLIER;
 
         PeaksDifficultyValues {
@@ -42,14 +45,25 @@ impl Peaks {
         }
     }
 
-    pub(crate) fn into_raw(self) -> PeaksRaw {
+    pub(crate) fn into_raw(mut self) -> PeaksRaw {
         PeaksRaw {
+            curr_section_end: *self.colour.curr_section_end(),
             colour: self.colour.strain_peaks,
             rhythm: self.rhythm.strain_peaks,
             stamina: self.stamina.strain_peaks,
         }
     }
 
+    fn combined_peak(colour_peak: f64, rhythm_peak: f64, stamina_peak: f64) -> f64 {
+        let colour_peak = colour_peak * Self::COLOUR_SKILL_MULTIPLIER;
+        let rhythm_peak = rhythm_peak * Self::RHYTHM_SKILL_MULTIPLIER;
+        let stamina_peak = stamina_peak * Self::STAMINA_SKILL_MULTIPLIER;
+
+        let peak = Self::norm(1.5, [colour_peak, stamina_peak]);
+
+        Self::norm(2.0, [peak, rhythm_peak])
+    }
+
     fn norm(p: f64, values: impl IntoIterator<Item = f64>) -> f64 {
         values
             .into_iter()
@@ -64,47 +78,27 @@ impl Skill for Peaks {
         <Colour as Skill>::process(&mut self.colour, curr, hit_objects);
         <Rhythm as Skill>::process(&mut self.rhythm, curr, hit_objects);
         <Stamina as Skill>::process(&mut self.stamina, curr, hit_objects);
-    }
 
-    fn difficulty_value(self) -> f64 {
-        let mut peaks = Vec::new();
+        // All skills complete their sections at the same time
+        for i in self.combined_peaks.len()..self.colour.strain_peaks.len() {
+            let peak = Self::combined_peak(
+                self.colour.strain_peaks[i],
+                self.rhythm.strain_peaks[i],
+                self.stamina.strain_peaks[i],
+            );
 
-        let colour_peaks = self.colour.get_curr_strain_peaks();
-        let rhythm_peaks = self.rhythm.get_curr_strain_peaks();
-        let stamina_peaks = self.stamina.get_curr_strain_peaks();
-
-        let zip = colour_peaks
-            .into_iter()
-            .zip(rhythm_peaks)
-            .zip(stamina_peaks);
-
-        for ((mut colour_peak, mut rhythm_peak), mut stamina_peak) in zip {
-            colour_peak *= Self::COLOUR_SKILL_MULTIPLIER;
-            rhythm_peak *= Self::RHYTHM_SKILL_MULTIPLIER;
-            stamina_peak *= Self::STAMINA_SKILL_MULTIPLIER;
-
-            let mut peak = Self::norm(1.5, [colour_peak, stamina_peak]);
-            peak = Self::norm(2.0, [peak, rhythm_peak]);
-
-            // * Sections with 0 strain are excluded to avoid worst-case
-            // * time complexity of the following sort (e.g. /b/2351871).
-            // * These sections will not contribute to the difficulty.
-            if peak > 0.0 {
-                peaks.push(peak);
-            }
+            self.combined_peaks.push(peak);
         }
+    }
 
-        let mut difficulty = 0.0;
-        let mut weight = 1.0;
-
-        peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
-
-        for strain in peaks {
-            difficulty += strain * weight;
-            weight *= 0.9;
-        }
+    fn difficulty_value(&mut self) -> f64 {
+        let curr_peak = Self::combined_peak(
+            *self.colour.curr_section_peak(),
+            *self.rhythm.curr_section_peak(),
+            *self.stamina.curr_section_peak(),
+        );
 
-        difficulty
+        self.combined_peaks.weighted_sum(&[curr_peak], 0)
     }
 }
 
@@ -116,6 +110,7 @@ pub(crate) struct PeaksDifficultyValues {
 }
 
 pub(crate) struct PeaksRaw {
+    pub(crate) curr_section_end: f64,
     pub(crate) colour: Vec<f64>,
     pub(crate) rhythm: Vec<f64>,
     pub(crate) stamina: Vec<f64>,
@@ -1,6 +1,6 @@
 use crate::{
     taiko::difficulty_object::{HitObjectRhythm, ObjectLists, TaikoDifficultyObject},
-    util::LimitedQueue,
+    util::{LimitedQueue, SortedPeaks},
 };
 
 use super::{Skill, StrainDecaySkill, StrainSkill};
@@ -18,6 +18,7 @@ pub(crate) struct Rhythm {
     curr_section_peak: f64,
     curr_section_end: f64,
     pub(crate) strain_peaks: Vec<f64>,
+    sorted_peaks: SortedPeaks,
 }
 
 impl Rhythm {
@@ -32,6 +33,7 @@ impl Rhythm {
             curr_section_peak: 0.0,
             curr_section_end: 0.0,
             strain_peaks: Vec::new(),
+            sorted_peaks: SortedPeaks::new(Self::DECAY_WEIGHT),
         }
     }
 
@@ -106,7 +108,7 @@ impl Skill for Rhythm {
     }
 
     #[inline]
-    fn difficulty_value(self) -> f64 {
+    fn difficulty_value(&mut self) -> f64 {
         <Self as StrainSkill>::difficulty_value(self)
     }
 }
@@ -117,6 +119,16 @@ impl StrainSkill for Rhythm {
         &mut self.strain_peaks
     }
 
+    #[inline]
+    fn sorted_peaks(&self) -> &SortedPeaks {
+        &self.sorted_peaks
+    }
+
+    #[inline]
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks {
+        &mut self.sorted_peaks
+    }
+
     #[inline]
     fn curr_section_peak(&mut self) -> &mut f64 {
         &mut self.curr_section_peak
@@ -143,11 +155,13 @@ impl StrainDecaySkill for Rhythm {
     const STRAIN_DECAY_BASE: f64 = 0.0;
 
     #[inline]
+    #[allow(clippy::misnamed_getters)]
     fn curr_strain(&self) -> f64 {
         self.curr_decay_strain
     }
 
     #[inline]
+    #[allow(clippy::misnamed_getters)]
     fn curr_strain_mut(&mut self) -> &mut f64 {
         &mut self.curr_decay_strain
     }
@@ -1,4 +1,7 @@
-use crate::taiko::difficulty_object::{ObjectLists, TaikoDifficultyObject};
+use crate::{
+    taiko::difficulty_object::{ObjectLists, TaikoDifficultyObject},
+    util::SortedPeaks,
+};
 
 use super::{Skill, StrainDecaySkill, StrainSkill};
 
@@ -8,6 +11,7 @@ pub(crate) struct Stamina {
     curr_section_peak: f64,
     curr_section_end: f64,
     pub(crate) strain_peaks: Vec<f64>,
+    sorted_peaks: SortedPeaks,
 }
 
 impl Stamina {
@@ -17,6 +21,7 @@ impl Stamina {
             curr_section_peak: 0.0,
             curr_section_end: 0.0,
             strain_peaks: Vec::new(),
+            sorted_peaks: SortedPeaks::new(Self::DECAY_WEIGHT),
         }
     }
 }
@@ -28,7 +33,7 @@ impl Skill for Stamina {
     }
 
     #[inline]
-    fn difficulty_value(self) -> f64 {
+    fn difficulty_value(&mut self) -> f64 {
         <Self as StrainSkill>::difficulty_value(self)
     }
 }
@@ -39,6 +44,16 @@ impl StrainSkill for Stamina {
         &mut self.strain_peaks
     }
 
+    #[inline]
+    fn sorted_peaks(&self) -> &SortedPeaks {
+        &self.sorted_peaks
+    }
+
+    #[inline]
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks {
+        &mut self.sorted_peaks
+    }
+
     #[inline]
     fn curr_section_peak(&mut self) -> &mut f64 {
         &mut self.curr_section_peak
@@ -1,19 +1,22 @@
-use std::{cmp::Ordering, mem};
-
-use crate::taiko::{
-    difficulty_object::{ObjectLists, TaikoDifficultyObject},
-    SECTION_LEN,
+use crate::{
+    taiko::{
+        difficulty_object::{ObjectLists, TaikoDifficultyObject},
+        SECTION_LEN,
+    },
+    util::SortedPeaks,
 };
 
 pub(crate) trait Skill: Sized {
     fn process(&mut self, curr: &TaikoDifficultyObject, hit_objects: &ObjectLists);
-    fn difficulty_value(self) -> f64;
+    fn difficulty_value(&mut self) -> f64;
 }
 
 pub(crate) trait StrainSkill: Skill {
     const DECAY_WEIGHT: f64 = 0.9;
 
     fn strain_peaks_mut(&mut self) -> &mut Vec<f64>;
+    fn sorted_peaks(&self) -> &SortedPeaks;
+    fn sorted_peaks_mut(&mut self) -> &mut SortedPeaks;
     fn curr_section_peak(&mut self) -> &mut f64;
     fn curr_section_end(&mut self) -> &mut f64;
 
@@ -48,6 +51,7 @@ pub(crate) trait StrainSkill: Skill {
     fn save_curr_peak(&mut self) {
         let peak = *self.curr_section_peak();
         self.strain_peaks_mut().push(peak);
+        self.sorted_peaks_mut().push(peak);
     }
 
     #[inline]
@@ -58,34 +62,12 @@ pub(crate) trait StrainSkill: Skill {
         *self.curr_section_peak() = self.calculate_initial_strain(time, curr);
     }
 
-    fn difficulty_value(self) -> f64 {
-        let mut difficulty = 0.0;
-        let mut weight = 1.0;
-
-        // * Sections with 0 strain are excluded to avoid worst-case time complexity of the following sort (e.g. /b/2351871).
-        // * These sections will not contribute to the difficulty.
-        let mut peaks = self.get_curr_strain_peaks();
-
-        peaks.retain(|&peak| peak > 0.0);
-        peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    fn difficulty_value(&mut self) -> f64 {
+        let curr_peak = *self.curr_section_peak();
 
         // * Difficulty is the weighted sum of the highest strains from every section.
         // * We're sorting from highest to lowest strain.
-        for strain in peaks {
-            difficulty += strain * weight;
-            weight *= Self::DECAY_WEIGHT;
-        }
-
-        difficulty
-    }
-
-    #[inline]
-    fn get_curr_strain_peaks(mut self) -> Vec<f64> {
-        let curr_peak = *self.curr_section_peak();
-        let mut strain_peaks = mem::take(self.strain_peaks_mut());
-        strain_peaks.push(curr_peak);
-
-        strain_peaks
+        self.sorted_peaks().weighted_sum(&[curr_peak], 0)
     }
 }
 
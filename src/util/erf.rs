@@ -0,0 +1,47 @@
+/// Approximation of the error function with a maximum error of `1.5e-7`.
+///
+/// See Abramowitz and Stegun, formula 7.1.26.
+pub(crate) fn erf(x: f64) -> f64 {
+    const A1: f64 = 0.254_829_592;
+    const A2: f64 = -0.284_496_736;
+    const A3: f64 = 1.421_413_741;
+    const A4: f64 = -1.453_152_027;
+    const A5: f64 = 1.061_405_429;
+    const P: f64 = 0.327_591_1;
+
+    let sign = x.signum();
+    let x = x.abs();
+
+    let t = 1.0 / (1.0 + P * x);
+    let y = 1.0 - (((((A5 * t + A4) * t) + A3) * t + A2) * t + A1) * t * (-x * x).exp();
+
+    sign * y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::erf;
+
+    #[test]
+    fn erf_values() {
+        let values = [
+            (0.0, 0.0),
+            (0.5, 0.520_499_877_8),
+            (1.0, 0.842_700_792_9),
+            (2.0, 0.995_322_265_0),
+            (-1.0, -0.842_700_792_9),
+        ];
+
+        for (x, expected) in values {
+            let value = erf(x);
+
+            assert!(
+                (value - expected).abs() < 1e-6,
+                "expected {} for {}; got {}",
+                expected,
+                x,
+                value
+            );
+        }
+    }
+}
@@ -0,0 +1,122 @@
+/// Linearly interpolate between `start` and `end`.
+///
+/// An `amount` of `0.0` results in `start` and `1.0` in `end`.
+/// Values outside of that range extrapolate.
+///
+/// ```
+/// use akatsuki_pp::util::lerp;
+///
+/// assert_eq!(lerp(1.0, 10.0, 0.5), 5.5);
+/// assert_eq!(lerp(1.0, 10.0, 2.0), 19.0);
+/// ```
+#[inline]
+pub fn lerp(start: f64, end: f64, amount: f64) -> f64 {
+    start + (end - start) * amount
+}
+
+/// The inverse of [`lerp`], i.e. the amount at which `value` lies between `start` and `end`.
+///
+/// ```
+/// use akatsuki_pp::util::reverse_lerp;
+///
+/// assert_eq!(reverse_lerp(5.5, 1.0, 10.0), 0.5);
+/// ```
+#[inline]
+pub fn reverse_lerp(value: f64, start: f64, end: f64) -> f64 {
+    (value - start) / (end - start)
+}
+
+/// Restrict `value` to the interval `[min, max]`.
+///
+/// Unlike [`f64::clamp`], this does not panic if `min > max` or either bound is NaN.
+/// Instead, `min` takes precedence over `max` and a NaN bound is ignored. Note that
+/// C#'s `Math.Clamp` throws in the former case so this only matches osu! for valid bounds.
+///
+/// ```
+/// use akatsuki_pp::util::clamp;
+///
+/// assert_eq!(clamp(1.5, 0.0, 1.0), 1.0);
+/// assert_eq!(clamp(-0.5, 0.0, 1.0), 0.0);
+/// assert_eq!(clamp(0.5, 1.0, 0.0), 1.0);
+/// ```
+#[inline]
+pub fn clamp(value: f64, min: f64, max: f64) -> f64 {
+    value.min(max).max(min)
+}
+
+/// Smoothly interpolate from `0.0` to `1.0` while `x` goes from `start` to `end`.
+///
+/// Uses the cubic polynomial `3t² - 2t³` on the clamped progress `t`.
+///
+/// ```
+/// use akatsuki_pp::util::smoothstep;
+///
+/// assert_eq!(smoothstep(50.0, 0.0, 100.0), 0.5);
+/// assert_eq!(smoothstep(25.0, 0.0, 100.0), 0.15625);
+/// assert_eq!(smoothstep(150.0, 0.0, 100.0), 1.0);
+/// ```
+#[inline]
+pub fn smoothstep(x: f64, start: f64, end: f64) -> f64 {
+    let t = clamp(reverse_lerp(x, start, end), 0.0, 1.0);
+
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Like [`smoothstep`] but with vanishing first and second derivatives at the edges.
+///
+/// Uses the quintic polynomial `6t⁵ - 15t⁴ + 10t³` on the clamped progress `t`.
+///
+/// ```
+/// use akatsuki_pp::util::smootherstep;
+///
+/// assert_eq!(smootherstep(50.0, 0.0, 100.0), 0.5);
+/// assert_eq!(smootherstep(-10.0, 0.0, 100.0), 0.0);
+/// ```
+#[inline]
+pub fn smootherstep(x: f64, start: f64, end: f64) -> f64 {
+    let t = clamp(reverse_lerp(x, start, end), 0.0, 1.0);
+
+    t * t * t * (t * (6.0 * t - 15.0) + 10.0)
+}
+
+/// Map a difficulty value in `[0, 10]` such as AR or OD onto a range.
+///
+/// `min`, `mid`, and `max` are the results for a difficulty of 0, 5, and 10 respectively
+/// and values inbetween are interpolated linearly.
+///
+/// ```
+/// use akatsuki_pp::util::difficulty_range;
+///
+/// // AR to preempt time in milliseconds
+/// assert_eq!(difficulty_range(9.0, 1800.0, 1200.0, 450.0), 600.0);
+/// assert_eq!(difficulty_range(5.0, 1800.0, 1200.0, 450.0), 1200.0);
+/// assert_eq!(difficulty_range(0.0, 1800.0, 1200.0, 450.0), 1800.0);
+/// ```
+#[inline]
+pub fn difficulty_range(difficulty: f64, min: f64, mid: f64, max: f64) -> f64 {
+    if difficulty > 5.0 {
+        mid + (max - mid) * (difficulty - 5.0) / 5.0
+    } else if difficulty < 5.0 {
+        mid - (mid - min) * (5.0 - difficulty) / 5.0
+    } else {
+        mid
+    }
+}
+
+/// The inverse of [`difficulty_range`], i.e. the difficulty value that maps onto `value`.
+///
+/// ```
+/// use akatsuki_pp::util::inverse_difficulty_range;
+///
+/// // Preempt time in milliseconds to AR
+/// assert_eq!(inverse_difficulty_range(600.0, 1800.0, 1200.0, 450.0), 9.0);
+/// assert_eq!(inverse_difficulty_range(1500.0, 1800.0, 1200.0, 450.0), 2.5);
+/// ```
+#[inline]
+pub fn inverse_difficulty_range(value: f64, min: f64, mid: f64, max: f64) -> f64 {
+    if (value - mid).signum() == (max - mid).signum() {
+        (value - mid) / (max - mid) * 5.0 + 5.0
+    } else {
+        (value - mid) / (mid - min) * 5.0 + 5.0
+    }
+}
@@ -1,12 +1,70 @@
 mod byte_hasher;
+mod erf;
 mod float_ext;
 mod limited_queue;
+mod math;
+mod md5;
+mod sorted_peaks;
 mod sorted_vec;
+mod strain_summary;
 mod tandem_sort;
 
-pub use self::sorted_vec::SortedVec;
+pub use self::{
+    math::{
+        clamp, difficulty_range, inverse_difficulty_range, lerp, reverse_lerp, smootherstep,
+        smoothstep,
+    },
+    sorted_vec::SortedVec,
+    strain_summary::StrainSummary,
+};
 
 pub(crate) use self::{
-    byte_hasher::ByteHasher, float_ext::FloatExt, limited_queue::LimitedQueue,
+    byte_hasher::ByteHasher,
+    erf::erf,
+    float_ext::FloatExt,
+    limited_queue::LimitedQueue,
+    md5::{to_hex, Md5},
+    sorted_peaks::SortedPeaks,
     tandem_sort::TandemSorter,
 };
+
+/// Length of a strain section in the map's unmodified time.
+///
+/// Strain sections are measured in clock rate adjusted time so the
+/// length in terms of the map's own timestamps scales with the clock rate.
+#[inline]
+pub(crate) fn section_len_with_clock_rate(section_len: f64, clock_rate: f64) -> f64 {
+    section_len * clock_rate
+}
+
+/// Start of the first strain section in milliseconds in terms of the map's timestamps.
+///
+/// Saving a strain peak moves the end of the current section by one section
+/// so the first section can be restored from the amount of saved peaks.
+pub(crate) fn first_section_start(
+    curr_section_end: f64,
+    n_saved_peaks: usize,
+    section_len: f64,
+    clock_rate: f64,
+) -> f64 {
+    let first_section_end = curr_section_end - n_saved_peaks as f64 * section_len;
+
+    (first_section_end - section_len) * clock_rate
+}
+
+/// The highest strain peak alongside the start of its section in terms of the map's timestamps.
+///
+/// If multiple sections share the highest peak, the earliest one is picked.
+pub(crate) fn strain_peak(peaks: &[f64], start_time: f64, section_len: f64) -> Option<(f64, f64)> {
+    let (idx, peak) =
+        peaks
+            .iter()
+            .copied()
+            .enumerate()
+            .fold(None, |max: Option<(usize, f64)>, (i, peak)| match max {
+                Some((_, max_peak)) if max_peak >= peak => max,
+                _ => Some((i, peak)),
+            })?;
+
+    Some((start_time + idx as f64 * section_len, peak))
+}
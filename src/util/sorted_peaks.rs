@@ -0,0 +1,317 @@
+const NIL: usize = usize::MAX;
+
+/// Strain peaks of completed sections in descending order.
+///
+/// The peaks are stored in a treap whose nodes know the weighted sum of their
+/// subtree so that the weighted sum of all peaks plus a few extra values, e.g.
+/// the peak of the current section, can be evaluated in logarithmic time
+/// instead of sorting all peaks again every time.
+#[derive(Clone, Debug)]
+pub(crate) struct SortedPeaks {
+    decay_weight: f64,
+    /// `weights[i]` is the weight of the peak with rank `i`.
+    weights: Vec<f64>,
+    nodes: Vec<Node>,
+    root: usize,
+    /// The sum of all peaks in the order they were pushed.
+    sum: f64,
+    seed: u32,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    value: f64,
+    priority: u32,
+    left: usize,
+    right: usize,
+    /// Amount of nodes in this subtree.
+    len: usize,
+    /// Weighted sum of this subtree as if its first value had rank 0.
+    weighted: f64,
+}
+
+impl SortedPeaks {
+    pub(crate) fn new(decay_weight: f64) -> Self {
+        Self {
+            decay_weight,
+            weights: vec![1.0],
+            nodes: Vec::new(),
+            root: NIL,
+            sum: 0.0,
+            seed: 0x9E37_79B9,
+        }
+    }
+
+    pub(crate) fn from_peaks(peaks: &[f64], decay_weight: f64) -> Self {
+        let mut sorted = Self::new(decay_weight);
+        peaks.iter().for_each(|&peak| sorted.push(peak));
+
+        sorted
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len_of(self.root)
+    }
+
+    /// The sum of all peaks, regardless of their order.
+    pub(crate) fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    pub(crate) fn push(&mut self, peak: f64) {
+        self.sum += peak;
+
+        while self.weights.len() <= self.nodes.len() + 1 {
+            let weight = self.weights[self.weights.len() - 1] * self.decay_weight;
+            self.weights.push(weight);
+        }
+
+        // xorshift to keep the tree balanced while staying deterministic
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+
+        self.nodes.push(Node {
+            value: peak,
+            priority: self.seed,
+            left: NIL,
+            right: NIL,
+            len: 1,
+            weighted: peak,
+        });
+
+        let node = self.nodes.len() - 1;
+        let (greater, smaller) = self.split(self.root, peak);
+        let greater = self.merge(greater, node);
+        self.root = self.merge(greater, smaller);
+    }
+
+    /// The `n` highest peaks in descending order.
+    pub(crate) fn highest(&self, n: usize) -> Vec<f64> {
+        let mut highest = Vec::with_capacity(n);
+        self.collect_highest(self.root, n, &mut highest);
+
+        highest
+    }
+
+    /// The amount of peaks that are greater than `value`.
+    pub(crate) fn count_greater(&self, value: f64) -> usize {
+        let mut count = 0;
+        let mut curr = self.root;
+
+        while curr != NIL {
+            let node = &self.nodes[curr];
+
+            if node.value > value {
+                count += self.len_of(node.left) + 1;
+                curr = node.right;
+            } else {
+                curr = node.left;
+            }
+        }
+
+        count
+    }
+
+    /// The weighted sum of all peaks with `extra` sorted in and the first
+    /// `skip` peaks left out.
+    ///
+    /// `extra` must be in descending order.
+    pub(crate) fn weighted_sum(&self, extra: &[f64], skip: usize) -> f64 {
+        let len = self.len();
+        let skip = skip.min(len);
+        let mut start = skip;
+        let mut start_sum = self.prefix_sum(start);
+        let mut sum = 0.0;
+
+        for (i, &value) in extra.iter().enumerate() {
+            let end = self.count_greater(value).max(start);
+            let end_sum = self.prefix_sum(end);
+            sum += self.shifted(end_sum - start_sum, i as i32 - skip as i32);
+            sum += value * self.weight(end - skip + i);
+            start = end;
+            start_sum = end_sum;
+        }
+
+        let total = self.weighted_of(self.root);
+
+        sum + self.shifted(total - start_sum, extra.len() as i32 - skip as i32)
+    }
+
+    /// The weighted sum of the first `n` peaks.
+    fn prefix_sum(&self, mut n: usize) -> f64 {
+        let mut sum = 0.0;
+        let mut rank = 0;
+        let mut curr = self.root;
+
+        while curr != NIL && n > 0 {
+            let node = &self.nodes[curr];
+            let left_len = self.len_of(node.left);
+
+            if n <= left_len {
+                curr = node.left;
+            } else {
+                sum += self.weighted_of(node.left) * self.weight(rank);
+                sum += node.value * self.weight(rank + left_len);
+                rank += left_len + 1;
+                n -= left_len + 1;
+                curr = node.right;
+            }
+        }
+
+        sum
+    }
+
+    fn collect_highest(&self, curr: usize, n: usize, highest: &mut Vec<f64>) {
+        if curr == NIL || highest.len() >= n {
+            return;
+        }
+
+        let node = &self.nodes[curr];
+        self.collect_highest(node.left, n, highest);
+
+        if highest.len() < n {
+            highest.push(node.value);
+            self.collect_highest(node.right, n, highest);
+        }
+    }
+
+    /// Split the subtree into peaks that are at least `value` and peaks that
+    /// are smaller than `value`.
+    fn split(&mut self, curr: usize, value: f64) -> (usize, usize) {
+        if curr == NIL {
+            return (NIL, NIL);
+        }
+
+        if self.nodes[curr].value >= value {
+            let (left, right) = self.split(self.nodes[curr].right, value);
+            self.nodes[curr].right = left;
+            self.update(curr);
+
+            (curr, right)
+        } else {
+            let (left, right) = self.split(self.nodes[curr].left, value);
+            self.nodes[curr].left = right;
+            self.update(curr);
+
+            (left, curr)
+        }
+    }
+
+    fn merge(&mut self, left: usize, right: usize) -> usize {
+        if left == NIL {
+            return right;
+        } else if right == NIL {
+            return left;
+        }
+
+        if self.nodes[left].priority > self.nodes[right].priority {
+            let merged = self.merge(self.nodes[left].right, right);
+            self.nodes[left].right = merged;
+            self.update(left);
+
+            left
+        } else {
+            let merged = self.merge(left, self.nodes[right].left);
+            self.nodes[right].left = merged;
+            self.update(right);
+
+            right
+        }
+    }
+
+    fn update(&mut self, curr: usize) {
+        let Node {
+            value,
+            priority: _,
+            left,
+            right,
+            len: _,
+            weighted: _,
+        } = self.nodes[curr];
+
+        let left_len = self.len_of(left);
+        let right_len = self.len_of(right);
+
+        let weighted = self.weighted_of(left)
+            + value * self.weight(left_len)
+            + self.weighted_of(right) * self.weight(left_len + 1);
+
+        let node = &mut self.nodes[curr];
+        node.len = left_len + 1 + right_len;
+        node.weighted = weighted;
+    }
+
+    fn len_of(&self, curr: usize) -> usize {
+        self.nodes.get(curr).map_or(0, |node| node.len)
+    }
+
+    fn weighted_of(&self, curr: usize) -> f64 {
+        self.nodes.get(curr).map_or(0.0, |node| node.weighted)
+    }
+
+    fn shifted(&self, sum: f64, shift: i32) -> f64 {
+        if shift == 0 {
+            sum
+        } else {
+            sum * self.decay_weight.powi(shift)
+        }
+    }
+
+    fn weight(&self, rank: usize) -> f64 {
+        match self.weights.get(rank) {
+            Some(&weight) => weight,
+            None => self.decay_weight.powi(rank as i32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SortedPeaks;
+
+    fn naive_weighted_sum(mut peaks: Vec<f64>, decay_weight: f64) -> f64 {
+        peaks.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap());
+
+        peaks
+            .iter()
+            .enumerate()
+            .map(|(i, peak)| peak * decay_weight.powi(i as i32))
+            .sum()
+    }
+
+    #[test]
+    fn weighted_sum_with_extra() {
+        let peaks: Vec<_> = (0..500).map(|i| ((i * 7919) % 263) as f64).collect();
+        let sorted = SortedPeaks::from_peaks(&peaks, 0.9);
+
+        assert_eq!(sorted.len(), peaks.len());
+        assert_eq!(sorted.highest(3), vec![262.0, 262.0, 261.0]);
+        assert_eq!(sorted.count_greater(261.0), 2);
+
+        let mut with_extra = peaks.clone();
+        with_extra.extend([300.0, 100.5, 0.5]);
+        let expected = naive_weighted_sum(with_extra, 0.9);
+        let actual = sorted.weighted_sum(&[300.0, 100.5, 0.5], 0);
+        assert!(
+            (expected - actual).abs() < 1e-10,
+            "{} vs {}",
+            expected,
+            actual
+        );
+
+        let mut highest = sorted.highest(2);
+        highest.iter_mut().for_each(|peak| *peak *= 0.5);
+        let mut skipped = peaks[..].to_vec();
+        skipped.sort_unstable_by(|a, b| b.partial_cmp(a).unwrap());
+        skipped.splice(..2, highest.iter().copied());
+        let expected = naive_weighted_sum(skipped, 0.9);
+        let actual = sorted.weighted_sum(&highest, 2);
+        assert!(
+            (expected - actual).abs() < 1e-10,
+            "{} vs {}",
+            expected,
+            actual
+        );
+    }
+}
@@ -0,0 +1,60 @@
+/// Summary statistics of the strain peaks of a skill.
+///
+/// Just like for the difficulty calculation, sections with 0 strain
+/// such as breaks are not considered.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct StrainSummary {
+    /// The mean of the strain peaks.
+    pub mean: f64,
+    /// The population variance of the strain peaks.
+    pub variance: f64,
+    /// The highest strain peak.
+    pub max: f64,
+}
+
+impl StrainSummary {
+    /// Summarize the given strain peaks.
+    pub fn new(peaks: &[f64]) -> Self {
+        let non_zero = || peaks.iter().copied().filter(|&peak| peak > 0.0);
+
+        let (sum, count, max) = non_zero().fold((0.0, 0, 0.0_f64), |(sum, count, max), peak| {
+            (sum + peak, count + 1, max.max(peak))
+        });
+
+        if count == 0 {
+            return Self::default();
+        }
+
+        let mean = sum / count as f64;
+
+        let variance = non_zero()
+            .map(|peak| (peak - mean) * (peak - mean))
+            .sum::<f64>()
+            / count as f64;
+
+        Self {
+            mean,
+            variance,
+            max,
+        }
+    }
+
+    /// The standard deviation of the strain peaks.
+    #[inline]
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    /// The standard deviation of the strain peaks relative to their mean.
+    ///
+    /// Close to 0 for maps with consistent difficulty and larger
+    /// the more the difficulty is focused on spikes.
+    #[inline]
+    pub fn spikiness(&self) -> f64 {
+        if self.mean > 0.0 {
+            self.std_dev() / self.mean
+        } else {
+            0.0
+        }
+    }
+}
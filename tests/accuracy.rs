@@ -0,0 +1,31 @@
+use akatsuki_pp::Accuracy;
+
+#[test]
+fn from_fraction() {
+    let acc = Accuracy::from_fraction(0.985).unwrap();
+    assert!((acc.fraction() - 0.985).abs() <= f64::EPSILON);
+    assert!((acc.percent() - 98.5).abs() < 1e-10);
+
+    assert!(Accuracy::from_fraction(0.0).is_some());
+    assert!(Accuracy::from_fraction(1.0).is_some());
+
+    // the classic mistake of passing a percentage
+    assert!(Accuracy::from_fraction(98.5).is_none());
+    assert!(Accuracy::from_fraction(-0.1).is_none());
+    assert!(Accuracy::from_fraction(f64::NAN).is_none());
+}
+
+#[test]
+fn from_percent() {
+    let acc = Accuracy::from_percent(98.5).unwrap();
+    assert!((acc.fraction() - 0.985).abs() <= f64::EPSILON);
+    assert!((acc.percent() - 98.5).abs() < 1e-10);
+
+    assert!(Accuracy::from_percent(0.0).is_some());
+    assert!(Accuracy::from_percent(100.0).is_some());
+
+    assert!(Accuracy::from_percent(100.5).is_none());
+    assert!(Accuracy::from_percent(-1.0).is_none());
+    assert!(Accuracy::from_percent(f64::INFINITY).is_none());
+    assert!(Accuracy::from_percent(f64::NAN).is_none());
+}
@@ -0,0 +1,792 @@
+#![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+
+use akatsuki_pp::{
+    beatmap::TimingPoint,
+    catch::{CatchDifficultyParams, CatchGradualDifficultyAttributes},
+    mania::{ManiaDifficultyParams, ManiaGradualDifficultyAttributes},
+    osu::{OsuDifficultyAttributes, OsuDifficultyParams, OsuPerformanceAttributes},
+    osu_2019,
+    parse::{HitObject, HitObjectKind, Pos2},
+    Accuracy, AnyPP, AnyStars, Beatmap, BeatmapBuilder, BeatmapExt, CatchPP, CatchStars,
+    DifficultyAttributes, GameMode, ManiaPP, ManiaStars, Mods, OsuPP, OsuStars,
+    PerformanceAttributes, Precision, Strains, TaikoPP, TaikoStars,
+};
+
+use crate::common::{Catch, Mania, Osu, Taiko};
+
+mod common;
+
+#[test]
+fn performance_max_combo_eq_difficulty() {
+    let map = test_map!(Osu);
+    let attrs = OsuPP::new(&map).calculate();
+    assert_eq!(attrs.max_combo(), attrs.difficulty.max_combo);
+
+    let attrs = osu_2019::OsuPP::new(&map).calculate();
+    assert_eq!(attrs.max_combo(), attrs.difficulty.max_combo);
+
+    let map = test_map!(Taiko);
+    let attrs = TaikoPP::new(&map).calculate();
+    assert_eq!(attrs.max_combo(), attrs.difficulty.max_combo);
+
+    let map = test_map!(Catch);
+    let attrs = CatchPP::new(&map).calculate();
+    assert_eq!(attrs.max_combo(), attrs.difficulty.max_combo());
+
+    let map = test_map!(Mania);
+    let attrs = ManiaPP::new(&map).calculate();
+    assert_eq!(attrs.max_combo(), attrs.difficulty.max_combo);
+
+    for map in [
+        test_map!(Osu),
+        test_map!(Taiko),
+        test_map!(Catch),
+        test_map!(Mania),
+    ] {
+        let attrs = map.max_pp(0);
+        assert_eq!(attrs.max_combo(), attrs.difficulty_attributes().max_combo());
+    }
+}
+
+#[test]
+fn section_stars_last_eq_stars() {
+    let map = test_map!(Osu);
+
+    for mods in [0, 8 + 64, 16 + 1024, 8192] {
+        let stars = OsuStars::new(&map).mods(mods).calculate().stars;
+        let section_stars = OsuStars::new(&map).mods(mods).section_stars();
+        let strains = OsuStars::new(&map).mods(mods).strains();
+
+        // One value per strain peak plus one for the final section
+        assert_eq!(section_stars.len(), strains.len() + 1);
+
+        // The section of the first peak ends at the same time for both
+        let first_end = strains.start_time + strains.section_len * strains.clock_rate;
+        let passed = map
+            .hit_objects
+            .iter()
+            .take_while(|h| h.start_time <= first_end)
+            .count();
+        let first = OsuStars::new(&map)
+            .mods(mods)
+            .passed_objects(passed)
+            .calculate()
+            .stars;
+        assert!(
+            (section_stars[0] - first).abs() < 1e-10,
+            "{} != {}",
+            section_stars[0],
+            first
+        );
+
+        let last = *section_stars.last().unwrap();
+        assert!(
+            (last - stars).abs() < 1e-10,
+            "last section stars {} != stars {} (mods {})",
+            last,
+            stars,
+            mods
+        );
+    }
+}
+
+#[test]
+fn section_stars_respect_options() {
+    let mut map = test_map!(Osu);
+
+    let end_time = map.hit_objects[51].start_time - 1.0;
+    map.hit_objects[50].kind = HitObjectKind::Spinner { end_time };
+
+    let params = OsuDifficultyParams {
+        aim_weight: 1.5,
+        ..Default::default()
+    };
+
+    let stars = || {
+        OsuStars::new(&map)
+            .mods(64)
+            .ignore_spinners(true)
+            .precision(Precision::Single)
+            .params(params)
+    };
+
+    let expected = stars().calculate().stars;
+    let section_stars = stars().section_stars();
+    let last = *section_stars.last().unwrap();
+
+    assert!(
+        (last - expected).abs() <= f64::EPSILON,
+        "{} != {}",
+        last,
+        expected
+    );
+    assert!((last - OsuStars::new(&map).mods(64).calculate().stars).abs() > f64::EPSILON);
+
+    // Passed objects still count the ignored spinner
+    let passed = stars().passed_objects(100);
+    let expected = passed.clone().calculate().stars;
+    let last = *passed.section_stars().last().unwrap();
+
+    assert!(
+        (last - expected).abs() <= f64::EPSILON,
+        "{} != {}",
+        last,
+        expected
+    );
+}
+
+#[test]
+fn section_len_with_clock_rate() {
+    let clock_rate = 64.clock_rate();
+
+    let osu = test_map!(Osu).strains(64);
+    let taiko = test_map!(Taiko).strains(64);
+    let catch = test_map!(Catch).strains(64);
+    let mania = test_map!(Mania).strains(64);
+
+    for strains in [&osu, &taiko, &catch, &mania] {
+        assert_eq!(
+            strains.section_len_with_clock_rate(clock_rate),
+            strains.section_len() * clock_rate
+        );
+    }
+
+    let osu_len = osu.section_len_with_clock_rate(clock_rate);
+    assert_eq!(osu_len, 600.0);
+    assert_eq!(taiko.section_len_with_clock_rate(clock_rate), osu_len);
+    assert_eq!(mania.section_len_with_clock_rate(clock_rate), osu_len);
+    assert_eq!(catch.section_len_with_clock_rate(clock_rate), 1125.0);
+
+    if let Strains::Osu(strains) = osu {
+        assert_eq!(strains.section_len_with_clock_rate(clock_rate), osu_len);
+    }
+}
+
+#[test]
+fn double_star_scaling_factor() {
+    let map = test_map!(Mania);
+    let stars = ManiaStars::new(&map).calculate().stars;
+
+    let params = ManiaDifficultyParams {
+        star_scaling_factor: 2.0 * ManiaDifficultyParams::default().star_scaling_factor,
+    };
+
+    let doubled = ManiaStars::new(&map).params(params).calculate().stars;
+    assert!((doubled - 2.0 * stars).abs() < 1e-10);
+
+    let map = test_map!(Catch);
+    let stars = CatchStars::new(&map).calculate().stars;
+
+    let params = CatchDifficultyParams {
+        star_scaling_factor: 2.0 * CatchDifficultyParams::default().star_scaling_factor,
+    };
+
+    let doubled = CatchStars::new(&map).params(params).calculate().stars;
+    assert!((doubled - 2.0 * stars).abs() < 1e-10);
+
+    let gradual = CatchGradualDifficultyAttributes::with_params(&map, 0, params)
+        .last()
+        .unwrap();
+    assert!((gradual.stars - doubled).abs() < 1e-10);
+
+    let map = test_map!(Mania);
+    let params = ManiaDifficultyParams {
+        star_scaling_factor: 2.0 * ManiaDifficultyParams::default().star_scaling_factor,
+    };
+
+    let doubled = ManiaStars::new(&map).params(params).calculate().stars;
+    let gradual = ManiaGradualDifficultyAttributes::with_params(&map, 0, params)
+        .last()
+        .unwrap();
+    assert!((gradual.stars - doubled).abs() < 1e-10);
+}
+
+#[test]
+fn osu_skill_weights() {
+    let map = test_map!(Osu);
+    let attrs = OsuStars::new(&map).calculate();
+
+    let default = OsuStars::new(&map)
+        .params(OsuDifficultyParams::default())
+        .calculate();
+    assert_eq!(default, attrs);
+
+    // Star rating without the constant offset
+    let scale = 1.14_f64.cbrt() * 0.027;
+    let raw = |stars: f64| stars / scale - 4.0;
+
+    // The norm is homogeneous so doubling all weights doubles the
+    // combined performance which scales the stars by its cube root
+    let params = OsuDifficultyParams {
+        aim_weight: 2.0,
+        speed_weight: 2.0,
+        ..Default::default()
+    };
+
+    let doubled = OsuStars::new(&map).params(params).calculate();
+    assert!((doubled.aim - attrs.aim).abs() <= f64::EPSILON);
+    assert!((doubled.speed - attrs.speed).abs() <= f64::EPSILON);
+    assert!((raw(doubled.stars) - 2.0_f64.cbrt() * raw(attrs.stars)).abs() < 1e-10);
+
+    let aim_heavy = OsuDifficultyParams {
+        aim_weight: 1.5,
+        ..Default::default()
+    };
+
+    let speed_heavy = OsuDifficultyParams {
+        speed_weight: 1.5,
+        ..Default::default()
+    };
+
+    let aim_heavy = OsuStars::new(&map).params(aim_heavy).calculate().stars;
+    let speed_heavy = OsuStars::new(&map).params(speed_heavy).calculate().stars;
+
+    assert!(aim_heavy > attrs.stars && speed_heavy > attrs.stars);
+
+    // Weighting the stronger skill affects the stars more
+    assert_eq!(attrs.aim > attrs.speed, aim_heavy > speed_heavy);
+}
+
+#[test]
+fn object_counts() {
+    let map = test_map!(Osu);
+    let counts = map.object_counts();
+
+    assert!(counts.circles > 0 && counts.sliders > 0 && counts.spinners > 0);
+    assert_eq!(counts.total() as usize, map.hit_objects.len());
+
+    let attrs = OsuStars::new(&map).calculate();
+    assert_eq!(counts.circles as usize, attrs.n_circles);
+    assert_eq!(counts.sliders as usize, attrs.n_sliders);
+    assert_eq!(counts.spinners as usize, attrs.n_spinners);
+}
+
+#[test]
+fn count_objects_by_kind() {
+    let map = test_map!(Osu);
+    let counts = map.count_objects_by_kind();
+
+    assert_eq!(counts, map.object_counts());
+    assert_eq!(counts.holds, 0);
+    assert_eq!(counts.total() as usize, map.hit_objects.len());
+
+    let map = test_map!(Mania);
+    let counts = map.count_objects_by_kind();
+
+    assert!(counts.circles > 0 && counts.holds > 0);
+    assert_eq!(counts.sliders + counts.spinners, 0);
+    assert_eq!(counts, map.object_counts());
+}
+
+#[test]
+fn combined_stars_eq_stars() {
+    let map = test_map!(Osu);
+
+    for mods in [0, 8 + 64, 16 + 1024, 128, 8192] {
+        let attrs = OsuStars::new(&map).mods(mods).calculate();
+
+        assert!(
+            (attrs.combined_stars(mods) - attrs.stars).abs() <= f64::EPSILON,
+            "{} != {} (mods {})",
+            attrs.combined_stars(mods),
+            attrs.stars,
+            mods
+        );
+    }
+}
+
+#[test]
+fn windowed_stars_flat_map() {
+    let mut content = String::from(
+        "osu file format v14\n\n[General]\nMode: 0\n\n[Difficulty]\nHPDrainRate:5\nCircleSize:4\n\
+         OverallDifficulty:8\nApproachRate:9\nSliderMultiplier:1.4\nSliderTickRate:1\n\n\
+         [TimingPoints]\n0,300,4,2,0,100,1,0\n\n[HitObjects]\n",
+    );
+
+    for i in 0..400 {
+        let x = if i % 2 == 0 { 100 } else { 400 };
+        content.push_str(&format!("{},192,{},1,0,0:0:0:0:\n", x, 1000 + i * 150));
+    }
+
+    let map = Beatmap::from_bytes(content.as_bytes()).unwrap();
+    let windows: Vec<_> = OsuStars::new(&map).windowed_stars(5000.0).collect();
+
+    assert!(windows.windows(2).all(|w| w[0].0 < w[1].0));
+
+    // Skip windows that include the strain build-up at the start
+    let stars: Vec<_> = windows.iter().skip(15).map(|(_, stars)| *stars).collect();
+    let min = stars.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = stars.iter().copied().fold(0.0, f64::max);
+
+    assert!(max - min < 0.05 * max, "min={} max={}", min, max);
+
+    let full = OsuStars::new(&map).calculate().stars;
+    let whole: Vec<_> = OsuStars::new(&map).windowed_stars(f64::MAX).collect();
+
+    assert_eq!(whole.len(), 1);
+    assert!(
+        (whole[0].1 - full).abs() < 1e-10,
+        "{} != {}",
+        whole[0].1,
+        full
+    );
+}
+
+#[test]
+fn mania_forced_key_count() {
+    let mut map = test_map!(Mania);
+    let stars = ManiaStars::new(&map).calculate().stars;
+
+    assert!((map.cs - 7.0).abs() > f32::EPSILON);
+
+    let forced = ManiaStars::new(&map).key_count(7).calculate().stars;
+
+    // Fewer columns than the map's 4K merge columns and change the strain
+    let merged = ManiaStars::new(&map).key_count(2).calculate().stars;
+    assert!((merged - stars).abs() > f64::EPSILON);
+
+    map.cs = 7.0;
+    let seven_k = ManiaStars::new(&map).calculate().stars;
+    assert!((forced - seven_k).abs() <= f64::EPSILON);
+
+    let clamped = ManiaStars::new(&map).key_count(50).calculate().stars;
+    let eighteen_k = ManiaStars::new(&map).key_count(18).calculate().stars;
+    assert!((clamped - eighteen_k).abs() <= f64::EPSILON);
+}
+
+#[test]
+fn strains_with_section_len() {
+    let map = test_map!(Osu);
+
+    let default = OsuStars::new(&map).strains();
+    let explicit = OsuStars::new(&map).strains_with_section_len(400.0);
+    let fine = OsuStars::new(&map).strains_with_section_len(100.0);
+
+    assert_eq!(default.aim, explicit.aim);
+    assert!((fine.section_len - 100.0).abs() <= f64::EPSILON);
+
+    let (fine_len, default_len) = (fine.len(), default.len());
+    assert!(fine_len > default_len, "{} <= {}", fine_len, default_len);
+}
+
+#[test]
+fn merge_difficulty_attributes() {
+    let mut easy = OsuDifficultyAttributes::default();
+    easy.aim = 1.5;
+    easy.speed = 1.2;
+    easy.n_circles = 100;
+    easy.n_sliders = 50;
+    easy.n_spinners = 1;
+    easy.stars = 3.0;
+    easy.max_combo = 250;
+
+    let mut hard = OsuDifficultyAttributes::default();
+    hard.aim = 3.0;
+    hard.speed = 2.5;
+    hard.n_circles = 300;
+    hard.n_sliders = 200;
+    hard.n_spinners = 2;
+    hard.stars = 6.0;
+    hard.max_combo = 900;
+
+    let mut expected = hard.clone();
+    expected.n_circles = 400;
+    expected.n_sliders = 250;
+    expected.n_spinners = 3;
+    expected.max_combo = 1150;
+
+    let easy = DifficultyAttributes::Osu(easy);
+    let hard = DifficultyAttributes::Osu(hard);
+
+    for merged in [easy.merge(&hard), hard.merge(&easy)] {
+        match merged {
+            Some(DifficultyAttributes::Osu(attrs)) => assert_eq!(attrs, expected),
+            other => panic!("expected osu attributes, got {:?}", other),
+        }
+    }
+
+    let mania = DifficultyAttributes::Mania(Default::default());
+    assert!(easy.merge(&mania).is_none());
+}
+
+#[test]
+fn performance_attributes_new() {
+    let mut difficulty = OsuDifficultyAttributes::default();
+    difficulty.stars = 5.5;
+    difficulty.max_combo = 700;
+
+    let attrs = OsuPerformanceAttributes::new(difficulty, 250.0);
+
+    assert!((attrs.pp() - 250.0).abs() <= f64::EPSILON);
+    assert!((attrs.stars() - 5.5).abs() <= f64::EPSILON);
+    assert_eq!(attrs.max_combo(), 700);
+    assert!(attrs.pp_aim.abs() <= f64::EPSILON);
+}
+
+#[test]
+fn lazer_max_combo() {
+    for map in [test_map!(Osu), test_map!(Taiko), test_map!(Catch)] {
+        let stable = AnyStars::new(&map).calculate().max_combo();
+        let lazer = AnyStars::new(&map).lazer(true).calculate().max_combo();
+
+        assert_eq!(stable, lazer, "{:?}", map.mode);
+    }
+
+    let map = test_map!(Mania);
+    let stable = AnyStars::new(&map).calculate().max_combo();
+    let lazer = AnyStars::new(&map).lazer(true).calculate().max_combo();
+
+    assert!(lazer < stable, "{} >= {}", lazer, stable);
+}
+
+#[test]
+fn performance_attributes_variants() {
+    fn check(map: &Beatmap, mode: GameMode) {
+        let attrs = AnyPP::new(map).mode(mode).calculate();
+
+        let (variant, pp, stars, max_combo) = match &attrs {
+            PerformanceAttributes::Osu(a) => (GameMode::Osu, a.pp, a.stars(), a.max_combo()),
+            PerformanceAttributes::Taiko(a) => (GameMode::Taiko, a.pp, a.stars(), a.max_combo()),
+            PerformanceAttributes::Catch(a) => (GameMode::Catch, a.pp, a.stars(), a.max_combo()),
+            PerformanceAttributes::Mania(a) => (GameMode::Mania, a.pp, a.stars(), a.max_combo()),
+        };
+
+        assert_eq!(variant, mode);
+        assert!((attrs.pp() - pp).abs() <= f64::EPSILON);
+        assert!((attrs.stars() - stars).abs() <= f64::EPSILON);
+        assert_eq!(attrs.max_combo(), max_combo);
+        assert_eq!(DifficultyAttributes::from(attrs).max_combo(), max_combo);
+    }
+
+    let map = test_map!(Osu);
+
+    for mode in [
+        GameMode::Osu,
+        GameMode::Taiko,
+        GameMode::Catch,
+        GameMode::Mania,
+    ] {
+        check(&map, mode);
+    }
+
+    let osu = OsuPP::new(&map).calculate();
+    let any = PerformanceAttributes::from(osu.clone());
+    assert!(matches!(any, PerformanceAttributes::Osu(ref attrs) if attrs == &osu));
+}
+
+#[test]
+fn calculate_all_modes() {
+    let map = test_map!(Osu);
+    let all = map.calculate_all(8 + 64);
+
+    assert_eq!(all.osu, Some(OsuStars::new(&map).mods(8 + 64).calculate()));
+    assert_eq!(
+        all.taiko,
+        Some(TaikoStars::new(&map).mods(8 + 64).calculate())
+    );
+    assert_eq!(
+        all.catch,
+        Some(CatchStars::new(&map).mods(8 + 64).calculate())
+    );
+    assert_eq!(
+        all.mania,
+        Some(ManiaStars::new(&map).mods(8 + 64).calculate())
+    );
+
+    let map = test_map!(Mania);
+    let all = map.calculate_all(0);
+
+    assert!(all.osu.is_none() && all.taiko.is_none() && all.catch.is_none());
+    assert_eq!(all.mania, Some(ManiaStars::new(&map).calculate()));
+}
+
+#[test]
+fn common_mods_table() {
+    let map = test_map!(Osu);
+    let table = map.common_mods_table(GameMode::Osu);
+
+    assert_eq!(table.len(), 9);
+
+    let (nm, nm_attrs) = &table[0];
+    let plain = OsuStars::new(&map).calculate();
+
+    assert_eq!(*nm, 0);
+    assert!((nm_attrs.stars() - plain.stars).abs() <= f64::EPSILON);
+    assert_eq!(nm_attrs.max_combo(), plain.max_combo);
+
+    let (_, hddt) = table.iter().find(|(mods, _)| *mods == 8 + 64).unwrap();
+    let expected = OsuStars::new(&map).mods(8 + 64).calculate().stars;
+    assert!((hddt.stars() - expected).abs() <= f64::EPSILON);
+
+    // converts and HD sharing the nomod difficulty outside of osu!standard
+    let table = map.common_mods_table(GameMode::Taiko);
+    let plain = TaikoStars::new(&map).calculate().stars;
+
+    assert!(matches!(table[0].1, DifficultyAttributes::Taiko(_)));
+    assert!((table[0].1.stars() - plain).abs() <= f64::EPSILON);
+    assert!((table[3].1.stars() - plain).abs() <= f64::EPSILON);
+
+    // other modes ignore the given mode
+    let map = test_map!(Mania);
+    let table = map.common_mods_table(GameMode::Osu);
+
+    assert!(table
+        .iter()
+        .all(|(_, attrs)| matches!(attrs, DifficultyAttributes::Mania(_))));
+}
+
+#[test]
+fn relax_suppresses_speed_strain() {
+    let map = test_map!(Osu);
+
+    let vanilla = OsuStars::new(&map).calculate();
+    let relax = OsuStars::new(&map).mods(128).calculate();
+
+    assert!((relax.stars - vanilla.stars).abs() > f64::EPSILON);
+    assert!(relax.speed.abs() <= f64::EPSILON);
+    assert!(relax.speed_note_count.abs() <= f64::EPSILON);
+    assert!((relax.aim - 0.9 * vanilla.aim).abs() < 1e-10);
+
+    let strains = OsuStars::new(&map).mods(128).strains();
+    assert!(!strains.speed.is_empty());
+    assert!(strains
+        .speed
+        .iter()
+        .all(|strain| strain.abs() <= f64::EPSILON));
+    assert!(strains.aim.iter().any(|strain| *strain > 0.0));
+
+    let relax_strains = OsuStars::new(&map).relax_strains();
+    let vanilla_strains = OsuStars::new(&map).strains();
+    assert_eq!(relax_strains.speed.len(), vanilla_strains.speed.len());
+    assert!(vanilla_strains.speed.iter().any(|strain| *strain > 0.0));
+    assert!(relax_strains
+        .speed
+        .iter()
+        .all(|strain| strain.abs() <= f64::EPSILON));
+    assert_eq!(relax_strains.aim, vanilla_strains.aim);
+    assert_eq!(relax_strains.speed, strains.speed);
+}
+
+#[test]
+fn ignore_spinners() {
+    let map = test_map!(Osu);
+    let regular = OsuStars::new(&map).calculate();
+    let ignored = OsuStars::new(&map).ignore_spinners(true).calculate();
+
+    assert!(regular.n_spinners > 0);
+    assert_eq!(ignored.n_spinners, 0);
+    assert_eq!(ignored.n_circles, regular.n_circles);
+    assert_eq!(ignored.n_sliders, regular.n_sliders);
+    assert_eq!(ignored.max_combo, regular.max_combo);
+    assert!(ignored.stars.is_finite());
+
+    let explicit = OsuStars::new(&map).ignore_spinners(false).calculate();
+    assert_eq!(explicit, regular);
+}
+
+#[test]
+fn attributes_diff() {
+    let map = test_map!(Osu);
+    let nomod = DifficultyAttributes::Osu(OsuStars::new(&map).calculate());
+    let hr = DifficultyAttributes::Osu(OsuStars::new(&map).mods(16).calculate());
+
+    let diff = nomod.diff(&hr).unwrap();
+    assert_eq!(diff.fields.len(), 15);
+
+    let stars = diff.get("stars").unwrap();
+    assert!((stars.old - nomod.stars()).abs() <= f64::EPSILON);
+    assert!((stars.new - hr.stars()).abs() <= f64::EPSILON);
+    assert!(stars.delta() > 0.0);
+
+    // HR changes the map's settings but not its objects
+    let changed: Vec<_> = diff.changed(1e-9).map(|field| field.name).collect();
+    assert!(changed.contains(&"ar"));
+    assert!(changed.contains(&"od"));
+    assert!(!changed.contains(&"n_circles"));
+    assert!(!changed.contains(&"max_combo"));
+
+    assert!(nomod.diff(&nomod).unwrap().is_equal(0.0));
+
+    let mania = DifficultyAttributes::Mania(ManiaStars::new(&test_map!(Mania)).calculate());
+    assert!(nomod.diff(&mania).is_none());
+}
+
+#[test]
+fn taiko_hit_window_without_rate_adjustment() {
+    let map = test_map!(Taiko);
+
+    let nomod = TaikoStars::new(&map).calculate();
+    let dt = TaikoStars::new(&map).mods(64).calculate();
+    let isolated = TaikoStars::new(&map)
+        .mods(64)
+        .rate_adjusted_hit_window(false)
+        .calculate();
+
+    assert!(dt.hit_window < nomod.hit_window);
+    assert_eq!(isolated.hit_window, nomod.hit_window);
+    assert_eq!(isolated.stars, dt.stars);
+}
+
+#[test]
+fn osu_hit_window_without_rate_adjustment() {
+    let map = test_map!(Osu);
+
+    let nomod = OsuStars::new(&map).calculate();
+    let dt = OsuStars::new(&map).mods(64).calculate();
+    let isolated = OsuStars::new(&map)
+        .mods(64)
+        .rate_adjusted_hit_window(false)
+        .calculate();
+
+    assert!(dt.od > nomod.od);
+    assert!((isolated.od - nomod.od).abs() <= f64::EPSILON);
+
+    // The clock rate still applies to the timing of hit objects
+    assert!((isolated.aim - dt.aim).abs() <= f64::EPSILON);
+    assert!(isolated.aim > nomod.aim);
+
+    let dt_pp = OsuPP::new(&map).mods(64).calculate();
+    let isolated_pp = OsuPP::new(&map)
+        .mods(64)
+        .rate_adjusted_hit_window(false)
+        .calculate();
+
+    assert_eq!(isolated_pp.difficulty, isolated);
+    assert!(isolated_pp.pp_acc < dt_pp.pp_acc);
+}
+
+#[test]
+fn catcher_size_by_cs() {
+    let mut map = test_map!(Catch);
+
+    let sizes: Vec<_> = [2.0, 5.0, 8.0]
+        .iter()
+        .map(|&cs| {
+            map.cs = cs;
+            let attrs = CatchStars::new(&map).calculate();
+
+            (attrs.catcher_width, attrs.fruit_radius)
+        })
+        .collect();
+
+    assert!((sizes[1].0 - 106.75).abs() < 1e-9, "{:?}", sizes);
+    assert!((sizes[1].1 - 32.0).abs() < 1e-9, "{:?}", sizes);
+
+    // Higher CS shrinks both the catcher and the fruits
+    assert!(sizes[0].0 > sizes[1].0 && sizes[1].0 > sizes[2].0);
+    assert!(sizes[0].1 > sizes[1].1 && sizes[1].1 > sizes[2].1);
+
+    // HR multiplies CS by 1.3
+    map.cs = 5.0;
+    let hr = CatchStars::new(&map).mods(16).calculate();
+    map.cs = 6.5;
+    let cs = CatchStars::new(&map).calculate();
+
+    assert_eq!(hr.catcher_width, cs.catcher_width);
+    assert_eq!(hr.fruit_radius, cs.fruit_radius);
+
+    let gradual = CatchGradualDifficultyAttributes::new(&map, 0)
+        .last()
+        .unwrap();
+    assert_eq!(gradual.catcher_width, cs.catcher_width);
+}
+
+#[test]
+fn aim_heavy_strain_summary() {
+    // Circles jumping across the playfield at a moderate pace
+    let jumps = (0..200).map(|i| HitObject {
+        pos: Pos2 {
+            x: if i % 2 == 0 { 16.0 } else { 496.0 },
+            y: if i % 4 < 2 { 16.0 } else { 368.0 },
+        },
+        start_time: 1000.0 + i as f64 * 250.0,
+        kind: HitObjectKind::Circle,
+        new_combo: false,
+    });
+
+    let map = BeatmapBuilder::new(GameMode::Osu)
+        .timing_point(TimingPoint::new(0.0, 500.0))
+        .hit_objects(jumps)
+        .build();
+
+    let strains = OsuStars::new(&map).strains();
+    let aim = strains.aim_summary();
+    let speed = strains.speed_summary();
+
+    assert!(aim.mean > speed.mean, "{:?} <= {:?}", aim, speed);
+    assert!(aim.max >= aim.mean);
+    assert!(aim.spikiness() < 0.5);
+    assert!(strains.flashlight_summary().mean > 0.0);
+}
+
+#[test]
+fn empty_vs_trivial_attributes() {
+    assert!(OsuDifficultyAttributes::default().is_empty());
+
+    let single_circle = HitObject {
+        pos: Pos2 { x: 256.0, y: 192.0 },
+        start_time: 1000.0,
+        kind: HitObjectKind::Circle,
+        new_combo: false,
+    };
+
+    let map = BeatmapBuilder::new(GameMode::Osu)
+        .timing_point(TimingPoint::new(0.0, 500.0))
+        .hit_objects(std::iter::once(single_circle))
+        .build();
+
+    let all = map.calculate_all(0);
+
+    let trivial = vec![
+        DifficultyAttributes::Osu(all.osu.unwrap()),
+        DifficultyAttributes::Taiko(all.taiko.unwrap()),
+        DifficultyAttributes::Catch(all.catch.unwrap()),
+        DifficultyAttributes::Mania(all.mania.unwrap()),
+    ];
+
+    // a single circle is hardly difficult but it's still a calculated map
+    for attrs in trivial {
+        assert_eq!(attrs.max_combo(), 1, "{:?}", attrs);
+        assert!(!attrs.is_empty(), "{:?}", attrs);
+    }
+
+    for mode in [
+        GameMode::Osu,
+        GameMode::Taiko,
+        GameMode::Catch,
+        GameMode::Mania,
+    ] {
+        let attrs = AnyStars::new(&map).mode(mode).passed_objects(0).calculate();
+        assert!(attrs.is_empty(), "{:?}", attrs);
+    }
+}
+
+#[test]
+fn single_precision_divergence() {
+    let map = test_map!(Osu);
+
+    let double = OsuStars::new(&map).mods(8 + 64).calculate();
+    let single = OsuStars::new(&map)
+        .mods(8 + 64)
+        .precision(Precision::Single)
+        .calculate();
+
+    assert!(double.stars > 6.0, "{}", double.stars);
+    assert!((single.stars as f32 as f64 - single.stars).abs() <= f64::EPSILON);
+
+    let star_diff = (single.stars - double.stars).abs();
+    assert!(star_diff > 0.0 && star_diff < 1e-5, "{}", star_diff);
+
+    let pp = |precision| {
+        OsuPP::new(&map)
+            .mods(8 + 64)
+            .precision(precision)
+            .accuracy(Accuracy::from_percent(98.5).unwrap())
+            .n_misses(1)
+            .calculate()
+            .pp
+    };
+
+    let pp_diff = (pp(Precision::Single) - pp(Precision::Double)).abs();
+    assert!(pp_diff > 0.0 && pp_diff < 1e-3, "{}", pp_diff);
+}
@@ -0,0 +1,75 @@
+#![cfg(all(
+    feature = "attribution",
+    not(any(feature = "async_tokio", feature = "async_std"))
+))]
+
+use akatsuki_pp::OsuStars;
+
+use crate::common::Osu;
+
+mod common;
+
+#[test]
+fn top_object_has_highest_strain() {
+    let map = test_map!(Osu);
+    let attribution = OsuStars::new(&map).attribution();
+
+    assert_eq!(attribution.len(), map.hit_objects.len() - 1);
+
+    let top = &attribution[0];
+    assert!(attribution.iter().all(|object| object.strain <= top.strain));
+    assert!(attribution
+        .windows(2)
+        .all(|pair| pair[0].strain >= pair[1].strain));
+
+    let top_object = &map.hit_objects[top.idx];
+    assert!((top_object.start_time - top.start_time).abs() <= f64::EPSILON);
+    assert!((top.strain - (top.aim + top.speed)).abs() <= f64::EPSILON);
+
+    // Relax leaves only the aim strain
+    let relax = OsuStars::new(&map).mods(128).attribution();
+    assert!(relax
+        .iter()
+        .all(|object| object.speed.abs() <= f64::EPSILON));
+    assert!((relax[0].strain - relax[0].aim).abs() <= f64::EPSILON);
+}
+
+#[test]
+fn attribution_indices_with_object_range() {
+    let map = test_map!(Osu);
+    let attribution = OsuStars::new(&map).object_range(100, 200).attribution();
+
+    assert_eq!(attribution.len(), 99);
+    assert!(attribution
+        .iter()
+        .all(|object| (101..200).contains(&object.idx)));
+}
+
+#[test]
+fn attribution_start_times_with_repeat() {
+    let map = test_map!(Osu);
+    let attribution = OsuStars::new(&map)
+        .object_range(0, 10)
+        .repeat(2)
+        .attribution();
+
+    assert_eq!(attribution.len(), 19);
+
+    for idx in 1..10 {
+        let mut times: Vec<_> = attribution
+            .iter()
+            .filter(|object| object.idx == idx)
+            .map(|object| object.start_time)
+            .collect();
+
+        times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        assert_eq!(times.len(), 2);
+        assert!((times[0] - map.hit_objects[idx].start_time).abs() <= f64::EPSILON);
+        assert!(times[1] > map.hit_objects[9].start_time);
+    }
+
+    // The first object of the second repetition has a strain
+    let first = attribution.iter().filter(|object| object.idx == 0).count();
+    assert_eq!(first, 1);
+}
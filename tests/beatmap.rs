@@ -0,0 +1,862 @@
+#![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+
+use akatsuki_pp::{
+    beatmap::{DifficultyPoint, TimingPoint},
+    parse::{HitObject, HitObjectKind, PathControlPoint, PathType, Pos2},
+    AnyStars, Beatmap, BeatmapBuilder, BeatmapExt, CatchStars, DifficultyAttributes, GameMode,
+    ManiaStars, OsuStars, ParseError, TaikoStars,
+};
+
+use crate::common::Osu;
+
+mod common;
+
+#[test]
+fn hit_objects_in_range() {
+    let map = test_map!(Osu);
+    let mid = map.hit_objects.len() / 2;
+    let start = map.hit_objects[mid].start_time;
+    let end = map.hit_objects[mid + 10].start_time;
+
+    let window = map.hit_objects_in_range(start, end);
+
+    assert!(!window.is_empty());
+    assert!(window
+        .iter()
+        .all(|h| start <= h.start_time && h.start_time < end));
+
+    let expected = map
+        .hit_objects
+        .iter()
+        .filter(|h| start <= h.start_time && h.start_time < end)
+        .count();
+
+    assert_eq!(window.len(), expected);
+    assert!(map.hit_objects_in_range(end, start).is_empty());
+}
+
+#[test]
+fn mod_adjusted_hp() {
+    let mut map = test_map!(Osu);
+    map.hp = 8.0;
+
+    let hp = |mods| map.attributes().mods(mods).build().hp;
+
+    assert!((hp(0) - 8.0).abs() < 1e-6);
+    assert!((hp(2) - 4.0).abs() < 1e-6); // EZ
+    assert!((hp(16) - 10.0).abs() < 1e-6); // HR
+}
+
+fn jumps() -> impl DoubleEndedIterator<Item = HitObject> {
+    (0..10).map(|i| HitObject {
+        pos: Pos2 {
+            x: if i % 2 == 0 { 64.0 } else { 448.0 },
+            y: 192.0,
+        },
+        start_time: 1000.0 + i as f64 * 200.0,
+        kind: HitObjectKind::Circle,
+        new_combo: false,
+    })
+}
+
+#[test]
+fn build_map() {
+    let map = BeatmapBuilder::new(GameMode::Osu)
+        .ar(9.0)
+        .od(8.0)
+        .cs(4.0)
+        .timing_point(TimingPoint::new(0.0, 400.0))
+        .hit_objects(jumps().rev())
+        .build();
+
+    assert_eq!(map.n_circles, 10);
+    assert_eq!(map.hit_objects.len(), map.sounds.len());
+    assert!(map
+        .hit_objects
+        .windows(2)
+        .all(|w| w[0].start_time <= w[1].start_time));
+
+    let attrs = OsuStars::new(&map).calculate();
+
+    assert_eq!(attrs.max_combo, 10);
+    assert!(attrs.stars > 0.0);
+
+    let collected: Beatmap = jumps().collect();
+    let default_attrs = OsuStars::new(&collected).calculate();
+
+    assert_eq!(default_attrs.max_combo, 10);
+    assert!(default_attrs.stars > 0.0);
+}
+
+#[test]
+fn cs_adjusted_radius() {
+    let stars = |cs: f32, mods: u32| {
+        let map = BeatmapBuilder::new(GameMode::Osu)
+            .cs(cs)
+            .timing_point(TimingPoint::new(0.0, 400.0))
+            .hit_objects(jumps())
+            .build();
+
+        OsuStars::new(&map).mods(mods).calculate()
+    };
+
+    let cs4 = stars(4.0, 0);
+    let cs6 = stars(6.0, 0);
+
+    // same distances are relatively larger for smaller circles
+    assert!(cs4.aim < cs6.aim);
+    assert!(cs4.stars < cs6.stars);
+
+    // HR multiplies CS by 1.3
+    let hr = stars(4.0, 16);
+    let cs5_2 = stars(5.2, 0);
+    assert!((hr.aim - cs5_2.aim).abs() < 1e-10);
+}
+
+#[test]
+fn mutated_difficulty() {
+    let mut map = test_map!(Osu);
+    let before = OsuStars::new(&map).calculate();
+
+    map.cs += 2.0;
+    map.ar -= 1.0;
+    let after = OsuStars::new(&map).calculate();
+
+    assert!(before.aim < after.aim);
+    assert!((after.ar - f64::from(map.ar)).abs() < 1e-6);
+}
+
+#[test]
+fn trimmed_stars() {
+    let map = test_map!(Osu);
+
+    let mut builder = BeatmapBuilder::new(GameMode::Osu);
+
+    builder
+        .ar(map.ar)
+        .od(map.od)
+        .cs(map.cs)
+        .hp(map.hp)
+        .slider_mult(map.slider_mult)
+        .tick_rate(map.tick_rate)
+        .stack_leniency(map.stack_leniency);
+
+    for &point in map.timing_points.iter() {
+        builder.timing_point(point);
+    }
+
+    for &point in map.difficulty_points.iter() {
+        builder.difficulty_point(point);
+    }
+
+    let late_objects = map
+        .hit_objects
+        .iter()
+        .filter(|h| h.start_time >= 10_000.0)
+        .cloned();
+
+    let late_map = builder.hit_objects(late_objects).build();
+
+    let trimmed = OsuStars::new(&map).start_time(10_000.0).calculate();
+    let expected = OsuStars::new(&late_map).calculate();
+    let full = OsuStars::new(&map).calculate();
+
+    assert!((trimmed.stars - expected.stars).abs() < 1e-9);
+    assert_eq!(trimmed.max_combo, expected.max_combo);
+    assert!(trimmed.max_combo < full.max_combo);
+    assert!((trimmed.stars - full.stars).abs() > f64::EPSILON);
+}
+
+#[test]
+fn object_range_stars() {
+    let map = test_map!(Osu);
+
+    let start = map.hit_objects[100].start_time;
+    let end = map.hit_objects[200].start_time;
+
+    let sliced = OsuStars::new(&map).object_range(100, 200).calculate();
+    let timed = OsuStars::new(&map)
+        .start_time(start)
+        .end_time(end)
+        .calculate();
+
+    assert_eq!(sliced, timed);
+    assert_eq!(sliced.n_circles + sliced.n_sliders + sliced.n_spinners, 100);
+
+    // only objects within both ranges are considered
+    let both = OsuStars::new(&map)
+        .object_range(100, 200)
+        .start_time(map.hit_objects[150].start_time)
+        .calculate();
+
+    let expected = OsuStars::new(&map).object_range(150, 200).calculate();
+    assert_eq!(both, expected);
+
+    let empty = AnyStars::new(&map).object_range(200, 100).calculate();
+    assert_eq!(empty.max_combo(), 0);
+}
+
+#[test]
+fn object_range_out_of_bounds() {
+    let map = test_map!(Osu);
+    let n_objects = map.hit_objects.len();
+
+    let past_end = OsuStars::new(&map)
+        .object_range(n_objects + 5, n_objects + 10)
+        .calculate();
+    assert_eq!(past_end.max_combo, 0);
+
+    let windows = OsuStars::new(&map)
+        .object_range(n_objects + 5, n_objects + 10)
+        .windowed_stars(5000.0);
+    assert_eq!(windows.count(), 0);
+
+    let partially = OsuStars::new(&map)
+        .object_range(n_objects - 100, n_objects + 10)
+        .calculate();
+    let expected = OsuStars::new(&map)
+        .object_range(n_objects - 100, n_objects)
+        .calculate();
+    assert_eq!(partially, expected);
+
+    // Converts may have more objects than the original map
+    for mode in [GameMode::Taiko, GameMode::Catch, GameMode::Mania] {
+        let attrs = OsuStars::new(&map)
+            .mode(mode)
+            .object_range(10 * n_objects, 10 * n_objects + 5)
+            .calculate();
+        assert_eq!(attrs.max_combo(), 0);
+    }
+}
+
+#[test]
+fn forced_mode() {
+    let mut map = test_map!(Osu);
+    map.set_mode(GameMode::Taiko);
+
+    let forced = match AnyStars::new(&map).calculate() {
+        DifficultyAttributes::Taiko(attrs) => attrs,
+        other => panic!("expected taiko attributes, got {:?}", other),
+    };
+
+    assert!(forced.stars > 0.0);
+    assert_eq!(forced, TaikoStars::new(&map).calculate());
+
+    // Forcing the mode keeps the hit objects while converting adjusts them
+    let converted = TaikoStars::new(&test_map!(Osu)).calculate();
+
+    assert_ne!(forced, converted);
+}
+
+#[test]
+fn preempt_and_fade_in() {
+    let map = BeatmapBuilder::new(GameMode::Osu)
+        .ar(9.0)
+        .timing_point(TimingPoint::new(0.0, 400.0))
+        .hit_objects(jumps())
+        .build();
+
+    let nm = OsuStars::new(&map).calculate();
+    assert!((nm.preempt - 600.0).abs() < 1e-6);
+    assert!((nm.fade_in - 400.0).abs() < 1e-6);
+
+    let hd = OsuStars::new(&map).mods(8).calculate();
+    assert!((hd.preempt - 600.0).abs() < 1e-6);
+    assert!((hd.fade_in - 240.0).abs() < 1e-6);
+
+    // HR increases AR to 10
+    let hr = OsuStars::new(&map).mods(16).calculate();
+    assert!((hr.preempt - 450.0).abs() < 1e-6);
+
+    // DT only speeds up the clock, the preempt time of the map stays the same
+    let dt = OsuStars::new(&map).mods(64).calculate();
+    assert!((dt.preempt - 600.0).abs() < 1e-3);
+}
+
+#[test]
+fn malformed_sliders() {
+    let slider = |pixel_len, end: Pos2| HitObject {
+        pos: Pos2 { x: 256.0, y: 192.0 },
+        start_time: 1500.0,
+        kind: HitObjectKind::Slider {
+            pixel_len,
+            repeats: 1,
+            control_points: vec![
+                PathControlPoint {
+                    pos: Pos2::default(),
+                    kind: Some(PathType::Linear),
+                },
+                PathControlPoint::from(end),
+            ],
+            edge_sounds: vec![0; 3],
+        },
+        new_combo: false,
+    };
+
+    let zero = Pos2::default();
+    let nan = Pos2 {
+        x: f32::NAN,
+        y: 0.0,
+    };
+
+    for slider in [
+        slider(Some(0.0), zero),
+        slider(None, nan),
+        slider(Some(f64::NAN), nan),
+    ] {
+        let map = BeatmapBuilder::new(GameMode::Osu)
+            .timing_point(TimingPoint::new(0.0, 400.0))
+            .hit_objects(jumps().take(2))
+            .hit_object(slider)
+            .build();
+
+        assert_eq!(map.n_circles, 3);
+        assert_eq!(map.n_sliders, 0);
+
+        let attrs = map.calculate_all(0);
+
+        assert_eq!(attrs.osu.as_ref().unwrap().max_combo, 3);
+        assert!(attrs.osu.unwrap().stars.is_finite());
+        assert!(attrs.taiko.unwrap().stars.is_finite());
+        assert!(attrs.catch.unwrap().stars.is_finite());
+        assert!(attrs.mania.unwrap().stars.is_finite());
+        assert!(map.max_pp(0).pp().is_finite());
+    }
+
+    // an invalid pixel length falls back to the length of the control points
+    let fallback = BeatmapBuilder::new(GameMode::Osu)
+        .hit_object(slider(Some(f64::NAN), Pos2 { x: 100.0, y: 0.0 }))
+        .build();
+
+    match &fallback.hit_objects[0].kind {
+        HitObjectKind::Slider { pixel_len, .. } => assert!(pixel_len.is_none()),
+        other => panic!("expected slider, got {:?}", other),
+    }
+
+    let bytes = b"osu file format v14\n\n[HitObjects]\n64,192,1000,1,0\n\
+        256,192,1500,2,0,L|256:192,1,0\n448,192,2000,1,0\n";
+    let parsed = Beatmap::from_bytes(bytes).unwrap();
+
+    assert_eq!(parsed.n_circles, 3);
+    assert_eq!(parsed.n_sliders, 0);
+    assert!(parsed.hit_objects.iter().all(HitObject::is_circle));
+    assert!(parsed.stars().calculate().stars().is_finite());
+}
+
+#[test]
+fn collinear_curves() {
+    let map = |kind, points: &[(f32, f32)]| {
+        let control_points = points
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, y))| PathControlPoint {
+                pos: Pos2 { x, y },
+                kind: (i == 0).then_some(kind),
+            })
+            .collect();
+
+        let slider = HitObject {
+            pos: Pos2 { x: 64.0, y: 192.0 },
+            start_time: 1500.0,
+            kind: HitObjectKind::Slider {
+                pixel_len: Some(200.0),
+                repeats: 1,
+                control_points,
+                edge_sounds: vec![0; 3],
+            },
+            new_combo: false,
+        };
+
+        BeatmapBuilder::new(GameMode::Catch)
+            .timing_point(TimingPoint::new(0.0, 400.0))
+            .hit_objects(jumps().take(2))
+            .hit_object(slider)
+            .build()
+    };
+
+    let linear = CatchStars::new(&map(PathType::Linear, &[(0.0, 0.0), (200.0, 0.0)])).calculate();
+
+    for points in [
+        [(0.0, 0.0), (100.0, 0.0), (200.0, 0.0)],
+        [(0.0, 0.0), (100.0, 1e-3), (200.0, 0.0)],
+        [(0.0, 0.0), (100.0, 1e-5), (200.0, 0.0)],
+    ] {
+        let perfect = CatchStars::new(&map(PathType::PerfectCurve, &points)).calculate();
+
+        assert!(perfect.stars.is_finite());
+        assert_eq!(perfect.max_combo(), linear.max_combo());
+        assert!((perfect.stars - linear.stars).abs() < 1e-3);
+    }
+
+    // the middle point lies behind the end point
+    let reversed = CatchStars::new(&map(
+        PathType::PerfectCurve,
+        &[(0.0, 0.0), (300.0, 0.0), (200.0, 0.0)],
+    ))
+    .calculate();
+
+    assert!(reversed.stars.is_finite());
+
+    for points in [
+        &[(0.0, 0.0), (200.0, 0.0)][..],
+        &[(0.0, 0.0), (0.0, 0.0), (200.0, 0.0), (200.0, 0.0)],
+    ] {
+        let catmull = CatchStars::new(&map(PathType::Catmull, points)).calculate();
+
+        assert!(catmull.stars.is_finite());
+        assert_eq!(catmull.max_combo(), linear.max_combo());
+    }
+}
+
+#[test]
+fn slider_ticks() {
+    struct Case {
+        len: f64,
+        repeats: usize,
+        tick_rate: f64,
+        slider_vel: f64,
+        version: u8,
+        max_combo: usize,
+    }
+
+    let default = Case {
+        len: 350.0,
+        repeats: 0,
+        tick_rate: 1.0,
+        slider_vel: 1.0,
+        version: 14,
+        max_combo: 0,
+    };
+
+    // With a beat length of 500ms and slider multiplier 1.0, one tick is generated
+    // every 100px / tick rate / slider velocity; the max combo counts the slider
+    // head, all ticks and repeats, and the tail.
+    let cases = [
+        // ticks at 100, 200, and 300px
+        Case {
+            max_combo: 5,
+            ..default
+        },
+        // the tick at 300px would be within 10ms of the end so it's suppressed
+        Case {
+            len: 301.0,
+            max_combo: 4,
+            ..default
+        },
+        Case {
+            len: 302.5,
+            max_combo: 5,
+            ..default
+        },
+        // ticks every 50px
+        Case {
+            tick_rate: 2.0,
+            max_combo: 8,
+            ..default
+        },
+        // three ticks on both spans plus the repeat point
+        Case {
+            repeats: 1,
+            max_combo: 9,
+            ..default
+        },
+        // ticks every 200px
+        Case {
+            slider_vel: 2.0,
+            max_combo: 3,
+            ..default
+        },
+        // prior to v8, the slider velocity does not affect the tick distance
+        Case {
+            slider_vel: 2.0,
+            version: 7,
+            max_combo: 5,
+            ..default
+        },
+    ];
+
+    for case in cases.iter() {
+        let slider = HitObject {
+            pos: Pos2 { x: 64.0, y: 192.0 },
+            start_time: 1000.0,
+            kind: HitObjectKind::Slider {
+                pixel_len: Some(case.len),
+                repeats: case.repeats,
+                control_points: vec![
+                    PathControlPoint {
+                        pos: Pos2::default(),
+                        kind: Some(PathType::Linear),
+                    },
+                    PathControlPoint::from(Pos2 { x: 400.0, y: 0.0 }),
+                ],
+                edge_sounds: vec![0; case.repeats + 2],
+            },
+            new_combo: false,
+        };
+
+        let mut map = BeatmapBuilder::new(GameMode::Osu)
+            .tick_rate(case.tick_rate)
+            .timing_point(TimingPoint::new(0.0, 500.0))
+            .difficulty_point(DifficultyPoint::new(
+                0.0,
+                -100.0 / case.slider_vel,
+                case.slider_vel,
+            ))
+            .hit_object(slider)
+            .build();
+
+        map.version = case.version;
+
+        let attrs = OsuStars::new(&map).calculate();
+
+        assert_eq!(
+            attrs.max_combo, case.max_combo,
+            "len={} repeats={} tick_rate={} sv={} v{}",
+            case.len, case.repeats, case.tick_rate, case.slider_vel, case.version
+        );
+    }
+}
+
+#[test]
+fn hit_windows_with_dt() {
+    let map = BeatmapBuilder::new(GameMode::Osu).ar(9.0).od(8.0).build();
+
+    let windows = |mode, od| {
+        map.attributes()
+            .mode(mode)
+            .od(od)
+            .mods(64) // DT
+            .hit_windows()
+    };
+
+    let osu = windows(GameMode::Osu, 8.0);
+    assert!((osu.ar - 400.0).abs() < 1e-9);
+    assert!((osu.od - 32.0 / 1.5).abs() < 1e-9);
+    assert!((osu.n100.unwrap() - 76.0 / 1.5).abs() < 1e-9);
+    assert!((osu.n50.unwrap() - 120.0 / 1.5).abs() < 1e-9);
+
+    let taiko = windows(GameMode::Taiko, 5.0);
+    assert!((taiko.od - 35.0 / 1.5).abs() < 1e-9);
+    assert!((taiko.n100.unwrap() - 80.0 / 1.5).abs() < 1e-9);
+    assert!(taiko.n50.is_none());
+
+    let catch = windows(GameMode::Catch, 8.0);
+    assert!(catch.n100.is_none() && catch.n50.is_none());
+
+    let mania = windows(GameMode::Mania, 8.0);
+    assert!((mania.od - 40.0).abs() < 1e-9);
+    assert!((mania.n100.unwrap() - 103.0).abs() < 1e-9);
+    assert!((mania.n50.unwrap() - 127.0).abs() < 1e-9);
+}
+
+#[test]
+fn clock_rate_slider_aim() {
+    // Back and forth sliders where both the slider travel and the jumps inbetween are
+    // compressed by `rate` so that the map equals the original map played at that rate.
+    let sliders = |rate: f64| {
+        let sliders = (0..40).map(move |i| {
+            let dir = if i % 2 == 0 { 1.0 } else { -1.0 };
+
+            HitObject {
+                pos: Pos2 {
+                    x: 256.0 - dir * 192.0,
+                    y: 192.0,
+                },
+                start_time: (1000.0 + i as f64 * 300.0) / rate,
+                kind: HitObjectKind::Slider {
+                    pixel_len: Some(200.0),
+                    repeats: 0,
+                    control_points: vec![
+                        PathControlPoint {
+                            pos: Pos2::default(),
+                            kind: Some(PathType::Linear),
+                        },
+                        PathControlPoint::from(Pos2 {
+                            x: dir * 200.0,
+                            y: 0.0,
+                        }),
+                    ],
+                    edge_sounds: vec![0; 2],
+                },
+                new_combo: false,
+            }
+        });
+
+        BeatmapBuilder::new(GameMode::Osu)
+            .slider_mult(1.4)
+            .timing_point(TimingPoint::new(0.0, 300.0 / rate))
+            .hit_objects(sliders)
+            .build()
+    };
+
+    let map = sliders(1.0);
+    let nomod = OsuStars::new(&map).calculate();
+    let dt = OsuStars::new(&map).mods(64).calculate();
+    let compressed = OsuStars::new(&sliders(1.5)).calculate();
+
+    assert!(nomod.aim < dt.aim);
+    assert!(nomod.stars < dt.stars);
+
+    // Only the legacy last tick of sliders is not affected by the clock rate
+    assert!(
+        (dt.aim - compressed.aim).abs() / compressed.aim < 0.01,
+        "{} vs {}",
+        dt.aim,
+        compressed.aim
+    );
+    assert!((dt.slider_factor - compressed.slider_factor).abs() < 0.02);
+}
+
+#[test]
+fn mode_from_str() {
+    let aliases = [
+        ("osu", GameMode::Osu),
+        ("std", GameMode::Osu),
+        ("Standard", GameMode::Osu),
+        ("0", GameMode::Osu),
+        ("taiko", GameMode::Taiko),
+        ("TAIKO", GameMode::Taiko),
+        ("1", GameMode::Taiko),
+        ("catch", GameMode::Catch),
+        ("CtB", GameMode::Catch),
+        ("fruits", GameMode::Catch),
+        ("2", GameMode::Catch),
+        ("mania", GameMode::Mania),
+        ("Mania", GameMode::Mania),
+        ("3", GameMode::Mania),
+    ];
+
+    for (alias, mode) in aliases.iter() {
+        assert_eq!(alias.parse::<GameMode>().ok(), Some(*mode), "{}", alias);
+    }
+
+    for invalid in ["", "4", "osu!", "fruit", " taiko"].iter() {
+        assert!(
+            matches!(invalid.parse::<GameMode>(), Err(ParseError::InvalidMode)),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn effective_attributes_hrdt() {
+    let map = BeatmapBuilder::new(GameMode::Osu)
+        .ar(9.0)
+        .od(8.0)
+        .cs(4.0)
+        .hp(6.0)
+        .build();
+
+    let attrs = map.effective_attributes(16 + 64, None);
+
+    assert!((attrs.ar - 11.0).abs() < 1e-9, "{}", attrs.ar);
+    assert!(
+        (attrs.od - (80.0 - 20.0 / 1.5) / 6.0).abs() < 1e-9,
+        "{}",
+        attrs.od
+    );
+    assert!((attrs.cs - 5.2).abs() < 1e-6, "{}", attrs.cs);
+    assert!((attrs.hp - 8.4).abs() < 1e-6, "{}", attrs.hp);
+    assert_eq!(attrs.clock_rate, 1.5);
+
+    let windows = attrs.hit_windows;
+    assert!((windows.ar - 300.0).abs() < 1e-9, "{}", windows.ar);
+    assert!((windows.od - 20.0 / 1.5).abs() < 1e-9, "{}", windows.od);
+    assert!((windows.n100.unwrap() - 40.0).abs() < 1e-9);
+    assert!((windows.n50.unwrap() - 100.0 / 1.5).abs() < 1e-9);
+
+    // A custom clock rate replaces the one of the mods
+    let custom = map.effective_attributes(16 + 64, Some(1.2));
+    assert_eq!(custom.clock_rate, 1.2);
+    assert!((custom.hit_windows.ar - 450.0 / 1.2).abs() < 1e-9);
+    assert_eq!(custom, map.attributes().mods(16).clock_rate(1.2).build());
+}
+
+#[test]
+fn reversed_symmetric_map() {
+    // Jumps whose distance grows towards both ends of the map
+    let circles = (0..21).map(|i: i32| HitObject {
+        pos: Pos2 {
+            x: 256.0 + (10 - i).abs() as f32 * if i % 2 == 0 { 20.0 } else { -20.0 },
+            y: 192.0,
+        },
+        start_time: 1000.0 + f64::from(i) * 150.0,
+        kind: HitObjectKind::Circle,
+        new_combo: false,
+    });
+
+    let map = BeatmapBuilder::new(GameMode::Osu)
+        .timing_point(TimingPoint::new(0.0, 300.0))
+        .hit_objects(circles)
+        .build();
+
+    let reversed = map.reverse();
+
+    assert_eq!(reversed.hit_objects.len(), map.hit_objects.len());
+    assert_eq!(reversed.sounds.len(), map.sounds.len());
+    assert_eq!(reversed.hit_objects, map.hit_objects);
+
+    let stars = OsuStars::new(&map).calculate().stars;
+    let reversed_stars = OsuStars::new(&reversed).calculate().stars;
+    assert!((stars - reversed_stars).abs() < 1e-10);
+
+    // Reversing twice restores the original timing
+    let map = test_map!(Osu);
+    let twice = map.reverse().reverse();
+
+    assert!(map
+        .hit_objects
+        .iter()
+        .zip(twice.hit_objects.iter())
+        .all(|(a, b)| (a.start_time - b.start_time).abs() < 1e-6));
+    assert_eq!(map.timing_points.len(), twice.timing_points.len());
+}
+
+fn single_slider() -> Beatmap {
+    // 200px at 100px per beat of 500ms so the slider lasts 1000ms
+    let slider = HitObject {
+        pos: Pos2 { x: 64.0, y: 192.0 },
+        start_time: 1000.0,
+        kind: HitObjectKind::Slider {
+            pixel_len: Some(200.0),
+            repeats: 0,
+            control_points: vec![
+                PathControlPoint {
+                    pos: Pos2::default(),
+                    kind: Some(PathType::Linear),
+                },
+                PathControlPoint::from(Pos2 { x: 200.0, y: 0.0 }),
+            ],
+            edge_sounds: vec![0; 2],
+        },
+        new_combo: false,
+    };
+
+    BeatmapBuilder::new(GameMode::Osu)
+        .timing_point(TimingPoint::new(0.0, 500.0))
+        .hit_object(slider)
+        .build()
+}
+
+#[test]
+fn repeat_after_slider_end() {
+    let map = single_slider();
+    let repeated = map.repeat(3);
+
+    let start_times: Vec<_> = repeated.hit_objects.iter().map(|h| h.start_time).collect();
+
+    // one beat after the slider ended
+    assert_eq!(start_times, [1000.0, 2500.0, 4000.0]);
+    assert_eq!(repeated.n_sliders, 3);
+
+    let capped = map.repeat(usize::MAX);
+    assert_eq!(capped.hit_objects.len(), Beatmap::MAX_REPEATS);
+    assert_eq!(capped.n_sliders, Beatmap::MAX_REPEATS as u32);
+}
+
+#[test]
+fn reversed_slider_starts_at_its_end() {
+    let mut map = single_slider();
+
+    map.hit_objects.push(HitObject {
+        pos: Pos2 { x: 256.0, y: 192.0 },
+        start_time: 3000.0,
+        kind: HitObjectKind::Circle,
+        new_combo: false,
+    });
+    map.sounds.push(0);
+    map.n_circles += 1;
+
+    let reversed = map.reverse();
+
+    let start_times: Vec<_> = reversed.hit_objects.iter().map(|h| h.start_time).collect();
+
+    // the slider used to end at 2000ms so it now starts there and ends at 3000ms
+    assert_eq!(start_times, [1000.0, 2000.0]);
+    assert!(matches!(
+        reversed.hit_objects[1].kind,
+        HitObjectKind::Slider { .. }
+    ));
+}
+
+#[test]
+fn control_points_across_inherited_point() {
+    let map = BeatmapBuilder::new(GameMode::Osu)
+        .timing_point(TimingPoint::new(0.0, 400.0))
+        .difficulty_point(DifficultyPoint::new(1000.0, -50.0, 2.0))
+        .timing_point(TimingPoint::new(2000.0, 300.0))
+        .hit_objects(jumps())
+        .build();
+
+    let (timing, difficulty) = map.control_points_at(500.0);
+    assert!((timing.beat_len - 400.0).abs() <= f64::EPSILON);
+    assert!((difficulty.slider_vel - DifficultyPoint::DEFAULT_SLIDER_VEL).abs() <= f64::EPSILON);
+
+    // the inherited point keeps the beat length but changes the velocity
+    let (timing, difficulty) = map.control_points_at(1000.0);
+    assert!((timing.beat_len - 400.0).abs() <= f64::EPSILON);
+    assert!((difficulty.slider_vel - 2.0).abs() <= f64::EPSILON);
+
+    // a later uninherited point changes the beat length only
+    let (timing, difficulty) = map.control_points_at(2500.0);
+    assert!((timing.beat_len - 300.0).abs() <= f64::EPSILON);
+    assert!((difficulty.slider_vel - 2.0).abs() <= f64::EPSILON);
+
+    assert_eq!(map.timing_point_at(1500.0), map.control_points_at(1500.0).0);
+}
+
+#[test]
+fn convert_once_calculate_twice() {
+    let map = test_map!(Osu);
+    let converted = map.converted(GameMode::Taiko);
+
+    assert_eq!(converted.mode, GameMode::Taiko);
+    assert!(converted.is_convert);
+    assert!(!map.is_convert);
+
+    for mods in [0, 64] {
+        let expected = TaikoStars::new(&map).mods(mods).calculate();
+        let attrs = TaikoStars::new(&converted).mods(mods).calculate();
+
+        assert!((attrs.stars - expected.stars).abs() <= f64::EPSILON);
+        assert_eq!(attrs.max_combo, expected.max_combo);
+    }
+
+    let converted = map.converted(GameMode::Mania);
+    let expected = ManiaStars::new(&map).calculate();
+    let attrs = ManiaStars::new(&converted).calculate();
+
+    assert!((attrs.stars - expected.stars).abs() <= f64::EPSILON);
+    assert!((attrs.hit_window - expected.hit_window).abs() <= f64::EPSILON);
+
+    let converted = map.converted(GameMode::Catch);
+    assert_eq!(converted.mode, GameMode::Catch);
+
+    match AnyStars::new(&converted).calculate() {
+        DifficultyAttributes::Catch(attrs) => {
+            let expected = CatchStars::new(&map).calculate();
+            assert!((attrs.stars - expected.stars).abs() <= f64::EPSILON);
+        }
+        other => panic!("expected catch attributes, got {:?}", other),
+    }
+}
+
+#[test]
+fn objects_before_fail_time() {
+    let map = test_map!(Osu);
+    let fail_time = map.hit_objects[300].start_time;
+
+    assert!(map.hit_objects[299].start_time < fail_time);
+    assert_eq!(map.objects_before(fail_time, 1.0), 300);
+    assert_eq!(map.objects_before(fail_time + 1.0, 1.0), 301);
+
+    // With DT the same object is reached after two thirds of the time
+    assert_eq!(map.objects_before(fail_time / 1.5, 1.5), 300);
+
+    assert_eq!(map.objects_before(f64::NEG_INFINITY, 1.0), 0);
+    assert_eq!(
+        map.objects_before(f64::INFINITY, 1.0),
+        map.hit_objects.len()
+    );
+}
@@ -0,0 +1,136 @@
+#![cfg(all(
+    feature = "cache",
+    not(any(feature = "async_tokio", feature = "async_std"))
+))]
+
+use akatsuki_pp::{AnyStars, Beatmap, DifficultyCache, GameMode};
+
+use crate::common::{Osu, Taiko};
+
+mod common;
+
+#[test]
+fn identical_requests_hit_cache() {
+    let map = test_map!(Osu);
+    let mut cache = DifficultyCache::new(4);
+
+    let first = cache.calculate(&map, 64, None);
+    let second = cache.calculate(&map, 64, None);
+
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 1);
+    assert!((first.stars() - second.stars()).abs() < f64::EPSILON);
+
+    // DT implies a clock rate of 1.5
+    cache.calculate(&map, 64, Some(1.5));
+    assert_eq!(cache.hits(), 2);
+
+    cache.calculate(&map, 64, Some(1.2));
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.misses(), 2);
+}
+
+#[test]
+fn evicts_least_recently_used() {
+    let osu = test_map!(Osu);
+    let taiko = test_map!(Taiko);
+    let mut cache = DifficultyCache::new(2);
+
+    cache.calculate(&osu, 0, None);
+    cache.calculate(&taiko, 0, None);
+    cache.calculate(&osu, 0, None);
+    cache.calculate(&osu, 16, None);
+
+    assert_eq!(cache.len(), 2);
+
+    // taiko was evicted, the nomod osu! entry is still cached
+    cache.calculate(&osu, 0, None);
+    assert_eq!(cache.hits(), 2);
+
+    cache.calculate(&taiko, 0, None);
+    assert_eq!(cache.misses(), 4);
+}
+
+#[test]
+fn eviction_order_survives_many_hits() {
+    let osu = test_map!(Osu);
+    let taiko = test_map!(Taiko);
+    let mut cache = DifficultyCache::new(2);
+
+    cache.calculate(&osu, 0, None);
+    cache.calculate(&taiko, 0, None);
+
+    for _ in 0..10 {
+        cache.calculate(&osu, 0, None);
+    }
+
+    cache.calculate(&taiko, 0, None);
+    cache.calculate(&osu, 16, None);
+
+    // osu! nomod was used less recently than taiko
+    cache.calculate(&taiko, 0, None);
+    assert_eq!(cache.hits(), 12);
+
+    cache.calculate(&osu, 0, None);
+    assert_eq!(cache.misses(), 4);
+}
+
+#[test]
+fn unparsed_maps_are_not_cached() {
+    let map = Beatmap::default();
+    let mut cache = DifficultyCache::new(4);
+
+    cache.calculate(&map, 0, None);
+    cache.calculate(&map, 0, None);
+
+    assert!(cache.is_empty());
+    assert_eq!(cache.hits(), 0);
+}
+
+#[test]
+fn derived_maps_do_not_share_entries() {
+    let map = test_map!(Osu);
+    let reversed = map.reverse();
+    let mut cache = DifficultyCache::new(4);
+
+    let forward = cache.calculate(&map, 0, None);
+    let cached_reversed = cache.calculate(&reversed, 0, None);
+    let direct_reversed = AnyStars::new(&reversed).calculate();
+
+    assert!(reversed.checksum.is_none());
+    assert!((cached_reversed.stars() - direct_reversed.stars()).abs() <= f64::EPSILON);
+    assert!((cached_reversed.stars() - forward.stars()).abs() > f64::EPSILON);
+    assert_eq!(cache.hits(), 0);
+
+    // A convert and the same map forced into the mode are calculated differently
+    let converted = map.converted(GameMode::Taiko);
+    let mut forced = map.clone();
+    forced.set_mode(GameMode::Taiko);
+
+    let cached_converted = cache.calculate(&converted, 0, None);
+    let cached_forced = cache.calculate(&forced, 0, None);
+
+    assert!((cached_converted.stars() - cached_forced.stars()).abs() > f64::EPSILON);
+    assert_eq!(cache.hits(), 0);
+}
+
+#[test]
+fn modified_maps_do_not_share_entries() {
+    let mut map = test_map!(Osu);
+    let mut cache = DifficultyCache::new(4);
+
+    let original = cache.calculate(&map, 0, None);
+
+    map.cs += 1.0;
+    let higher_cs = cache.calculate(&map, 0, None);
+
+    map.hit_objects.truncate(map.hit_objects.len() / 2);
+    let truncated = cache.calculate(&map, 0, None);
+
+    assert!(map.checksum.is_some());
+    assert_eq!(cache.hits(), 0);
+    assert_eq!(cache.len(), 3);
+    assert!((original.stars() - higher_cs.stars()).abs() > f64::EPSILON);
+    assert!((higher_cs.stars() - truncated.stars()).abs() > f64::EPSILON);
+}
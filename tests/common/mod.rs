@@ -1,7 +1,9 @@
 mod mode;
 
-use rosu_pp::Beatmap;
+use akatsuki_pp::Beatmap;
 
+// Test crates only use the modes they cover
+#[allow(unused_imports)]
 pub use self::mode::{Catch, Mania, Mode, Osu, Taiko};
 
 #[macro_export]
@@ -16,6 +18,7 @@ macro_rules! test_map {
 }
 
 #[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+#[allow(dead_code)]
 pub fn test_map<M: Mode>() -> Beatmap {
     let path = format!("./maps/{}.osu", M::TEST_MAP_ID);
 
@@ -1,4 +1,4 @@
-use rosu_pp::{
+use akatsuki_pp::{
     catch::CatchDifficultyAttributes, mania::ManiaDifficultyAttributes,
     osu::OsuDifficultyAttributes, taiko::TaikoDifficultyAttributes,
 };
@@ -12,17 +12,32 @@ pub trait Mode {
     type DifficultyAttributes;
 
     const TEST_MAP_ID: u32;
-    const TEST_DIFF_ATTRS: Self::DifficultyAttributes;
+
+    // Not every test crate that includes this module compares attributes
+    #[allow(dead_code)]
+    fn test_diff_attrs() -> Self::DifficultyAttributes;
 }
 
 macro_rules! impl_mode {
-    ( $( $mode:ident: $map_id:literal, $diff_attrs:ident $fields:tt; )* ) => {
+    (
+        $(
+            $mode:ident: $map_id:literal, $diff_attrs:ident {
+                $( $field:ident: $value:expr, )*
+            };
+        )*
+    ) => {
         $(
             impl Mode for $mode {
                 type DifficultyAttributes = $diff_attrs;
 
                 const TEST_MAP_ID: u32 = $map_id;
-                const TEST_DIFF_ATTRS: Self::DifficultyAttributes = $diff_attrs $fields;
+
+                fn test_diff_attrs() -> Self::DifficultyAttributes {
+                    let mut attrs = $diff_attrs::default();
+                    $( attrs.$field = $value; )*
+
+                    attrs
+                }
             }
         )*
     };
@@ -38,6 +53,8 @@ impl_mode! {
         ar: 9.300000190734863,
         od: 8.800000190734863,
         hp: 5.0,
+        preempt: 555.0,
+        fade_in: 400.0,
         n_circles: 307,
         n_sliders: 293,
         n_spinners: 1,
@@ -0,0 +1,109 @@
+use akatsuki_pp::{grade, GameMode, Grade, ScoreState};
+
+const HD: u32 = 8;
+const FL: u32 = 1024;
+
+#[test]
+fn osu_grades() {
+    let osu = |n300, n100, n50, n_misses| grade(GameMode::Osu, 0, n300, n100, n50, n_misses);
+
+    assert_eq!(osu(100, 0, 0, 0), Grade::X);
+
+    // S requires more than 90% 300s, at most 1% 50s, and no misses
+    assert_eq!(osu(91, 8, 1, 0), Grade::S);
+    assert_eq!(osu(91, 7, 2, 0), Grade::A);
+    assert_eq!(osu(90, 10, 0, 0), Grade::A);
+    assert_eq!(osu(99, 0, 0, 1), Grade::A);
+
+    // A requires more than 80% 300s without misses or more than 90% 300s
+    assert_eq!(osu(81, 19, 0, 0), Grade::A);
+    assert_eq!(osu(80, 20, 0, 0), Grade::B);
+    assert_eq!(osu(89, 10, 0, 1), Grade::B);
+
+    // B requires more than 70% 300s without misses or more than 80% 300s
+    assert_eq!(osu(71, 29, 0, 0), Grade::B);
+    assert_eq!(osu(70, 30, 0, 0), Grade::C);
+    assert_eq!(osu(79, 20, 0, 1), Grade::C);
+
+    // C requires more than 60% 300s
+    assert_eq!(osu(61, 30, 0, 9), Grade::C);
+    assert_eq!(osu(60, 30, 0, 10), Grade::D);
+
+    assert_eq!(osu(0, 0, 0, 0), Grade::D);
+}
+
+#[test]
+fn taiko_grades() {
+    let taiko = |n300, n100, n_misses| grade(GameMode::Taiko, 0, n300, n100, 0, n_misses);
+
+    assert_eq!(taiko(100, 0, 0), Grade::X);
+    assert_eq!(taiko(91, 9, 0), Grade::S);
+    assert_eq!(taiko(91, 8, 1), Grade::A);
+    assert_eq!(taiko(80, 20, 0), Grade::B);
+    assert_eq!(taiko(61, 39, 0), Grade::C);
+    assert_eq!(taiko(60, 40, 0), Grade::D);
+}
+
+#[test]
+fn catch_grades() {
+    // accuracy of caught fruits, droplets, and tiny droplets
+    let catch = |caught, n_misses| grade(GameMode::Catch, 0, caught, 0, 0, n_misses);
+
+    assert_eq!(catch(100, 0), Grade::X);
+    assert_eq!(catch(9801, 199), Grade::S);
+    assert_eq!(catch(98, 2), Grade::A);
+    assert_eq!(catch(9401, 599), Grade::A);
+    assert_eq!(catch(94, 6), Grade::B);
+    assert_eq!(catch(9001, 999), Grade::B);
+    assert_eq!(catch(90, 10), Grade::C);
+    assert_eq!(catch(8501, 1499), Grade::C);
+    assert_eq!(catch(85, 15), Grade::D);
+
+    // missed tiny droplets are stored as katus in a score state
+    let state = ScoreState {
+        n300: 98,
+        n_katu: 2,
+        ..Default::default()
+    };
+
+    assert_eq!(state.grade(GameMode::Catch, 0), Grade::A);
+}
+
+#[test]
+fn mania_grades() {
+    let mania = |n300, n100| grade(GameMode::Mania, 0, n300, n100, 0, 0);
+
+    // (300 * n300 + 100 * n100) / (300 * 100)
+    assert_eq!(mania(100, 0), Grade::X);
+    assert_eq!(mania(93, 7), Grade::S);
+    assert_eq!(mania(92, 8), Grade::A);
+    assert_eq!(mania(86, 14), Grade::A);
+    assert_eq!(mania(85, 15), Grade::B);
+    assert_eq!(mania(71, 29), Grade::B);
+    assert_eq!(mania(70, 30), Grade::C);
+    assert_eq!(mania(56, 44), Grade::C);
+    assert_eq!(mania(55, 45), Grade::D);
+
+    // 320s and 200s through the score state
+    let state = ScoreState {
+        n_geki: 50,
+        n300: 40,
+        n_katu: 10,
+        ..Default::default()
+    };
+
+    // (300 * 90 + 200 * 10) / (300 * 100) = 96.67%
+    assert_eq!(state.grade(GameMode::Mania, 0), Grade::S);
+}
+
+#[test]
+fn silver_grades() {
+    assert_eq!(grade(GameMode::Osu, HD, 100, 0, 0, 0), Grade::XH);
+    assert_eq!(grade(GameMode::Osu, FL, 95, 5, 0, 0), Grade::SH);
+    assert_eq!(grade(GameMode::Osu, HD + FL, 90, 10, 0, 0), Grade::A);
+    assert_eq!(grade(GameMode::Mania, HD, 100, 0, 0, 0), Grade::XH);
+    assert_eq!(grade(GameMode::Catch, FL, 99, 0, 0, 1), Grade::SH);
+
+    assert!(Grade::XH > Grade::X);
+    assert!(Grade::S > Grade::A);
+}
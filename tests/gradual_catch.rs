@@ -1,6 +1,6 @@
 #![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
 
-use rosu_pp::{
+use akatsuki_pp::{
     catch::{CatchGradualDifficultyAttributes, CatchGradualPerformanceAttributes, CatchScoreState},
     Beatmap, CatchPP, CatchStars,
 };
@@ -1,6 +1,6 @@
 #![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
 
-use rosu_pp::{
+use akatsuki_pp::{
     mania::{ManiaGradualDifficultyAttributes, ManiaGradualPerformanceAttributes, ManiaScoreState},
     Beatmap, ManiaPP, ManiaStars,
 };
@@ -29,6 +29,57 @@ fn iter_end_eq_regular() {
     assert_eq!(regular, iter_end);
 }
 
+#[test]
+fn iter_end_eq_regular_from_stars() {
+    let map = test_map!(Mania);
+    let stars = || ManiaStars::new(&map).mods(64).key_count(7).lazer(true);
+    let regular = stars().calculate();
+
+    let iter_end = ManiaGradualDifficultyAttributes::from(stars())
+        .last()
+        .expect("empty iter");
+
+    assert_eq!(regular, iter_end);
+    assert_ne!(regular, ManiaStars::new(&map).mods(64).calculate());
+}
+
+#[test]
+fn iter_end_eq_regular_trimmed_and_repeated() {
+    let map = test_map!(Mania);
+    let stars = || ManiaStars::new(&map).object_range(100, 300).repeat(3);
+    let regular = stars().calculate();
+
+    let iter_end = ManiaGradualDifficultyAttributes::from(stars())
+        .last()
+        .expect("empty iter");
+
+    assert_eq!(regular, iter_end);
+    assert_ne!(regular, ManiaStars::new(&map).calculate());
+}
+
+#[test]
+fn gradual_end_eq_regular_lazer() {
+    let map = test_map!(Mania);
+    let regular = ManiaPP::new(&map).lazer(true).calculate();
+    let stable = ManiaStars::new(&map).calculate();
+    assert_ne!(regular.difficulty.max_combo, stable.max_combo);
+
+    let mut gradual = ManiaGradualPerformanceAttributes::from(ManiaPP::new(&map).lazer(true));
+
+    let state = ManiaScoreState {
+        n320: map.hit_objects.len(),
+        ..Default::default()
+    };
+
+    let gradual_end = gradual.process_next_n_objects(state, usize::MAX).unwrap();
+
+    assert_eq!(
+        regular.difficulty.max_combo,
+        gradual_end.difficulty.max_combo
+    );
+    assert!((regular.pp - gradual_end.pp).abs() < f64::EPSILON);
+}
+
 #[test]
 fn correct_empty() {
     let map = test_map!(Mania);
@@ -129,3 +180,44 @@ fn gradual_eq_regular_passed() {
 
     assert_eq!(regular, gradual);
 }
+
+#[test]
+fn resume_eq_iterating() {
+    let map = test_map!(Mania);
+
+    for &processed in &[0, 1, 100, 1000] {
+        let mut iterated = ManiaGradualDifficultyAttributes::new(&map, 64);
+
+        for _ in 0..processed {
+            let _ = iterated.next();
+        }
+
+        let mut resumed = ManiaGradualDifficultyAttributes::resume(&map, 64, processed);
+
+        assert_eq!(resumed.len(), iterated.len());
+        assert_eq!(resumed.next(), iterated.next());
+    }
+
+    let len = ManiaGradualDifficultyAttributes::new(&map, 0).len();
+    let mut resumed = ManiaGradualDifficultyAttributes::resume(&map, 0, len + 5);
+    assert!(resumed.next().is_none());
+}
+
+#[test]
+fn resume_from_stars_eq_iterating() {
+    let map = test_map!(Mania);
+    let stars = || ManiaStars::new(&map).mods(64).key_count(7).lazer(true);
+
+    for &processed in &[0, 1, 100, 1000] {
+        let mut iterated = ManiaGradualDifficultyAttributes::from(stars());
+
+        for _ in 0..processed {
+            let _ = iterated.next();
+        }
+
+        let mut resumed = ManiaGradualDifficultyAttributes::resume_from(stars(), processed);
+
+        assert_eq!(resumed.len(), iterated.len());
+        assert_eq!(resumed.next(), iterated.next());
+    }
+}
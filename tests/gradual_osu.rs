@@ -1,6 +1,6 @@
 #![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
 
-use rosu_pp::{
+use akatsuki_pp::{
     osu::{OsuGradualDifficultyAttributes, OsuGradualPerformanceAttributes, OsuScoreState},
     Beatmap, OsuPP, OsuStars,
 };
@@ -29,6 +29,74 @@ fn iter_end_eq_regular() {
     assert_eq!(regular, iter_end);
 }
 
+#[test]
+fn iter_end_eq_regular_with_autopilot() {
+    let map = test_map!(Osu);
+    let regular = OsuStars::new(&map).mods(8192).calculate();
+
+    let iter_end = OsuGradualDifficultyAttributes::new(&map, 8192)
+        .last()
+        .expect("empty iter");
+
+    assert!(iter_end.aim.abs() <= f64::EPSILON);
+    assert_eq!(regular, iter_end);
+}
+
+#[test]
+fn live_stars_eq_passed_objects() {
+    let map = test_map!(Osu);
+    let mods = 8 + 64;
+    let passed_objects = map.hit_objects.len() / 2;
+
+    let regular = OsuStars::new(&map)
+        .mods(mods)
+        .passed_objects(passed_objects)
+        .calculate()
+        .stars;
+
+    let (time, live) = OsuGradualDifficultyAttributes::new(&map, mods)
+        .live_stars()
+        .nth(passed_objects - 2)
+        .expect("too few objects");
+
+    assert_eq!(regular, live);
+
+    // The live star rating only applies once the object has been completed
+    let last = &map.hit_objects[passed_objects - 1];
+    assert!(time >= last.start_time);
+    assert!(time < map.hit_objects[passed_objects].start_time);
+
+    let slider_idx = map
+        .hit_objects
+        .iter()
+        .position(|h| h.is_slider())
+        .filter(|&i| i > 0)
+        .expect("no slider");
+
+    let (slider_end, _) = OsuGradualDifficultyAttributes::new(&map, mods)
+        .live_stars()
+        .nth(slider_idx - 1)
+        .unwrap();
+
+    assert!(slider_end > map.hit_objects[slider_idx].start_time);
+}
+
+#[test]
+fn iter_end_eq_regular_without_rate_adjusted_hit_window() {
+    let map = test_map!(Osu);
+
+    let regular = OsuStars::new(&map)
+        .mods(64)
+        .rate_adjusted_hit_window(false)
+        .calculate();
+
+    let iter_end = OsuGradualDifficultyAttributes::with_rate_adjusted_hit_window(&map, 64, false)
+        .last()
+        .expect("empty iter");
+
+    assert_eq!(regular, iter_end);
+}
+
 #[test]
 fn correct_empty() {
     let map = test_map!(Osu);
@@ -66,6 +134,8 @@ fn next_and_next_n() {
         n100: 8,
         n50: 2,
         n_misses: 2,
+        slider_end_hits: 0,
+        slider_tick_hits: 0,
     };
 
     let next = gradual1.process_next_object(state.clone());
@@ -86,6 +156,8 @@ fn gradual_end_eq_regular() {
         n100: 0,
         n50: 0,
         n_misses: 0,
+        slider_end_hits: 0,
+        slider_tick_hits: 0,
     };
 
     let gradual_end = gradual.process_next_n_objects(state, usize::MAX).unwrap();
@@ -107,6 +179,8 @@ fn gradual_eq_regular_passed() {
         n100: 0,
         n50: 0,
         n_misses: 0,
+        slider_end_hits: 0,
+        slider_tick_hits: 0,
     };
 
     let gradual = gradual.process_next_n_objects(state, n).unwrap();
@@ -0,0 +1,169 @@
+#![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+
+use std::sync::OnceLock;
+
+use akatsuki_pp::{
+    mania::{ManiaDifficultyAttributes, ManiaScoreState},
+    osu::{OsuDifficultyAttributes, OsuScoreState},
+    Accuracy, Beatmap, ManiaPP, ManiaStars, OsuPP, OsuStars,
+};
+use proptest::prelude::*;
+
+use crate::common::{Mania, Osu};
+
+mod common;
+
+fn osu_map() -> &'static (Beatmap, OsuDifficultyAttributes) {
+    static MAP: OnceLock<(Beatmap, OsuDifficultyAttributes)> = OnceLock::new();
+
+    MAP.get_or_init(|| {
+        let map = test_map!(Osu);
+        let attrs = OsuStars::new(&map).calculate();
+
+        (map, attrs)
+    })
+}
+
+fn mania_map() -> &'static (Beatmap, ManiaDifficultyAttributes) {
+    static MAP: OnceLock<(Beatmap, ManiaDifficultyAttributes)> = OnceLock::new();
+
+    MAP.get_or_init(|| {
+        let map = test_map!(Mania);
+        let attrs = ManiaStars::new(&map).calculate();
+
+        (map, attrs)
+    })
+}
+
+proptest! {
+    #[test]
+    fn osu_state_accuracy_in_range(
+        n300 in 0..2000_usize,
+        n100 in 0..2000_usize,
+        n50 in 0..2000_usize,
+        n_misses in 0..2000_usize,
+    ) {
+        let state = OsuScoreState { max_combo: 0, n300, n100, n50, n_misses, ..Default::default() };
+        let acc = state.accuracy();
+
+        prop_assert!((0.0..=1.0).contains(&acc), "accuracy {}", acc);
+    }
+
+    #[test]
+    fn mania_state_accuracy_in_range(
+        n320 in 0..2000_usize,
+        n300 in 0..2000_usize,
+        n200 in 0..2000_usize,
+        n100 in 0..2000_usize,
+        n50 in 0..2000_usize,
+        n_misses in 0..2000_usize,
+    ) {
+        let state = ManiaScoreState { n320, n300, n200, n100, n50, n_misses };
+        let acc = state.accuracy();
+
+        prop_assert!((0.0..=1.0).contains(&acc), "accuracy {}", acc);
+    }
+
+    #[test]
+    fn osu_pp_non_negative(acc in 0.0..=100.0_f64, n_misses in 0..700_usize, combo in 0..1000_usize) {
+        let (map, attrs) = osu_map();
+
+        let pp = OsuPP::new(map)
+            .attributes(attrs.clone())
+            .accuracy(Accuracy::from_percent(acc).unwrap())
+            .n_misses(n_misses)
+            .combo(combo)
+            .calculate()
+            .pp;
+
+        prop_assert!(pp.is_finite() && pp >= 0.0, "pp {}", pp);
+    }
+
+    #[test]
+    fn mania_pp_non_negative(acc in 0.0..=100.0_f64, n_misses in 0..3500_usize) {
+        let (map, attrs) = mania_map();
+
+        let pp = ManiaPP::new(map)
+            .attributes(*attrs)
+            .accuracy(Accuracy::from_percent(acc).unwrap())
+            .n_misses(n_misses)
+            .calculate()
+            .pp;
+
+        prop_assert!(pp.is_finite() && pp >= 0.0, "pp {}", pp);
+    }
+
+    // The hitresults are generated to match the accuracy as closely as possible so
+    // with a different amount of misses, the actual accuracy may end up slightly
+    // higher. Additionally, osu!mania generates more 320s for more misses.
+    // Hence, the play with fewer misses is granted a perfect hit for each extra miss.
+    #[test]
+    fn osu_more_misses_less_pp(
+        acc in 0.0..=1.0_f64,
+        n_misses in 0..100_usize,
+        extra in 1..50_usize,
+    ) {
+        let (map, attrs) = osu_map();
+
+        let calculate = |acc: f64, n_misses| {
+            OsuPP::new(map)
+                .attributes(attrs.clone())
+                .accuracy(Accuracy::from_fraction(acc.min(1.0)).unwrap())
+                .n_misses(n_misses)
+                .calculate()
+                .pp
+        };
+
+        let fewer = calculate(acc + extra as f64 / map.hit_objects.len() as f64, n_misses);
+        let more = calculate(acc, n_misses + extra);
+
+        prop_assert!(more <= fewer, "{} misses: {}pp | {} misses: {}pp", n_misses, fewer, n_misses + extra, more);
+    }
+
+    #[test]
+    fn mania_more_misses_less_pp(
+        acc in 0.0..=1.0_f64,
+        n_misses in 0..200_usize,
+        extra in 1..200_usize,
+    ) {
+        let (map, attrs) = mania_map();
+
+        let calculate = |acc: f64, n_misses| {
+            ManiaPP::new(map)
+                .attributes(*attrs)
+                .accuracy(Accuracy::from_fraction(acc.min(1.0)).unwrap())
+                .n_misses(n_misses)
+                .calculate()
+                .pp
+        };
+
+        let fewer = calculate(acc + extra as f64 / map.hit_objects.len() as f64, n_misses);
+        let more = calculate(acc, n_misses + extra);
+
+        prop_assert!(more <= fewer, "{} misses: {}pp | {} misses: {}pp", n_misses, fewer, n_misses + extra, more);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn osu_partial_below_full(passed_objects in 1..601_usize) {
+        let (map, attrs) = osu_map();
+
+        let full = OsuPP::new(map).attributes(attrs.clone()).calculate().pp;
+        let partial = OsuPP::new(map).passed_objects(passed_objects).calculate().pp;
+
+        prop_assert!(partial <= full, "partial: {}pp | full: {}pp", partial, full);
+    }
+
+    #[test]
+    fn mania_partial_below_full(passed_objects in 1..3238_usize) {
+        let (map, attrs) = mania_map();
+
+        let full = ManiaPP::new(map).attributes(*attrs).calculate().pp;
+        let partial = ManiaPP::new(map).passed_objects(passed_objects).calculate().pp;
+
+        prop_assert!(partial <= full, "partial: {}pp | full: {}pp", partial, full);
+    }
+}
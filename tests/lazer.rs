@@ -0,0 +1,110 @@
+#![cfg(all(
+    feature = "lazer",
+    not(any(feature = "async_tokio", feature = "async_std"))
+))]
+
+use std::collections::HashSet;
+
+use akatsuki_pp::{GameMods, LazerMods, OsuStars};
+use serde::{Deserialize, Serialize};
+
+use crate::common::Osu;
+
+mod common;
+
+#[test]
+fn parse_dt_with_rate() {
+    let json = r#"[
+        {"acronym": "HD"},
+        {"acronym": "DT", "settings": {"speed_change": 1.3, "adjust_pitch": true}}
+    ]"#;
+
+    let mods = LazerMods::from_json(json).unwrap();
+
+    assert_eq!(mods.mods, 8 + 64);
+    assert_eq!(mods.speed_change, Some(1.3));
+    assert!((mods.clock_rate() - 1.3).abs() <= f64::EPSILON);
+
+    let map = test_map!(Osu);
+
+    let attrs = OsuStars::new(&map)
+        .mods(mods.mods)
+        .clock_rate(mods.clock_rate())
+        .calculate();
+
+    let expected = OsuStars::new(&map).mods(72).clock_rate(1.3).calculate();
+
+    assert_eq!(attrs, expected);
+}
+
+#[test]
+fn parse_without_settings() {
+    let mods = LazerMods::from_json(r#"[{"acronym": "NC"}, {"acronym": "CL"}]"#).unwrap();
+
+    assert_eq!(mods.mods, 64 + 512);
+    assert_eq!(mods.speed_change, None);
+    assert!((mods.clock_rate() - 1.5).abs() <= f64::EPSILON);
+
+    assert!(LazerMods::from_json(r#"[{"settings": {}}]"#).is_err());
+}
+
+#[test]
+fn lazer_mods_as_keys() {
+    let json = r#"[{"acronym": "DT", "settings": {"speed_change": 1.3}}]"#;
+
+    let mut set = HashSet::new();
+    set.insert(LazerMods::from_json(json).unwrap());
+    set.insert(LazerMods::from_json(json).unwrap());
+    set.insert(LazerMods::from_json(r#"[{"acronym": "DT"}]"#).unwrap());
+    set.insert(LazerMods {
+        mods: 64,
+        speed_change: Some(f64::NAN),
+    });
+    set.insert(LazerMods {
+        mods: 64,
+        speed_change: Some(f64::NAN),
+    });
+
+    assert_eq!(set.len(), 3);
+}
+
+#[test]
+fn game_mods_integer_round_trip() {
+    let mods = GameMods(8 + 64 + 512);
+    let json = serde_json::to_string(&mods).unwrap();
+
+    assert_eq!(json, "584");
+    assert_eq!(serde_json::from_str::<GameMods>(&json).unwrap(), mods);
+
+    // osu!api v1 returns the integer as string
+    assert_eq!(serde_json::from_str::<GameMods>(r#""584""#).unwrap(), mods);
+    assert!(serde_json::from_str::<GameMods>("-1").is_err());
+    assert!(serde_json::from_str::<GameMods>("4294967296").is_err());
+}
+
+#[test]
+fn game_mods_acronyms_round_trip() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Score {
+        #[serde(serialize_with = "GameMods::serialize_acronyms")]
+        mods: GameMods,
+    }
+
+    let score = Score {
+        mods: GameMods(8 + 64 + 512 + (1 << 29)),
+    };
+
+    let json = serde_json::to_string(&score).unwrap();
+
+    assert_eq!(json, r#"{"mods":["HD","NC","SV2"]}"#);
+    assert_eq!(serde_json::from_str::<Score>(&json).unwrap(), score);
+
+    let parse = |json| serde_json::from_str::<GameMods>(json).unwrap();
+
+    assert_eq!(parse(r#""HDNCSV2""#), score.mods);
+    assert_eq!(
+        parse(r#"[{"acronym": "HD"}, "nc", "SV2", "CL"]"#),
+        score.mods
+    );
+    assert_eq!(parse(r#"[]"#), GameMods(0));
+}
@@ -0,0 +1,229 @@
+#![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+
+use akatsuki_pp::{Beatmap, ManiaStars};
+
+const HEADER: &str = "osu file format v14\n\n[General]\nMode: 3\n\n[Difficulty]\nHPDrainRate:8\n\
+    CircleSize:4\nOverallDifficulty:8\nApproachRate:5\nSliderMultiplier:1.4\nSliderTickRate:1\n\n\
+    [TimingPoints]\n0,300,4,2,0,100,1,0\n\n[HitObjects]\n";
+
+// x positions of the four columns
+const COLUMNS: [u32; 4] = [64, 192, 320, 448];
+
+fn parse(content: &str) -> Beatmap {
+    Beatmap::from_bytes(content.as_bytes()).unwrap()
+}
+
+#[test]
+fn long_notes_increase_strain() {
+    let mut rice = HEADER.to_owned();
+    let mut long_notes = HEADER.to_owned();
+
+    for i in 0..200 {
+        let time = 1000 + i * 150;
+        let x = COLUMNS[i as usize % 4];
+
+        rice.push_str(&format!("{},192,{},1,0,0:0:0:0:\n", x, time));
+
+        // Hold column 1 while the other columns are being hit
+        if i % 4 == 1 {
+            long_notes.push_str(&format!(
+                "{},192,{},128,0,{}:0:0:0:0:\n",
+                x,
+                time,
+                time + 400
+            ));
+        } else {
+            long_notes.push_str(&format!("{},192,{},1,0,0:0:0:0:\n", x, time));
+        }
+    }
+
+    let rice_stars = ManiaStars::new(&parse(&rice)).calculate().stars;
+    let ln_stars = ManiaStars::new(&parse(&long_notes)).calculate().stars;
+
+    assert!(ln_stars > rice_stars, "{} <= {}", ln_stars, rice_stars);
+}
+
+/// Left hand alternates every note, right hand only plays every fourth one
+fn asymmetric() -> Beatmap {
+    let mut content = HEADER.to_owned();
+
+    for i in 0..200 {
+        let time = 1000 + i * 100;
+
+        content.push_str(&format!(
+            "{},192,{},1,0,0:0:0:0:\n",
+            COLUMNS[i as usize % 2],
+            time
+        ));
+
+        if i % 4 == 0 {
+            let x = COLUMNS[2 + (i as usize / 4) % 2];
+            content.push_str(&format!("{},192,{},1,0,0:0:0:0:\n", x, time));
+        }
+    }
+
+    parse(&content)
+}
+
+#[test]
+fn column_strains_asymmetric() {
+    let column_strains = ManiaStars::new(&asymmetric()).column_strains();
+
+    assert_eq!(column_strains.len(), 4);
+
+    let left = column_strains[0] + column_strains[1];
+    let right = column_strains[2] + column_strains[3];
+
+    assert!(left > right, "{:?}", column_strains);
+}
+
+#[test]
+fn mirror_flips_columns() {
+    const MR: u32 = 1 << 30;
+
+    let map = asymmetric();
+    let column_strains = ManiaStars::new(&map).column_strains();
+    let mut mirrored = ManiaStars::new(&map).mods(MR).column_strains();
+    mirrored.reverse();
+
+    assert_eq!(column_strains, mirrored);
+
+    let stars = ManiaStars::new(&map).calculate().stars;
+    let mirrored_stars = ManiaStars::new(&map).mods(MR).calculate().stars;
+
+    assert!(
+        (stars - mirrored_stars).abs() < 1e-9,
+        "{} vs {}",
+        stars,
+        mirrored_stars
+    );
+}
+
+#[test]
+fn lazer_hold_combo() {
+    let mut content = HEADER.to_owned();
+
+    // a circle and a hold note lasting 1000ms
+    content.push_str("64,192,1000,1,0,0:0:0:0:\n");
+    content.push_str("192,192,2000,128,0,3000:0:0:0:0:\n");
+
+    let map = parse(&content);
+
+    let stable = ManiaStars::new(&map).calculate();
+    let lazer = ManiaStars::new(&map).lazer(true).calculate();
+
+    assert_eq!(stable.max_combo, 1 + 1 + 10);
+    assert_eq!(lazer.max_combo, 1 + 2);
+}
+
+#[test]
+fn random_seed() {
+    let map = asymmetric();
+
+    let unseeded = ManiaStars::new(&map).column_strains();
+    let seeded = |seed| ManiaStars::new(&map).random_seed(seed).column_strains();
+
+    assert_eq!(seeded(42), seeded(42));
+
+    let mut sorted = seeded(42);
+    let mut expected = unseeded.clone();
+    sorted.sort_by(f64::total_cmp);
+    expected.sort_by(f64::total_cmp);
+
+    // only the column order changes
+    assert_eq!(sorted, expected);
+    assert!((0..10).any(|seed| seeded(seed) != seeded(42)));
+}
+
+#[test]
+fn strain_consistency() {
+    let mut flat = HEADER.to_owned();
+    let mut spiky = HEADER.to_owned();
+
+    for i in 0..400 {
+        let x = COLUMNS[i as usize % 4];
+        flat.push_str(&format!("{},192,{},1,0,0:0:0:0:\n", x, 1000 + i * 150));
+
+        // Dense bursts of 20 notes inbetween slow sections
+        let time = if (i / 20) % 2 == 0 {
+            1000 + (i / 40) * 6000 + (i % 20) * 50
+        } else {
+            1000 + (i / 40) * 6000 + 1000 + (i % 20) * 250
+        };
+
+        spiky.push_str(&format!("{},192,{},1,0,0:0:0:0:\n", x, time));
+    }
+
+    let flat = ManiaStars::new(&parse(&flat)).strains();
+    let spiky = ManiaStars::new(&parse(&spiky)).strains();
+
+    assert!(flat.mean() > 0.0);
+    // Only the strain's ramp-up at the start deviates
+    assert!(flat.spikiness() < 0.1, "{}", flat.spikiness());
+    assert!(
+        (flat.variance() - flat.std_dev() * flat.std_dev()).abs() < 1e-9,
+        "{}",
+        flat.variance()
+    );
+    assert!(
+        flat.spikiness() < spiky.spikiness(),
+        "{} >= {}",
+        flat.spikiness(),
+        spiky.spikiness()
+    );
+}
+
+#[test]
+fn hit_window_without_rate_adjustment() {
+    let mut map = asymmetric();
+    map.od = 8.1;
+
+    let nomod = ManiaStars::new(&map).calculate();
+    let dt = ManiaStars::new(&map).mods(64).calculate();
+    let isolated = ManiaStars::new(&map)
+        .mods(64)
+        .rate_adjusted_hit_window(false)
+        .calculate();
+
+    // The window of 39.7ms is rounded differently in terms of the rate adjusted time
+    assert_eq!(nomod.hit_window, 39.0);
+    assert_eq!(dt.hit_window, 40.0);
+    assert_eq!(isolated.hit_window, nomod.hit_window);
+
+    // The clock rate still applies to the strain
+    assert_eq!(isolated.stars, dt.stars);
+    assert!(isolated.stars > nomod.stars);
+}
+
+#[test]
+fn strain_peak_at_spike() {
+    let mut content = HEADER.to_owned();
+
+    for i in 0..200 {
+        let x = COLUMNS[i as usize % 4];
+
+        // Dense burst of 40 notes between 16000ms and 17950ms
+        let time = match i {
+            0..=99 => 1000 + i * 150,
+            100..=139 => 16000 + (i - 100) * 50,
+            _ => 18500 + (i - 140) * 150,
+        };
+
+        content.push_str(&format!("{},192,{},1,0,0:0:0:0:\n", x, time));
+    }
+
+    let map = parse(&content);
+    let strains = ManiaStars::new(&map).strains();
+    let (time, peak) = strains.peak().unwrap();
+
+    assert!((peak - strains.summary().max).abs() <= f64::EPSILON);
+
+    // The second note at 1150ms ends the first section at 1200ms
+    assert!((strains.start_time - 800.0).abs() <= f64::EPSILON);
+    assert!((16000.0..18400.0).contains(&time), "{}", time);
+
+    // The time is in terms of the map's timestamps regardless of the clock rate
+    let dt = ManiaStars::new(&map).mods(64).strains();
+    let (dt_time, _) = dt.peak().unwrap();
+    assert!((16000.0..18400.0).contains(&dt_time), "{}", dt_time);
+}
@@ -0,0 +1,29 @@
+#![cfg(all(
+    feature = "metrics",
+    not(any(feature = "async_tokio", feature = "async_std"))
+))]
+
+use std::fs::File;
+
+use akatsuki_pp::Beatmap;
+
+use crate::common::{Mania, Mode, Osu};
+
+mod common;
+
+#[test]
+fn stats_are_populated() {
+    let path = format!("./maps/{}.osu", Osu::TEST_MAP_ID);
+    let (map, stats) = Beatmap::parse_with_stats(File::open(path).unwrap()).unwrap();
+
+    assert_eq!(stats.n_objects, map.hit_objects.len());
+    assert_eq!(stats.n_sliders, map.n_sliders as usize);
+    assert!(stats.n_sliders > 0);
+    assert!(stats.duration.as_nanos() > 0);
+
+    let path = format!("./maps/{}.osu", Mania::TEST_MAP_ID);
+    let (map, stats) = Beatmap::parse_with_stats(File::open(path).unwrap()).unwrap();
+
+    assert_eq!(stats.n_objects, map.hit_objects.len());
+    assert_eq!(stats.n_sliders, map.n_sliders as usize);
+}
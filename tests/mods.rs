@@ -0,0 +1,159 @@
+#![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+
+use std::collections::HashMap;
+
+use akatsuki_pp::{
+    mania::ManiaGradualDifficultyAttributes, osu::OsuDifficultyAttributes, DifficultyAttributes,
+    GameMode, ManiaPP, ManiaStars, Mods, OsuPP, OsuStars,
+};
+
+use crate::common::{Mania, Osu};
+
+mod common;
+
+const DT: u32 = 64;
+const HT: u32 = 256;
+
+#[test]
+fn speed_change_rates() {
+    assert!((DT.speed_change(1.3) - 1.3).abs() <= f64::EPSILON);
+    assert!((HT.speed_change(0.6) - 0.6).abs() <= f64::EPSILON);
+    assert!((0.speed_change(1.3) - 1.0).abs() <= f64::EPSILON);
+    assert!((DT.speed_change(3.0) - 2.0).abs() <= f64::EPSILON);
+    assert!((DT.speed_change(1.0) - 1.01).abs() <= f64::EPSILON);
+    assert!((HT.speed_change(1.3) - 0.99).abs() <= f64::EPSILON);
+}
+
+#[test]
+fn without_difficulty_mods() {
+    // HDHRDTNCFL -> HD
+    assert_eq!((8 + 16 + 64 + 512 + 1024_u32).without_difficulty_mods(), 8);
+    // NFEZHT -> NF
+    assert_eq!((1 + 2 + HT).without_difficulty_mods(), 1);
+}
+
+#[test]
+fn dt_custom_rate() {
+    let map = test_map!(Osu);
+
+    let nm = OsuStars::new(&map).calculate();
+    let dt = OsuStars::new(&map).mods(DT).calculate();
+    let dt_1_3 = OsuStars::new(&map)
+        .mods(DT)
+        .clock_rate(DT.speed_change(1.3))
+        .calculate();
+
+    assert!(nm.stars < dt_1_3.stars);
+    assert!(dt_1_3.stars < dt.stars);
+
+    let pp = OsuPP::new(&map)
+        .mods(DT)
+        .clock_rate(DT.speed_change(1.3))
+        .calculate();
+
+    assert_eq!(pp.difficulty, dt_1_3);
+}
+
+#[test]
+fn description() {
+    assert_eq!((8 + DT).description(), "Hidden, Double Time");
+    // NC implies DT, PF implies SD
+    assert_eq!(
+        (8 + 16 + DT + 512).description(),
+        "Hidden, Hard Rock, Nightcore"
+    );
+    assert_eq!((1 << 14 | 1 << 5).description(), "Perfect");
+    assert_eq!(
+        (1 + HT + (1 << 30)).description(),
+        "No Fail, Half Time, Mirror"
+    );
+    assert_eq!(0.description(), "");
+}
+
+#[test]
+fn mode_and_mods_as_keys() {
+    let map = test_map!(Osu);
+    let mut cache: HashMap<(GameMode, u32), OsuDifficultyAttributes> = HashMap::new();
+
+    for &mods in [0, DT, HT, DT].iter() {
+        cache
+            .entry((map.mode, mods))
+            .or_insert_with(|| OsuStars::new(&map).mods(mods).calculate());
+    }
+
+    assert_eq!(cache.len(), 3);
+    assert!(cache.contains_key(&(GameMode::Osu, DT)));
+    assert!(!cache.contains_key(&(GameMode::Taiko, DT)));
+}
+
+#[test]
+fn key_mods_force_columns() {
+    const KEY4: u32 = 1 << 15;
+    const KEY7: u32 = 1 << 18;
+
+    assert_eq!(KEY4.key_count(), Some(4));
+    assert_eq!((KEY7 + DT).key_count(), Some(7));
+    assert_eq!((1_u32 << 26).key_count(), Some(1));
+    assert_eq!((1_u32 << 24).key_count(), Some(9));
+    assert_eq!(DT.key_count(), None);
+
+    let mut map = test_map!(Osu);
+
+    for &cs in &[2.0, 4.5, 8.0] {
+        map.cs = cs;
+
+        let columns = ManiaStars::new(&map).mods(KEY4).column_strains();
+        assert_eq!(columns.len(), 4, "CS {}", cs);
+
+        let columns = ManiaStars::new(&map).mods(KEY7 + DT).column_strains();
+        assert_eq!(columns.len(), 7, "CS {}", cs);
+    }
+
+    let stars = ManiaStars::new(&map).mods(KEY4).calculate();
+    let pp = ManiaPP::new(&map).mods(KEY4).calculate();
+    let converted = OsuStars::new(&map)
+        .mods(KEY4)
+        .mode(GameMode::Mania)
+        .calculate();
+    let gradual = ManiaGradualDifficultyAttributes::new(&map, KEY4).last();
+
+    assert_ne!(stars, ManiaStars::new(&map).calculate());
+    assert_eq!(stars, pp.difficulty);
+    match converted {
+        DifficultyAttributes::Mania(converted) => assert_eq!(stars, converted),
+        _ => panic!("expected mania attributes"),
+    }
+    assert_eq!(Some(stars), gradual);
+
+    // Key mods don't affect osu!mania maps themselves
+    let map = test_map!(Mania);
+    let columns = ManiaStars::new(&map).column_strains().len();
+
+    assert_eq!(
+        ManiaStars::new(&map).mods(KEY7).column_strains().len(),
+        columns
+    );
+}
+
+#[test]
+fn score_multiplier() {
+    let ez = u32::EZ;
+
+    for mode in [
+        GameMode::Osu,
+        GameMode::Taiko,
+        GameMode::Catch,
+        GameMode::Mania,
+    ] {
+        assert!((ez.score_multiplier(mode) - 0.5).abs() <= f64::EPSILON);
+        assert!((0.score_multiplier(mode) - 1.0).abs() <= f64::EPSILON);
+        assert!(((u32::EZ | u32::NF).score_multiplier(mode) - 0.25).abs() <= f64::EPSILON);
+    }
+
+    // difficulty increasing mods only reward outside of mania
+    let hdhr = u32::HD | u32::HR;
+    assert!((hdhr.score_multiplier(GameMode::Osu) - 1.06 * 1.06).abs() <= f64::EPSILON);
+    assert!((hdhr.score_multiplier(GameMode::Catch) - 1.06 * 1.12).abs() <= f64::EPSILON);
+    assert!((hdhr.score_multiplier(GameMode::Mania) - 1.0).abs() <= f64::EPSILON);
+    assert!((u32::HT.score_multiplier(GameMode::Mania) - 0.5).abs() <= f64::EPSILON);
+}
@@ -1,4 +1,10 @@
-use rosu_pp::{Beatmap, GameMode};
+use std::{
+    error::Error as StdError,
+    io::{Error as IoError, ErrorKind as IoErrorKind},
+    thread,
+};
+
+use akatsuki_pp::{Beatmap, GameMode, ParseError};
 
 use crate::common::{Catch, Mania, Osu, Taiko};
 
@@ -6,6 +12,8 @@ mod common;
 
 #[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
 mod sync {
+    use akatsuki_pp::{parse::Pos2, OsuStars};
+
     use super::*;
 
     #[test]
@@ -27,6 +35,211 @@ mod sync {
     fn parse_mania() {
         assert_mania(test_map!(Mania));
     }
+
+    #[test]
+    fn bad_difficulty_line() {
+        let bytes = b"osu file format v14\n\n[Difficulty]\nHPDrainRate:5\nCircleSize\n";
+
+        match Beatmap::from_bytes(bytes) {
+            Err(ParseError::InvalidDifficultyLine { line: 5 }) => {}
+            other => panic!("expected invalid difficulty line, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bad_general_line() {
+        let bytes = b"osu file format v14\n[General]\nMode 0\n[HitObjects]\n";
+
+        match Beatmap::from_bytes(bytes) {
+            Err(ParseError::BadLine { line: 3 }) => {}
+            other => panic!("expected bad line, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn md5() {
+        let mut map = test_map!(Osu);
+        assert_eq!(map.md5().unwrap(), "3ca9a5e23c9a4e9332f4f6e2a48865f7");
+
+        // modifying the map does not affect the hash of the original content
+        map.od = 10.0;
+        map.hit_objects.truncate(10);
+        assert_eq!(map.md5().unwrap(), "3ca9a5e23c9a4e9332f4f6e2a48865f7");
+
+        assert_eq!(
+            test_map!(Mania).md5().unwrap(),
+            "6399f31397f2b6adae6d164d8893c562"
+        );
+    }
+
+    #[test]
+    fn md5_of_raw_bytes() {
+        // BOM, `\r\n`, and comments are part of the hash
+        let bytes = b"\xef\xbb\xbfosu file format v14\r\n\r\n[HitObjects]\r\n256,192,1000,1,0 // comment\r\n";
+        let map = Beatmap::from_bytes(bytes).unwrap();
+
+        assert_eq!(map.md5().unwrap(), "86778b23fd8043bef525c029b6c850fe");
+        assert!(Beatmap::default().md5().is_none());
+    }
+
+    #[test]
+    fn missing_hitobjects() {
+        let bytes = b"osu file format v14\n[General]\nMode: 0\n";
+        let map = Beatmap::from_bytes(bytes).unwrap();
+
+        assert!(map.hit_objects.is_empty());
+    }
+
+    #[test]
+    fn audio_lead_in() {
+        let map = test_map!(Osu);
+
+        assert!(map.audio_lead_in.abs() <= f64::EPSILON);
+        assert!((map.preview_time - 25309.0).abs() <= f64::EPSILON);
+
+        let bytes = b"osu file format v14\n\n[General]\nAudioLeadIn: 1500\nPreviewTime: -1\n\n\
+            [HitObjects]\n256,192,1000,1,0\n";
+        let map = Beatmap::from_bytes(bytes).unwrap();
+
+        assert!((map.audio_lead_in - 1500.0).abs() <= f64::EPSILON);
+        assert!((map.preview_time + 1.0).abs() <= f64::EPSILON);
+    }
+
+    #[test]
+    fn slider_multiplier_and_tick_rate() {
+        let map = test_map!(Osu);
+        let max_combo = OsuStars::new(&map).calculate().max_combo;
+
+        assert!((map.slider_mult - 1.7).abs() <= f64::EPSILON);
+        assert!((map.tick_rate - 1.0).abs() <= f64::EPSILON);
+
+        // slower sliders have more ticks along the same path
+        let mut slow = map.clone();
+        slow.slider_mult = 0.85;
+        let slow_combo = OsuStars::new(&slow).calculate().max_combo;
+
+        assert!(slow_combo > max_combo, "{} <= {}", slow_combo, max_combo);
+
+        // halving the tick rate as well restores the tick distance
+        let mut ticks = slow.clone();
+        ticks.tick_rate = 0.5;
+        let ticks_combo = OsuStars::new(&ticks).calculate().max_combo;
+
+        assert_eq!(ticks_combo, max_combo);
+
+        let bytes = b"osu file format v14\n\n[Difficulty]\nSliderMultiplier:0.85\n\
+            SliderTickRate:0.5\n\n[HitObjects]\n256,192,1000,1,0\n";
+        let map = Beatmap::from_bytes(bytes).unwrap();
+
+        assert!((map.slider_mult - 0.85).abs() <= f64::EPSILON);
+        assert!((map.tick_rate - 0.5).abs() <= f64::EPSILON);
+    }
+
+    #[test]
+    fn background_and_storyboard() {
+        let map = test_map!(Osu);
+        assert_eq!(map.background_filename(), Some("87195968_p0.jpg"));
+        assert!(!map.has_storyboard);
+
+        let bytes = b"osu file format v14\n\n[Events]\n0,0,\"bg, with comma.png\",0,0\n\
+            Sprite,Foreground,Centre,\"sb\\star.png\",320,240\n\n[HitObjects]\n256,192,1000,1,0\n";
+        let map = Beatmap::from_bytes(bytes).unwrap();
+
+        assert_eq!(map.background_filename(), Some("bg, with comma.png"));
+        assert!(map.has_storyboard);
+
+        let bytes = b"osu file format v14\n\n[Events]\n0,0,bg.jpg\n\n[HitObjects]\n";
+        let map = Beatmap::from_bytes(bytes).unwrap();
+
+        assert_eq!(map.background_filename(), Some("bg.jpg"));
+        assert!(Beatmap::default().background_filename().is_none());
+    }
+
+    #[test]
+    fn metadata() {
+        let map = test_map!(Osu);
+
+        assert_eq!(map.title, "re[in]flaw");
+        assert_eq!(map.title_unicode, "re[in]flaw");
+        assert_eq!(map.artist, "MYUKKE.");
+        assert_eq!(map.artist_unicode, "MYUKKE.");
+        assert_eq!(map.creator, "captin1");
+        assert_eq!(map.difficulty_name, "toybot's Expert");
+        assert_eq!(map.source, "");
+        assert!(map.tags.starts_with("fa featured artist anna apple"));
+        assert_eq!(map.beatmap_id, 2785319);
+        assert_eq!(map.beatmap_set_id, 1344871);
+
+        let bytes = "osu file format v14\n\n[Metadata]\nTitle:Kimi no Shiranai Monogatari\n\
+            TitleUnicode:君の知らない物語\nArtist:supercell\nArtistUnicode:スーパーセル\n\
+            Creator:someone\nVersion:Insane: Extra\nSource:化物語\nTags:bakemonogatari\n\
+            BeatmapID:-1\nBeatmapSetID:-1\n\n[HitObjects]\n256,192,1000,1,0\n";
+        let map = Beatmap::from_bytes(bytes.as_bytes()).unwrap();
+
+        assert_eq!(map.title_unicode, "君の知らない物語");
+        assert_eq!(map.artist_unicode, "スーパーセル");
+        assert_eq!(map.difficulty_name, "Insane: Extra");
+        assert_eq!(map.source, "化物語");
+        assert_eq!(map.tags, "bakemonogatari");
+
+        // Unsubmitted maps have negative IDs
+        assert_eq!(map.beatmap_id, 0);
+        assert_eq!(map.beatmap_set_id, 0);
+    }
+
+    #[test]
+    fn new_combo_flags() {
+        let map = test_map!(Osu);
+        let new_combos: Vec<_> = map.hit_objects.iter().map(|h| h.new_combo).collect();
+
+        assert_eq!(
+            new_combos.iter().filter(|&&new_combo| new_combo).count(),
+            170
+        );
+        assert_eq!(
+            &new_combos[..11],
+            &[true, false, false, false, false, false, false, false, false, false, true]
+        );
+
+        // Combo color skips are set alongside the flag
+        let bytes = "osu file format v14\n\n[HitObjects]\n\
+            256,192,1000,5,0\n256,192,1500,1,0\n256,192,2000,21,0\n256,192,2500,1,0\n";
+        let map = Beatmap::from_bytes(bytes.as_bytes()).unwrap();
+        let new_combos: Vec<_> = map.hit_objects.iter().map(|h| h.new_combo).collect();
+
+        assert_eq!(new_combos, [true, false, true, false]);
+    }
+
+    #[test]
+    fn hr_flipped_position() {
+        let bytes = "osu file format v14\n\n[HitObjects]\n\
+            100,50,1000,1,0\n512,384,1500,1,0\n";
+        let map = Beatmap::from_bytes(bytes.as_bytes()).unwrap();
+        let positions = |mode, mods| -> Vec<_> {
+            map.hit_objects
+                .iter()
+                .map(|h| h.position(mode, mods))
+                .collect()
+        };
+
+        let unchanged = [Pos2 { x: 100.0, y: 50.0 }, Pos2 { x: 512.0, y: 384.0 }];
+
+        assert_eq!(positions(GameMode::Osu, 0), unchanged);
+        assert_eq!(positions(GameMode::Catch, 16), unchanged);
+
+        assert_eq!(
+            positions(GameMode::Osu, 16),
+            [Pos2 { x: 100.0, y: 334.0 }, Pos2 { x: 512.0, y: 0.0 }]
+        );
+    }
+
+    #[test]
+    fn missing_file() {
+        match Beatmap::from_path("./maps/does_not_exist.osu") {
+            Err(ParseError::IoError(_)) => {}
+            other => panic!("expected io error, got {:?}", other),
+        }
+    }
 }
 
 #[cfg(feature = "async_tokio")]
@@ -93,6 +306,24 @@ mod async_tokio {
     }
 }
 
+#[test]
+fn error_send_sync() {
+    fn boxed(err: ParseError) -> Box<dyn StdError + Send + Sync + 'static> {
+        Box::new(err)
+    }
+
+    let io_err = IoError::new(IoErrorKind::NotFound, "no map");
+    let err = boxed(ParseError::from(io_err));
+
+    let handle = thread::spawn(move || {
+        assert!(err.source().is_some());
+
+        err.to_string()
+    });
+
+    assert_eq!(handle.join().unwrap(), "IO error");
+}
+
 fn assert_osu(map: Beatmap) {
     assert_eq!(map.mode, GameMode::Osu);
     assert_eq!(map.version, 14);
@@ -111,6 +342,8 @@ fn assert_osu(map: Beatmap) {
     assert_eq!(map.difficulty_points.len(), 50);
     assert_eq!(map.effect_points.len(), 131);
     assert!((map.stack_leniency - 0.5).abs() <= f32::EPSILON);
+    assert!(map.audio_lead_in.abs() <= f64::EPSILON);
+    assert!((map.preview_time - 25309.0).abs() <= f64::EPSILON);
     assert_eq!(map.breaks.len(), 1)
 }
 
@@ -0,0 +1,761 @@
+#![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+
+use akatsuki_pp::{
+    catch::CatchScoreState,
+    mania::ManiaScoreState,
+    osu::{accuracy_from_unstable_rate, effective_miss_count, OsuScoreState},
+    taiko::TaikoScoreState,
+    Accuracy, AnyPP, CatchPP, CatchStars, GradualPerformanceAttributes, HitResult,
+    HitResultPriority, Judgement, ManiaPP, ManiaStars, OsuPP, OsuStars, PerformanceAttributes,
+    TaikoPP, TaikoStars,
+};
+
+use crate::common::{Catch, Mania, Osu, Taiko};
+
+mod common;
+
+#[test]
+fn effective_misses() {
+    // (909 - 0.1 * 293) / 500
+    let estimated = effective_miss_count(500, 909, 1, 293);
+    assert!((estimated - 1.7594).abs() < 1e-10, "{}", estimated);
+
+    // combo above the full combo threshold
+    assert!((effective_miss_count(880, 909, 0, 293) - 0.0).abs() <= f64::EPSILON);
+    // no sliders
+    assert!((effective_miss_count(100, 909, 3, 0) - 3.0).abs() <= f64::EPSILON);
+    // actual misses exceed the estimate
+    assert!((effective_miss_count(500, 909, 4, 293) - 4.0).abs() <= f64::EPSILON);
+    // zero combo counts as one
+    assert!((effective_miss_count(0, 909, 0, 293) - 879.7).abs() < 1e-10);
+}
+
+#[test]
+fn unstable_rate_accuracy() {
+    let low = accuracy_from_unstable_rate(8.8, 60.0);
+    let high = accuracy_from_unstable_rate(8.8, 200.0);
+
+    assert!(low > 0.995, "{}", low);
+    assert!(high < low, "{} >= {}", high, low);
+    assert!((accuracy_from_unstable_rate(8.8, 0.0) - 1.0).abs() <= f64::EPSILON);
+
+    for ur in [f64::NAN, f64::INFINITY] {
+        assert!(accuracy_from_unstable_rate(8.8, ur).abs() <= f64::EPSILON);
+    }
+
+    let acc = accuracy_from_unstable_rate(f64::NAN, 120.0);
+    assert!((0.0..=1.0).contains(&acc), "{}", acc);
+
+    let map = test_map!(Osu);
+    let attrs = OsuStars::new(&map).calculate();
+    let acc = accuracy_from_unstable_rate(attrs.od, 120.0);
+
+    let by_ur = OsuPP::new(&map)
+        .attributes(attrs.clone())
+        .unstable_rate(120.0)
+        .calculate();
+
+    let by_acc = OsuPP::new(&map)
+        .attributes(attrs)
+        .accuracy(Accuracy::from_fraction(acc).unwrap())
+        .calculate();
+
+    assert_eq!(by_ur, by_acc);
+}
+
+#[test]
+fn taiko_length_bonus() {
+    let map = test_map!(Taiko);
+    let attrs = TaikoStars::new(&map).calculate();
+
+    let pp = |max_combo| {
+        let mut attrs = attrs.clone();
+        attrs.max_combo = max_combo;
+
+        TaikoPP::new(&map)
+            .attributes(attrs)
+            .accuracy(Accuracy::from_percent(98.0).unwrap())
+            .calculate()
+    };
+
+    let short = pp(300);
+    let long = pp(1500);
+
+    assert!((short.stars() - long.stars()).abs() <= f64::EPSILON);
+    assert!(long.pp_difficulty > short.pp_difficulty);
+    assert!(long.pp_acc > short.pp_acc);
+    assert!(long.pp > short.pp, "{} <= {}", long.pp, short.pp);
+}
+
+#[test]
+fn catch_combo_scaling() {
+    let map = test_map!(Catch);
+    let attrs = CatchStars::new(&map).calculate();
+    let max_combo = attrs.max_combo();
+
+    let full = CatchPP::new(&map)
+        .attributes(attrs.clone())
+        .combo(max_combo)
+        .misses(1)
+        .calculate();
+
+    let dropped = CatchPP::new(&map)
+        .attributes(attrs)
+        .combo(max_combo / 2)
+        .misses(1)
+        .calculate();
+
+    assert!(dropped.pp < full.pp, "{} >= {}", dropped.pp, full.pp);
+}
+
+#[test]
+fn nomod_comparison() {
+    let map = test_map!(Osu);
+
+    let played = AnyPP::new(&map)
+        .mods(8 + 64) // HDDT
+        .accuracy(Accuracy::from_percent(98.5).unwrap())
+        .combo(500)
+        .n_misses(1);
+
+    let dt = played.clone().calculate();
+    let nomod = played.nomod().calculate();
+
+    assert!(nomod.pp() < dt.pp(), "{} >= {}", nomod.pp(), dt.pp());
+
+    let hidden = AnyPP::new(&map)
+        .mods(8) // HD
+        .accuracy(Accuracy::from_percent(98.5).unwrap())
+        .combo(500)
+        .n_misses(1)
+        .calculate();
+
+    assert!((nomod.pp() - hidden.pp()).abs() < f64::EPSILON);
+}
+
+#[test]
+fn judgement_timeline() {
+    let map = test_map!(Osu);
+
+    let judgements: Vec<_> = map.hit_objects[..20]
+        .iter()
+        .enumerate()
+        .map(|(i, h)| Judgement {
+            time: h.start_time,
+            result: match i {
+                5 => HitResult::N100,
+                12 => HitResult::Miss,
+                _ => HitResult::N300,
+            },
+        })
+        .collect();
+
+    let result = GradualPerformanceAttributes::from_judgements(&map, 0, &judgements);
+
+    // the first object is processed alongside the second one in osu!standard
+    assert_eq!(result.curve.len(), 19);
+    assert!(result
+        .curve
+        .iter()
+        .zip(judgements.iter().skip(1))
+        .all(|((time, _), judgement)| (time - judgement.time).abs() < f64::EPSILON));
+
+    let attrs = match result.attributes {
+        Some(PerformanceAttributes::Osu(ref attrs)) => attrs.clone(),
+        ref other => panic!("expected osu attributes, got {:?}", other),
+    };
+
+    // max combo up to the given amount of objects
+    let max_combo = |n| OsuStars::new(&map).passed_objects(n).calculate().max_combo;
+    let combo = max_combo(12).max(max_combo(20) - max_combo(13));
+
+    let expected = OsuPP::new(&map)
+        .attributes(attrs.difficulty.clone())
+        .passed_objects(20)
+        .n300(18)
+        .n100(1)
+        .n_misses(1)
+        .combo(combo)
+        .calculate();
+
+    assert!(
+        (result.pp() - expected.pp).abs() < 1e-9,
+        "{} vs {}",
+        result.pp(),
+        expected.pp
+    );
+
+    let empty = GradualPerformanceAttributes::from_judgements(&map, 0, &[]);
+    assert!(empty.attributes.is_none());
+    assert!(empty.curve.is_empty());
+}
+
+#[test]
+fn judgement_timeline_catch() {
+    let map = test_map!(Catch);
+    let attrs = CatchStars::new(&map).calculate();
+    let n_combo = attrs.n_fruits + attrs.n_droplets;
+    let per_object = attrs.n_tiny_droplets.div_ceil(n_combo);
+
+    let mut judgements = Vec::new();
+    let mut n_tiny = 0;
+
+    for i in 0..n_combo {
+        // tiny droplets come in between fruits and droplets
+        for _ in 0..per_object.min(attrs.n_tiny_droplets - n_tiny) {
+            let result = if n_tiny % 4 == 0 {
+                HitResult::TinyDropletMiss
+            } else {
+                HitResult::N50
+            };
+
+            judgements.push(Judgement { time: 0.0, result });
+            n_tiny += 1;
+        }
+
+        let result = match i {
+            10 => HitResult::Miss,
+            i if i < attrs.n_fruits => HitResult::N300,
+            _ => HitResult::N100,
+        };
+
+        judgements.push(Judgement {
+            time: i as f64,
+            result,
+        });
+    }
+
+    let result = GradualPerformanceAttributes::from_judgements(&map, 0, &judgements);
+
+    // one value per fruit and droplet
+    assert_eq!(result.curve.len(), n_combo);
+
+    let n_tiny_droplet_misses = attrs.n_tiny_droplets.div_ceil(4);
+
+    let state = CatchScoreState {
+        max_combo: 10.max(n_combo - 11),
+        n_fruits: attrs.n_fruits - 1,
+        n_droplets: attrs.n_droplets,
+        n_tiny_droplets: attrs.n_tiny_droplets - n_tiny_droplet_misses,
+        n_tiny_droplet_misses,
+        n_misses: 1,
+    };
+
+    let expected = CatchPP::new(&map)
+        .attributes(attrs)
+        .state(state)
+        .calculate();
+
+    match result.attributes {
+        Some(PerformanceAttributes::Catch(attrs)) => assert_eq!(attrs, expected),
+        other => panic!("expected catch attributes, got {:?}", other),
+    }
+}
+
+#[test]
+fn judgement_timeline_mania() {
+    let map = test_map!(Mania);
+    let attrs = ManiaStars::new(&map).calculate();
+
+    let results = [
+        HitResult::N320,
+        HitResult::N320,
+        HitResult::N300,
+        HitResult::N320,
+        HitResult::N200,
+        HitResult::N320,
+        HitResult::N100,
+        HitResult::N50,
+        HitResult::N320,
+        HitResult::Miss,
+    ];
+
+    let judgements: Vec<_> = map
+        .hit_objects
+        .iter()
+        .zip(results.iter().cycle())
+        .map(|(h, &result)| Judgement {
+            time: h.start_time,
+            result,
+        })
+        .collect();
+
+    let result = GradualPerformanceAttributes::from_judgements(&map, 0, &judgements);
+
+    // the first object is processed alongside the second one in osu!mania
+    assert_eq!(result.curve.len(), map.hit_objects.len() - 1);
+
+    let count = |hit_result| {
+        judgements
+            .iter()
+            .filter(|judgement| judgement.result == hit_result)
+            .count()
+    };
+
+    let state = ManiaScoreState {
+        n320: count(HitResult::N320),
+        n300: count(HitResult::N300),
+        n200: count(HitResult::N200),
+        n100: count(HitResult::N100),
+        n50: count(HitResult::N50),
+        n_misses: count(HitResult::Miss),
+    };
+
+    let expected = ManiaPP::new(&map)
+        .attributes(attrs)
+        .state(state)
+        .calculate();
+
+    match result.attributes {
+        Some(PerformanceAttributes::Mania(attrs)) => assert_eq!(attrs, expected),
+        other => panic!("expected mania attributes, got {:?}", other),
+    }
+}
+
+#[test]
+fn judgement_timeline_taiko() {
+    let map = test_map!(Taiko);
+    let attrs = TaikoStars::new(&map).calculate();
+
+    let judgements: Vec<_> = (0..attrs.max_combo)
+        .map(|i| Judgement {
+            time: i as f64,
+            result: if i == 50 {
+                HitResult::N100
+            } else {
+                HitResult::N300
+            },
+        })
+        .collect();
+
+    let result = GradualPerformanceAttributes::from_judgements(&map, 0, &judgements);
+
+    let expected = TaikoPP::new(&map)
+        .attributes(attrs.clone())
+        .n100(1)
+        .n_misses(0)
+        .combo(attrs.max_combo)
+        .calculate();
+
+    match result.attributes {
+        Some(PerformanceAttributes::Taiko(attrs)) => assert_eq!(attrs, expected),
+        other => panic!("expected taiko attributes, got {:?}", other),
+    }
+}
+
+#[test]
+fn slider_break_vs_miss() {
+    let map = test_map!(Osu);
+    let attrs = OsuStars::new(&map).calculate();
+    let n_objects = map.hit_objects.len();
+
+    let calculator = OsuPP::new(&map).attributes(attrs.clone());
+
+    let full_combo = calculator
+        .clone()
+        .combo(attrs.max_combo)
+        .n300(n_objects - 1)
+        .n100(1)
+        .n_misses(0)
+        .calculate();
+
+    // the slider is still hit but the combo breaks
+    let slider_break = calculator
+        .clone()
+        .combo(attrs.max_combo)
+        .n300(n_objects - 1)
+        .n100(1)
+        .n_misses(0)
+        .n_slider_breaks(1)
+        .calculate();
+
+    let miss = calculator
+        .combo(attrs.max_combo)
+        .n300(n_objects - 1)
+        .n100(0)
+        .n_misses(1)
+        .calculate();
+
+    assert!((slider_break.effective_miss_count - 1.0).abs() <= f64::EPSILON);
+    assert!((miss.effective_miss_count - 1.0).abs() <= f64::EPSILON);
+
+    // same combo penalty but a slider break keeps the accuracy of a hit
+    assert!(slider_break.pp < full_combo.pp);
+    assert!(
+        slider_break.pp > miss.pp,
+        "{} <= {}",
+        slider_break.pp,
+        miss.pp
+    );
+    assert!((slider_break.pp_acc - full_combo.pp_acc).abs() <= f64::EPSILON);
+}
+
+#[test]
+fn taiko_finishers_count_once() {
+    let map = test_map!(Taiko);
+
+    // turn every note into a finisher
+    let mut finishers = map.clone();
+    finishers
+        .sounds
+        .iter_mut()
+        .for_each(|sound| *sound |= 1 << 2);
+
+    let calculate = |map| {
+        TaikoPP::new(map)
+            .n300(500)
+            .n100(20)
+            .n_misses(2)
+            .combo(400)
+            .calculate()
+    };
+
+    let regular = calculate(&map);
+    let big = calculate(&finishers);
+
+    assert_eq!(regular.difficulty, big.difficulty);
+    assert!((regular.pp - big.pp).abs() <= f64::EPSILON);
+}
+
+#[test]
+fn mania_score_v2_accuracy() {
+    let map = test_map!(Mania);
+    const V2: u32 = 1 << 29;
+
+    let calculator = ManiaPP::new(&map)
+        .hitresult_priority(HitResultPriority::WorstCase)
+        .accuracy(Accuracy::from_percent(95.0).unwrap());
+
+    let v1 = calculator.clone().calculate();
+    let v2_mod = calculator.clone().mods(V2).calculate();
+    let v2_explicit = calculator.clone().score_v2(true).calculate();
+    let v2_disabled = calculator.mods(V2).score_v2(false).calculate();
+
+    // 300s are worth less in ScoreV2 so the same accuracy requires better hitresults
+    assert!(v1.pp < v2_mod.pp, "{} >= {}", v1.pp, v2_mod.pp);
+    assert!((v2_mod.pp - v2_explicit.pp).abs() <= f64::EPSILON);
+    assert!((v1.pp - v2_disabled.pp).abs() <= f64::EPSILON);
+}
+
+#[test]
+fn each_mod_matches_individual() {
+    let osu = test_map!(Osu);
+    let taiko = test_map!(Taiko);
+    let catch = test_map!(Catch);
+    let mania = test_map!(Mania);
+
+    // NF, HD, HR, DT, FL, SO, HDHR
+    let extra = [1, 8, 16, 64, 1024, 4096, 8 + 16];
+
+    let assert_same = |actual: &PerformanceAttributes, expected: PerformanceAttributes, mods| {
+        assert!(
+            (actual.pp() - expected.pp()).abs() <= f64::EPSILON,
+            "mods {}: {} vs {}",
+            mods,
+            actual.pp(),
+            expected.pp()
+        );
+        assert!((actual.stars() - expected.stars()).abs() <= f64::EPSILON);
+    };
+
+    // mods must be specified before the accuracy
+    let score = |map, mods| {
+        AnyPP::new(map)
+            .mods(mods)
+            .accuracy(Accuracy::from_percent(97.5).unwrap())
+            .n_misses(2)
+    };
+
+    for &(map, base) in &[(&osu, 8), (&taiko, 0), (&catch, 256), (&mania, 0)] {
+        let results = score(map, base).calculate_each_mod(&extra);
+        assert_eq!(results.len(), extra.len() + 1);
+        assert_same(&results[0], score(map, base).calculate(), base);
+
+        for (mods, result) in extra.iter().zip(&results[1..]) {
+            assert_same(result, score(map, base | mods).calculate(), base | mods);
+        }
+    }
+
+    // previously calculated attributes are reused for the base mods only
+    let attrs = OsuStars::new(&osu).mods(8).calculate();
+    let results = AnyPP::new(&osu)
+        .attributes(attrs)
+        .mods(8)
+        .accuracy(Accuracy::from_percent(99.0).unwrap())
+        .calculate_each_mod(&[16]);
+
+    let expected = AnyPP::new(&osu)
+        .mods(8 + 16)
+        .accuracy(Accuracy::from_percent(99.0).unwrap())
+        .calculate();
+    assert_same(&results[1], expected, 8 + 16);
+}
+
+#[test]
+fn max_pp_exceeds_score() {
+    let osu = test_map!(Osu);
+    let taiko = test_map!(Taiko);
+    let catch = test_map!(Catch);
+    let mania = test_map!(Mania);
+
+    let scores = vec![
+        AnyPP::new(&osu)
+            .mods(8)
+            .accuracy(Accuracy::from_percent(97.5).unwrap())
+            .combo(500)
+            .n_misses(2),
+        AnyPP::new(&taiko)
+            .mods(8)
+            .accuracy(Accuracy::from_percent(96.0).unwrap())
+            .n_misses(3),
+        AnyPP::new(&catch)
+            .mods(8)
+            .accuracy(Accuracy::from_percent(98.0).unwrap())
+            .n_misses(1),
+        AnyPP::new(&mania)
+            .mods(64)
+            .accuracy(Accuracy::from_percent(95.0).unwrap()),
+    ];
+
+    for score in scores {
+        let max = score.max_pp();
+        let actual = score.calculate();
+
+        assert!(max.pp() >= actual.pp(), "{} < {}", max.pp(), actual.pp());
+        assert!((max.stars() - actual.stars()).abs() <= f64::EPSILON);
+    }
+
+    let ss = OsuPP::new(&osu).mods(16).calculate();
+    let max = OsuPP::new(&osu)
+        .mods(16)
+        .accuracy(Accuracy::from_percent(90.0).unwrap())
+        .max_pp();
+    assert_eq!(ss, max);
+
+    let relax = akatsuki_pp::osu_2019::OsuPP::new(&osu)
+        .mods(128)
+        .accuracy(Accuracy::from_percent(97.0).unwrap())
+        .misses(1);
+
+    assert!(relax.max_pp().pp >= relax.calculate().pp);
+}
+
+#[test]
+fn accuracy_pp_by_od() {
+    let mut map = test_map!(Osu);
+
+    let mut acc_pp = |od| {
+        map.od = od;
+
+        let calculator = OsuPP::new(&map).n100(20).n50(2).n_misses(1);
+        let acc_pp = calculator.clone().acc_pp();
+
+        assert!((acc_pp - calculator.calculate().pp_acc).abs() <= f64::EPSILON);
+
+        acc_pp
+    };
+
+    let low = acc_pp(5.0);
+    let mid = acc_pp(8.0);
+    let high = acc_pp(10.0);
+
+    assert!(low < mid, "{} >= {}", low, mid);
+    assert!(mid < high, "{} >= {}", mid, high);
+}
+
+#[test]
+fn lazer_slider_head_accuracy() {
+    let map = test_map!(Osu);
+    let attrs = OsuStars::new(&map).calculate();
+    assert!(attrs.n_sliders > 0);
+
+    let calculator = OsuPP::new(&map).attributes(attrs).n100(20).n50(2);
+
+    let classic = calculator.clone().calculate();
+    let lazer = calculator.clone().lazer(true).calculate();
+
+    // hitresults are spread over circles and slider heads
+    assert!(
+        lazer.pp_acc > classic.pp_acc,
+        "{} <= {}",
+        lazer.pp_acc,
+        classic.pp_acc
+    );
+
+    // all slider ends and ticks are hit by default which raises the accuracy
+    assert!(lazer.pp_aim >= classic.pp_aim);
+    assert!(lazer.pp_speed >= classic.pp_speed);
+
+    let lazer_acc_pp = calculator.clone().lazer(true).acc_pp();
+    assert!((lazer_acc_pp - lazer.pp_acc).abs() <= f64::EPSILON);
+
+    let any = AnyPP::new(&map).n100(20).n50(2).lazer(true).calculate();
+    assert!((any.pp() - lazer.pp).abs() <= f64::EPSILON);
+}
+
+#[test]
+fn lazer_slider_end_accuracy() {
+    let map = test_map!(Osu);
+    let attrs = OsuStars::new(&map).calculate();
+    let calculator = OsuPP::new(&map)
+        .attributes(attrs.clone())
+        .n100(20)
+        .lazer(true);
+
+    let all_hit = calculator.clone().calculate();
+    let explicit = calculator
+        .clone()
+        .slider_end_hits(attrs.n_sliders)
+        .slider_tick_hits(usize::MAX)
+        .calculate();
+
+    assert_eq!(all_hit, explicit);
+
+    let dropped = calculator.clone().slider_end_hits(0).calculate();
+
+    assert!(dropped.pp_aim < all_hit.pp_aim);
+    assert!(dropped.pp < all_hit.pp);
+    assert!((dropped.pp_acc - all_hit.pp_acc).abs() <= f64::EPSILON);
+
+    // classic ignores slider ends
+    let classic = OsuPP::new(&map).attributes(attrs.clone()).n100(20);
+    assert_eq!(
+        classic.clone().calculate(),
+        classic.slider_end_hits(0).calculate()
+    );
+
+    // slider ends award combo in both clients
+    assert_eq!(dropped.difficulty.max_combo, attrs.max_combo);
+
+    let state = OsuScoreState {
+        max_combo: 0,
+        n300: 3,
+        n100: 1,
+        n50: 0,
+        n_misses: 0,
+        slider_end_hits: 1,
+        slider_tick_hits: 2,
+    };
+
+    let expected =
+        (300.0 * 3.0 + 100.0 + 150.0 + 30.0 * 2.0) / (300.0 * 4.0 + 150.0 * 2.0 + 30.0 * 2.0);
+    assert!((state.lazer_accuracy(2, 2) - expected).abs() <= f64::EPSILON);
+}
+
+#[test]
+fn acc_for_pp_round_trip() {
+    let map = test_map!(Osu);
+    let calculator = OsuPP::new(&map).mods(8);
+    let pp_for_acc = |acc| {
+        calculator
+            .clone()
+            .accuracy(Accuracy::from_percent(acc).unwrap())
+            .calculate()
+            .pp
+    };
+
+    for &acc in &[92.0, 96.5, 99.0] {
+        let target = pp_for_acc(acc);
+        let required = calculator.acc_for_pp(target).unwrap();
+
+        // Accuracies are rounded to hitresults so the estimate may be slightly off
+        assert!((acc - required).abs() < 0.2, "{} vs {}", required, acc);
+        assert!(pp_for_acc(required) >= target);
+    }
+
+    let max_pp = calculator.max_pp().pp;
+    assert!(calculator.acc_for_pp(max_pp + 1.0).is_none());
+    assert_eq!(calculator.acc_for_pp(0.0), Some(0.0));
+}
+
+#[test]
+fn hitresults_from_accuracy() {
+    for &n_objects in &[1, 7, 100, 1234] {
+        for &acc in &[50.0_f64, 66.6, 95.0, 98.76, 99.9, 100.0] {
+            let n_misses = n_objects / 50;
+            let tolerance = 0.5 / n_objects as f64;
+
+            let percent = Accuracy::from_percent(acc).unwrap();
+            let osu = OsuScoreState::from_accuracy(n_objects, percent, n_misses);
+            let taiko = TaikoScoreState::from_accuracy(n_objects, percent, n_misses);
+            let mania = ManiaScoreState::from_accuracy(n_objects, percent, n_misses);
+
+            assert_eq!(osu.total_hits(), n_objects);
+            assert_eq!(taiko.total_hits(), n_objects);
+            assert_eq!(mania.total_hits(), n_objects);
+
+            // the requested accuracy can't be reached with too many misses
+            let max_acc = (n_objects - n_misses) as f64 / n_objects as f64;
+            let expected = (acc / 100.0).min(max_acc);
+
+            for actual in [osu.accuracy(), taiko.accuracy(), mania.accuracy()] {
+                assert!(
+                    (actual - expected).abs() <= tolerance,
+                    "{} objects, {}%: {} vs {}",
+                    n_objects,
+                    acc,
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+
+    // as many 300s as possible while hitting the accuracy exactly
+    let osu = OsuScoreState::from_accuracy(100, Accuracy::from_percent(97.0).unwrap(), 1);
+    assert_eq!((osu.n300, osu.n100, osu.n50, osu.n_misses), (96, 3, 0, 1));
+
+    let map = test_map!(Catch);
+    let attrs = CatchStars::new(&map).calculate();
+    let catch = CatchScoreState::from_accuracy(&attrs, Accuracy::from_percent(98.5).unwrap(), 2);
+    let total = attrs.max_combo() + attrs.n_tiny_droplets;
+
+    assert_eq!(catch.total_hits(), total);
+    assert_eq!(catch.n_misses, 2);
+    assert!((catch.accuracy() - 0.985).abs() <= 0.5 / total as f64);
+}
+
+#[test]
+fn repeated_map_pp() {
+    let map = test_map!(Taiko);
+    let attrs = TaikoStars::new(&map).calculate();
+    let repeated = TaikoStars::new(&map).repeat(2).calculate();
+
+    assert_eq!(repeated.max_combo, 2 * attrs.max_combo);
+    assert!(repeated.stamina > attrs.stamina);
+
+    let pp = TaikoPP::new(&map).attributes(attrs).calculate().pp;
+    let repeated_pp = TaikoPP::new(&map).attributes(repeated).calculate().pp;
+
+    assert!(repeated_pp > pp, "{} <= {}", repeated_pp, pp);
+
+    let map = test_map!(Osu);
+    let attrs = OsuStars::new(&map).calculate();
+    let repeated = OsuStars::new(&map).repeat(2).calculate();
+
+    assert_eq!(repeated.n_circles, 2 * attrs.n_circles);
+    assert_eq!(repeated.max_combo, 2 * attrs.max_combo);
+    assert!(repeated.stars >= attrs.stars);
+
+    let map = test_map!(Catch);
+    let attrs = CatchStars::new(&map).calculate();
+    let repeated = CatchStars::new(&map).repeat(2).calculate();
+    assert_eq!(repeated.max_combo(), 2 * attrs.max_combo());
+
+    let map = test_map!(Mania);
+    let attrs = ManiaStars::new(&map).calculate();
+    let repeated = ManiaStars::new(&map).repeat(2).calculate();
+    assert_eq!(repeated.max_combo, 2 * attrs.max_combo);
+    assert!(repeated.stars >= attrs.stars);
+}
+
+#[test]
+fn catch_from_osu_keeps_accuracy() {
+    let map = test_map!(Catch);
+    let acc = Accuracy::from_percent(97.0).unwrap();
+
+    let converted = CatchPP::from(OsuPP::new(&map).accuracy(acc)).calculate();
+    let direct = CatchPP::new(&map).accuracy(acc).calculate();
+
+    assert!((converted.pp - direct.pp).abs() <= f64::EPSILON);
+}
@@ -0,0 +1,111 @@
+use std::{collections::HashMap, io};
+
+use akatsuki_pp::{calculate_by_id, AnyStars, BeatmapSource, SourceError};
+
+use crate::common::{Mode, Osu};
+
+mod common;
+
+struct MockSource {
+    maps: HashMap<u32, Vec<u8>>,
+}
+
+#[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+impl BeatmapSource for MockSource {
+    type Error = io::Error;
+
+    fn fetch(&self, map_id: u32) -> Result<Vec<u8>, Self::Error> {
+        self.maps
+            .get(&map_id)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "unknown map id"))
+    }
+}
+
+#[cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+#[test]
+fn calculate_through_source() {
+    let bytes = std::fs::read(format!("./maps/{}.osu", Osu::TEST_MAP_ID)).unwrap();
+
+    let source = MockSource {
+        maps: [(Osu::TEST_MAP_ID, bytes)].iter().cloned().collect(),
+    };
+
+    let attrs = calculate_by_id(Osu::TEST_MAP_ID, &source, 8 + 64).unwrap();
+    let expected = AnyStars::new(&test_map!(Osu)).mods(8 + 64).calculate();
+
+    assert!((attrs.stars() - expected.stars()).abs() <= f64::EPSILON);
+    assert_eq!(attrs.max_combo(), expected.max_combo());
+
+    assert!(matches!(
+        calculate_by_id(0, &source, 0),
+        Err(SourceError::Fetch(_))
+    ));
+
+    // closures are sources too
+    let garbage = |_| Ok::<_, io::Error>(b"not a map".to_vec());
+
+    assert!(matches!(
+        calculate_by_id(Osu::TEST_MAP_ID, &garbage, 0),
+        Err(SourceError::Parse(_))
+    ));
+}
+
+#[cfg(any(feature = "async_tokio", feature = "async_std"))]
+impl BeatmapSource for MockSource {
+    type Error = io::Error;
+
+    fn fetch(&self, map_id: u32) -> akatsuki_pp::FetchFuture<'_, Self::Error> {
+        Box::pin(async move {
+            self.maps
+                .get(&map_id)
+                .cloned()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "unknown map id"))
+        })
+    }
+}
+
+#[cfg(any(feature = "async_tokio", feature = "async_std"))]
+async fn assert_calculate_through_source() {
+    let bytes = std::fs::read(format!("./maps/{}.osu", Osu::TEST_MAP_ID)).unwrap();
+
+    let source = MockSource {
+        maps: [(Osu::TEST_MAP_ID, bytes)].iter().cloned().collect(),
+    };
+
+    let attrs = calculate_by_id(Osu::TEST_MAP_ID, &source, 8 + 64)
+        .await
+        .unwrap();
+    let expected = AnyStars::new(&test_map!(Osu)).mods(8 + 64).calculate();
+
+    assert!((attrs.stars() - expected.stars()).abs() <= f64::EPSILON);
+    assert_eq!(attrs.max_combo(), expected.max_combo());
+
+    assert!(matches!(
+        calculate_by_id(0, &source, 0).await,
+        Err(SourceError::Fetch(_))
+    ));
+
+    // closures returning futures are sources too
+    let garbage = |_| async { Ok::<_, io::Error>(b"not a map".to_vec()) };
+
+    assert!(matches!(
+        calculate_by_id(Osu::TEST_MAP_ID, &garbage, 0).await,
+        Err(SourceError::Parse(_))
+    ));
+}
+
+#[cfg(feature = "async_tokio")]
+#[test]
+fn calculate_through_source() {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(assert_calculate_through_source());
+}
+
+#[cfg(feature = "async_std")]
+#[test]
+fn calculate_through_source() {
+    async_std::task::block_on(assert_calculate_through_source());
+}
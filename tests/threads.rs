@@ -0,0 +1,57 @@
+#![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+
+use std::thread;
+
+use akatsuki_pp::{
+    AnyPP, AnyStars, Beatmap, CatchPP, CatchStars, ManiaPP, ManiaStars, OsuPP, OsuStars, TaikoPP,
+    TaikoStars,
+};
+
+use crate::common::Osu;
+
+mod common;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn assert_send<T: Send>() {}
+
+#[test]
+fn thread_safety() {
+    assert_send_sync::<Beatmap>();
+
+    assert_send::<OsuStars<'_>>();
+    assert_send::<TaikoStars<'_>>();
+    assert_send::<CatchStars<'_>>();
+    assert_send::<ManiaStars<'_>>();
+    assert_send::<AnyStars<'_>>();
+
+    assert_send::<OsuPP<'_>>();
+    assert_send::<TaikoPP<'_>>();
+    assert_send::<CatchPP<'_>>();
+    assert_send::<ManiaPP<'_>>();
+    assert_send::<AnyPP<'_>>();
+}
+
+#[test]
+fn shared_map() {
+    let map = test_map!(Osu);
+    let expected = OsuStars::new(&map).mods(64).calculate().stars;
+
+    let map = &map;
+
+    let results: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| scope.spawn(move || AnyPP::new(map).mods(64).calculate().stars()))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    assert_eq!(results.len(), 4);
+    assert!(results
+        .iter()
+        .all(|stars| (stars - expected).abs() <= f64::EPSILON));
+}
@@ -0,0 +1,155 @@
+#![cfg(not(any(feature = "async_tokio", feature = "async_std")))]
+
+use akatsuki_pp::{
+    beatmap::TimingPoint,
+    parse::{HitObject, HitObjectKind, Pos2},
+    Accuracy, AnyPP, AnyStars, Beatmap, BeatmapBuilder, CalcError, GameMode,
+};
+
+use crate::common::Osu;
+
+mod common;
+
+fn circles() -> Beatmap {
+    let circles = (0..10).map(|i| HitObject {
+        pos: Pos2 {
+            x: 50.0 * i as f32,
+            y: 100.0,
+        },
+        start_time: 1000.0 + i as f64 * 150.0,
+        kind: HitObjectKind::Circle,
+        new_combo: false,
+    });
+
+    BeatmapBuilder::new(GameMode::Osu)
+        .timing_point(TimingPoint::new(0.0, 300.0))
+        .hit_objects(circles)
+        .build()
+}
+
+#[test]
+fn valid_map() {
+    let map = test_map!(Osu);
+
+    let stars = AnyStars::new(&map).mods(8 + 64).try_calculate().unwrap();
+    let expected = AnyStars::new(&map).mods(8 + 64).calculate();
+    assert!((stars.stars() - expected.stars()).abs() <= f64::EPSILON);
+
+    let pp = AnyPP::new(&map)
+        .accuracy(Accuracy::from_percent(98.0).unwrap())
+        .try_calculate()
+        .unwrap();
+    let expected = AnyPP::new(&map)
+        .accuracy(Accuracy::from_percent(98.0).unwrap())
+        .calculate();
+    assert!((pp.pp() - expected.pp()).abs() <= f64::EPSILON);
+}
+
+#[test]
+fn infinite_start_time() {
+    let mut map = circles();
+    map.hit_objects[5].start_time = f64::INFINITY;
+
+    let res = AnyStars::new(&map).try_calculate();
+    assert!(matches!(res, Err(CalcError::InvalidHitObject { idx: 5 })));
+
+    for mode in [
+        GameMode::Osu,
+        GameMode::Taiko,
+        GameMode::Catch,
+        GameMode::Mania,
+    ] {
+        let res = AnyPP::new(&map).mode(mode).try_calculate();
+
+        assert!(
+            matches!(res, Err(CalcError::InvalidHitObject { idx: 5 })),
+            "{:?}: {:?}",
+            mode,
+            res
+        );
+    }
+}
+
+#[test]
+fn convert_does_not_unwind() {
+    let mut map = circles();
+    map.hp = 1e9;
+
+    for mode in [GameMode::Taiko, GameMode::Mania] {
+        let res = AnyStars::new(&map).mode(mode).try_calculate();
+
+        assert!(
+            !matches!(res, Err(CalcError::InvalidDifficulty(_))),
+            "{:?}: {:?}",
+            mode,
+            res
+        );
+
+        let res = AnyPP::new(&map).mode(mode).try_calculate();
+
+        assert!(
+            !matches!(res, Err(CalcError::InvalidDifficulty(_))),
+            "{:?}: {:?}",
+            mode,
+            res
+        );
+    }
+}
+
+#[test]
+fn non_finite_difficulty() {
+    let mut map = circles();
+    map.od = f32::INFINITY;
+
+    for mode in [
+        GameMode::Osu,
+        GameMode::Taiko,
+        GameMode::Catch,
+        GameMode::Mania,
+    ] {
+        let res = AnyStars::new(&map).mode(mode).try_calculate();
+
+        assert!(
+            matches!(res, Err(CalcError::InvalidDifficulty("OD"))),
+            "{:?}: {:?}",
+            mode,
+            res
+        );
+    }
+
+    let mut map = circles();
+    map.slider_mult = f64::NAN;
+
+    let res = AnyPP::new(&map).try_calculate();
+    assert!(matches!(
+        res,
+        Err(CalcError::InvalidDifficulty("slider multiplier"))
+    ));
+}
+
+#[test]
+fn slider_without_control_points() {
+    let mut map = circles();
+
+    map.hit_objects[2].kind = HitObjectKind::Slider {
+        pixel_len: Some(100.0),
+        repeats: 0,
+        control_points: Vec::new(),
+        edge_sounds: Vec::new(),
+    };
+
+    let res = AnyStars::new(&map).mode(GameMode::Catch).try_calculate();
+    assert!(matches!(res, Err(CalcError::InvalidHitObject { idx: 2 })));
+
+    let res = AnyPP::new(&map).try_calculate();
+    assert!(matches!(res, Err(CalcError::InvalidHitObject { idx: 2 })));
+}
+
+#[test]
+fn nan_position() {
+    let mut map = circles();
+    map.hit_objects[7].pos.y = f32::NAN;
+
+    let res = AnyPP::new(&map).try_calculate();
+    assert!(matches!(res, Err(CalcError::InvalidHitObject { idx: 7 })));
+}